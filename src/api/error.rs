@@ -0,0 +1,47 @@
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+use thiserror::Error;
+
+use crate::{error::Error, indexer::IndexerError};
+
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error(transparent)]
+    FetchFailed(#[from] Error),
+
+    #[error(transparent)]
+    DatabaseFailed(#[from] IndexerError),
+
+    #[error("{0} not found")]
+    NotFound(String),
+
+    #[error("Invalid {name}: {value}")]
+    InvalidParameter { name: &'static str, value: String },
+
+    #[error("Querying {0} requires an indexer database, start the server with --database-url")]
+    IndexerRequired(&'static str),
+}
+
+impl From<sqlx::Error> for ApiError {
+    fn from(error: sqlx::Error) -> Self {
+        ApiError::DatabaseFailed(IndexerError::Database(error))
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            ApiError::FetchFailed(_) | ApiError::DatabaseFailed(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::InvalidParameter { .. } => StatusCode::BAD_REQUEST,
+            ApiError::IndexerRequired(_) => StatusCode::NOT_IMPLEMENTED,
+        };
+        (status, Json(json!({ "error": self.to_string() }))).into_response()
+    }
+}
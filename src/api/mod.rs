@@ -0,0 +1,4 @@
+pub mod error;
+pub mod models;
+pub mod server;
+pub mod store;
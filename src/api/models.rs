@@ -0,0 +1,132 @@
+use serde::Serialize;
+
+use crate::indexer::block::IndexedBlock;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Block {
+    pub number: u32,
+    pub hash: String,
+    pub parent_hash: String,
+    pub state_root: String,
+    pub extrinsics_root: String,
+    pub spec_version: u64,
+    /// RFC 3339 timestamp
+    pub timestamp: String,
+    pub author: Option<String>,
+    pub finalized: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Extrinsic {
+    /// `{block}-{index}`, as in the extrinsics table
+    pub id: String,
+    pub block_number: u32,
+    pub index: u32,
+    pub hash: String,
+    pub pallet: String,
+    pub call: String,
+    pub signer: Option<String>,
+    pub success: Option<bool>,
+    pub finalized: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Event {
+    /// `{block}-{index}`, as in the events table
+    pub id: String,
+    pub block_number: u32,
+    pub index: u32,
+    pub extrinsic_id: Option<String>,
+    pub pallet: String,
+    pub name: String,
+    pub params: String,
+    pub finalized: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Transfer {
+    pub event_id: String,
+    pub block_number: u32,
+    pub extrinsic_id: Option<String>,
+    pub sender: String,
+    pub receiver: String,
+    /// Decimal string, `u128` amounts don't fit JSON numbers
+    pub amount: String,
+    pub finalized: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockDetails {
+    #[serde(flatten)]
+    pub block: Block,
+    pub extrinsics: Vec<Extrinsic>,
+    pub events: Vec<Event>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtrinsicDetails {
+    #[serde(flatten)]
+    pub extrinsic: Extrinsic,
+    pub events: Vec<Event>,
+}
+
+/// Extrinsics signed by an account and transfers from or to it, latest first
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountHistory {
+    pub address: String,
+    pub extrinsics: Vec<Extrinsic>,
+    pub transfers: Vec<Transfer>,
+}
+
+impl From<IndexedBlock> for BlockDetails {
+    fn from(indexed_block: IndexedBlock) -> Self {
+        let IndexedBlock {
+            finalized,
+            block,
+            extrinsics,
+            events,
+            ..
+        } = indexed_block;
+
+        BlockDetails {
+            extrinsics: extrinsics
+                .into_iter()
+                .map(|extrinsic| Extrinsic {
+                    id: extrinsic.id,
+                    block_number: block.number,
+                    index: extrinsic.index,
+                    hash: extrinsic.hash,
+                    pallet: extrinsic.pallet,
+                    call: extrinsic.call,
+                    signer: extrinsic.signer,
+                    success: extrinsic.success,
+                    finalized,
+                })
+                .collect(),
+            events: events
+                .into_iter()
+                .map(|event| Event {
+                    id: event.id,
+                    block_number: block.number,
+                    index: event.index,
+                    extrinsic_id: event.extrinsic_id,
+                    pallet: event.pallet,
+                    name: event.name,
+                    params: event.params,
+                    finalized,
+                })
+                .collect(),
+            block: Block {
+                number: block.number,
+                hash: block.hash.0,
+                parent_hash: block.parent_hash.0,
+                state_root: block.state_root,
+                extrinsics_root: block.extrinsics_root,
+                spec_version: block.spec_version,
+                timestamp: block.timestamp,
+                author: block.author,
+                finalized,
+            },
+        }
+    }
+}
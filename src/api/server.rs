@@ -0,0 +1,406 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    routing::get,
+};
+use ethereum_types::H160;
+use serde::Deserialize;
+use sp_runtime::{AccountId32, app_crypto::Ss58Codec};
+use ss58::Ss58AddressFormat;
+use tracing::info;
+
+use crate::{
+    api::{
+        error::ApiError,
+        models::{AccountHistory, BlockDetails, Event, ExtrinsicDetails},
+        store::{ApiStore, BlockId, EventFilter},
+    },
+    error::Error,
+    fetch,
+    indexer::block::decode_block,
+    node_rpc::{
+        client::NodeRPC,
+        models::{BlockHashHex, BlockHeader, BlockNumberHex},
+    },
+    pallets::ethereum::address::checksum_address,
+};
+
+/// Page size of the list queries without a `limit` parameter
+const DEFAULT_PAGE_LIMIT: u32 = 100;
+
+/// Largest page size of the list queries
+const MAX_PAGE_LIMIT: u32 = 1000;
+
+/// Serves the indexed records, blocks missing from the indexer database or served without one are
+/// decoded from the node on request
+pub struct ApiContext {
+    rpc: NodeRPC,
+    store: Option<ApiStore>,
+    address_format: Ss58AddressFormat,
+}
+
+#[derive(Debug, Deserialize)]
+struct PageParams {
+    limit: Option<u32>,
+    offset: Option<u32>,
+}
+
+impl PageParams {
+    fn limit(&self) -> u32 {
+        self.limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT)
+    }
+
+    fn offset(&self) -> u32 {
+        self.offset.unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct EventParams {
+    pallet: Option<String>,
+    name: Option<String>,
+    block: Option<u32>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+}
+
+fn header_block_number(header: &BlockHeader) -> Result<u32, Error> {
+    u32::try_from(&header.number)
+        .map_err(|err| Error::ParsingBlockNumberFailed(header.number.clone(), err))
+}
+
+fn parse_block_id(block_id: &str) -> Result<BlockId, ApiError> {
+    let invalid = || ApiError::InvalidParameter {
+        name: "block",
+        value: block_id.to_string(),
+    };
+
+    match block_id.strip_prefix("0x") {
+        Some(hash) if hash.len() == 64 && hash.bytes().all(|byte| byte.is_ascii_hexdigit()) => {
+            Ok(BlockId::Hash(block_id.to_lowercase()))
+        }
+        Some(_) => Err(invalid()),
+        None => block_id.parse().map(BlockId::Number).map_err(|_| invalid()),
+    }
+}
+
+/// Block number of a `{block}-{index}` extrinsic id
+fn parse_extrinsic_id(extrinsic_id: &str) -> Result<u32, ApiError> {
+    extrinsic_id
+        .split_once('-')
+        .and_then(|(block_number, index)| {
+            index.parse::<u32>().ok()?;
+            block_number.parse().ok()
+        })
+        .ok_or_else(|| ApiError::InvalidParameter {
+            name: "extrinsic id",
+            value: extrinsic_id.to_string(),
+        })
+}
+
+/// Re-encode an SS58 address of any network or a `0x` prefixed public key in the chain's address
+/// format, and 20 bytes accounts of Frontier chains with their EIP-55 checksum, as the indexer
+/// stores them
+fn normalize_address(address: &str, address_format: Ss58AddressFormat) -> Result<String, ApiError> {
+    let normalized = match address.strip_prefix("0x").map(hex::decode) {
+        Some(Ok(public_key)) => match public_key.len() {
+            32 => Some(ss58::encode(
+                &public_key.try_into().unwrap(),
+                address_format,
+            )),
+            20 => Some(checksum_address(&H160::from_slice(&public_key))),
+            _ => None,
+        },
+        Some(Err(_)) => None,
+        None => AccountId32::from_ss58check_with_version(address)
+            .ok()
+            .map(|(account_id, _)| ss58::encode(&account_id.into(), address_format)),
+    };
+
+    normalized.ok_or_else(|| ApiError::InvalidParameter {
+        name: "address",
+        value: address.to_string(),
+    })
+}
+
+impl ApiContext {
+    /// Decode a block from the node, `None` for block numbers above the best block
+    async fn decode_live_block(&self, block_id: &BlockId) -> Result<Option<BlockDetails>, Error> {
+        let (block_number, block_hash) = match block_id {
+            BlockId::Number(number) => {
+                let best_number = header_block_number(&self.rpc.chain_get_best_header().await?)?;
+                if *number > best_number {
+                    return Ok(None);
+                }
+                let block_hash = self
+                    .rpc
+                    .chain_get_block_hash(&BlockNumberHex::from(*number))
+                    .await?;
+                (*number, block_hash)
+            }
+            BlockId::Hash(hash) => {
+                let block_hash = BlockHashHex(hash.clone());
+                let Some(header) = self
+                    .rpc
+                    .chain_get_header_optional(block_hash.clone())
+                    .await?
+                else {
+                    return Ok(None);
+                };
+                (header_block_number(&header)?, block_hash)
+            }
+        };
+
+        let finalized_hash = self.rpc.chain_get_finalized_head().await?;
+        let finalized_number =
+            header_block_number(&self.rpc.chain_get_header(finalized_hash).await?)?;
+
+        let indexed_block = decode_block(
+            &self.rpc,
+            block_number,
+            block_hash,
+            block_number <= finalized_number,
+            self.address_format,
+        )
+        .await?;
+        Ok(Some(indexed_block.into()))
+    }
+
+    /// Block with its extrinsics and events, from the indexer database or else from the node
+    async fn block_details(&self, block_id: &BlockId) -> Result<Option<BlockDetails>, ApiError> {
+        if let Some(store) = &self.store
+            && let Some(block) = store.block(block_id).await?
+        {
+            let extrinsics = store.block_extrinsics(block.number).await?;
+            let events = store.block_events(block.number).await?;
+            return Ok(Some(BlockDetails {
+                block,
+                extrinsics,
+                events,
+            }));
+        }
+
+        Ok(self.decode_live_block(block_id).await?)
+    }
+}
+
+/// `GET /blocks/{block}`, by block number or `0x` prefixed block hash
+async fn get_block(
+    State(context): State<Arc<ApiContext>>,
+    Path(block_id): Path<String>,
+) -> Result<Json<BlockDetails>, ApiError> {
+    let block_id = parse_block_id(&block_id)?;
+    context
+        .block_details(&block_id)
+        .await?
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("Block {block_id}")))
+}
+
+/// `GET /extrinsics/{extrinsic}`, by `{block}-{index}` id or `0x` prefixed extrinsic hash
+///
+/// Extrinsic hashes are only looked up in the indexer database, the node has no index of them.
+async fn get_extrinsic(
+    State(context): State<Arc<ApiContext>>,
+    Path(extrinsic_id): Path<String>,
+) -> Result<Json<ExtrinsicDetails>, ApiError> {
+    let not_found = || ApiError::NotFound(format!("Extrinsic {extrinsic_id}"));
+
+    if extrinsic_id.starts_with("0x") {
+        let store = context
+            .store
+            .as_ref()
+            .ok_or(ApiError::IndexerRequired("extrinsics by hash"))?;
+        let extrinsic = store
+            .extrinsic_by_hash(&extrinsic_id.to_lowercase())
+            .await?
+            .ok_or_else(not_found)?;
+        let events = store.extrinsic_events(&extrinsic.id).await?;
+        return Ok(Json(ExtrinsicDetails { extrinsic, events }));
+    }
+
+    let block_number = parse_extrinsic_id(&extrinsic_id)?;
+    if let Some(store) = &context.store
+        && let Some(extrinsic) = store.extrinsic(&extrinsic_id).await?
+    {
+        let events = store.extrinsic_events(&extrinsic.id).await?;
+        return Ok(Json(ExtrinsicDetails { extrinsic, events }));
+    }
+
+    let block = context
+        .decode_live_block(&BlockId::Number(block_number))
+        .await?
+        .ok_or_else(not_found)?;
+    let extrinsic = block
+        .extrinsics
+        .into_iter()
+        .find(|extrinsic| extrinsic.id == extrinsic_id)
+        .ok_or_else(not_found)?;
+    let events = block
+        .events
+        .into_iter()
+        .filter(|event| event.extrinsic_id.as_ref() == Some(&extrinsic.id))
+        .collect();
+    Ok(Json(ExtrinsicDetails { extrinsic, events }))
+}
+
+/// `GET /events?pallet=&name=&block=&limit=&offset=`, latest first
+///
+/// Without an indexer database the events of a single block are decoded from the node.
+async fn get_events(
+    State(context): State<Arc<ApiContext>>,
+    Query(params): Query<EventParams>,
+) -> Result<Json<Vec<Event>>, ApiError> {
+    let page = PageParams {
+        limit: params.limit,
+        offset: params.offset,
+    };
+    let filter = EventFilter {
+        pallet: params.pallet,
+        name: params.name,
+        block_number: params.block,
+    };
+
+    // A block missing from the database is decoded from the node
+    if let Some(store) = &context.store {
+        let indexed = match filter.block_number {
+            Some(block_number) => store.block(&BlockId::Number(block_number)).await?.is_some(),
+            None => true,
+        };
+        if indexed {
+            let events = store.events(&filter, page.limit(), page.offset()).await?;
+            return Ok(Json(events));
+        }
+    }
+
+    let block_number = filter
+        .block_number
+        .ok_or(ApiError::IndexerRequired("events across blocks"))?;
+    let block = context
+        .decode_live_block(&BlockId::Number(block_number))
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Block {block_number}")))?;
+
+    let events = block
+        .events
+        .into_iter()
+        .rev()
+        .filter(|event| {
+            filter
+                .pallet
+                .as_ref()
+                .is_none_or(|pallet| &event.pallet == pallet)
+                && filter.name.as_ref().is_none_or(|name| &event.name == name)
+        })
+        .skip(page.offset() as usize)
+        .take(page.limit() as usize)
+        .collect();
+    Ok(Json(events))
+}
+
+/// `GET /accounts/{address}/history?limit=&offset=`, extrinsics signed by the SS58 address or
+/// public key and transfers from or to it
+async fn get_account_history(
+    State(context): State<Arc<ApiContext>>,
+    Path(address): Path<String>,
+    Query(params): Query<PageParams>,
+) -> Result<Json<AccountHistory>, ApiError> {
+    let store = context
+        .store
+        .as_ref()
+        .ok_or(ApiError::IndexerRequired("account history"))?;
+    let address = normalize_address(&address, context.address_format)?;
+
+    let extrinsics = store
+        .account_extrinsics(&address, params.limit(), params.offset())
+        .await?;
+    let transfers = store
+        .account_transfers(&address, params.limit(), params.offset())
+        .await?;
+
+    Ok(Json(AccountHistory {
+        address,
+        extrinsics,
+        transfers,
+    }))
+}
+
+fn router(context: ApiContext) -> Router {
+    Router::new()
+        .route("/blocks/{block}", get(get_block))
+        .route("/extrinsics/{extrinsic}", get(get_extrinsic))
+        .route("/events", get(get_events))
+        .route("/accounts/{address}/history", get(get_account_history))
+        .with_state(Arc::new(context))
+}
+
+/// Serve the REST API until the server stops
+pub async fn serve(
+    rpc: NodeRPC,
+    store: Option<ApiStore>,
+    listen_address: SocketAddr,
+) -> Result<(), Error> {
+    let address_format = fetch::ss58_address_format(&rpc).await?;
+    let context = ApiContext {
+        rpc,
+        store,
+        address_format,
+    };
+
+    let listener = tokio::net::TcpListener::bind(listen_address)
+        .await
+        .map_err(Error::ApiServerFailed)?;
+    let local_address = listener.local_addr().map_err(Error::ApiServerFailed)?;
+    info!("REST API server listening on {local_address}");
+
+    axum::serve(listener, router(context))
+        .await
+        .map_err(Error::ApiServerFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Alice's public key, as a Polkadot and a generic Substrate address
+    const ALICE_PUBLIC_KEY: &str =
+        "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+    const ALICE_POLKADOT: &str = "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5";
+    const ALICE_SUBSTRATE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    #[test]
+    fn addresses_are_reencoded_in_the_chain_format() {
+        for address in [ALICE_PUBLIC_KEY, ALICE_POLKADOT, ALICE_SUBSTRATE] {
+            assert_eq!(
+                normalize_address(address, Ss58AddressFormat::Polkadot).unwrap(),
+                ALICE_POLKADOT
+            );
+        }
+
+        // `AccountId20` of Frontier chains
+        assert_eq!(
+            normalize_address(
+                "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+                Ss58AddressFormat::Polkadot
+            )
+            .unwrap(),
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        );
+
+        // Corrupted checksum and truncated public key
+        for address in [
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ",
+            "0xd43593c7",
+        ] {
+            assert!(matches!(
+                normalize_address(address, Ss58AddressFormat::Polkadot),
+                Err(ApiError::InvalidParameter {
+                    name: "address",
+                    ..
+                })
+            ));
+        }
+    }
+}
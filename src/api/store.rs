@@ -0,0 +1,321 @@
+use derive_more::Display;
+use sqlx::{AnyPool, FromRow, any::AnyRow};
+
+use crate::{
+    api::{
+        error::ApiError,
+        models::{Block, Event, Extrinsic, Transfer},
+    },
+    indexer::{IndexerError, connect_pool, schema},
+};
+
+// Booleans are read as integers, the Any driver rejects the BOOLEAN columns of SQLite
+const BLOCK_COLUMNS: &str = "number, hash, parent_hash, state_root, extrinsics_root, spec_version,
+    timestamp, author, CASE WHEN finalized THEN 1 ELSE 0 END";
+const EXTRINSIC_COLUMNS: &str = "id, block_number, extrinsic_index, hash, pallet, call, signer,
+    CASE WHEN success IS NULL THEN NULL WHEN success THEN 1 ELSE 0 END,
+    CASE WHEN finalized THEN 1 ELSE 0 END";
+const EVENT_COLUMNS: &str = "id, block_number, event_index, extrinsic_id, pallet, name, params,
+    CASE WHEN finalized THEN 1 ELSE 0 END";
+const TRANSFER_COLUMNS: &str = "event_id, block_number, extrinsic_id, sender, receiver, amount,
+    CASE WHEN finalized THEN 1 ELSE 0 END";
+
+/// Block lookup key, a block number or a `0x` prefixed block hash
+#[derive(Debug, Clone, Display)]
+pub enum BlockId {
+    #[display("{_0}")]
+    Number(u32),
+    #[display("{_0}")]
+    Hash(String),
+}
+
+/// Filter of the events query, every set field must match
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    pub pallet: Option<String>,
+    pub name: Option<String>,
+    pub block_number: Option<u32>,
+}
+
+/// Read queries over the indexer tables
+#[derive(Debug, Clone)]
+pub struct ApiStore {
+    pool: AnyPool,
+}
+
+impl ApiStore {
+    /// Connect to an indexer database without migrating it, the indexer owns the schema
+    pub async fn connect(database_url: &str) -> Result<Self, IndexerError> {
+        let pool = connect_pool(database_url).await?;
+        schema::check_version(&pool).await?;
+
+        Ok(Self { pool })
+    }
+}
+
+impl<'r> FromRow<'r, AnyRow> for Block {
+    fn from_row(row: &'r AnyRow) -> Result<Self, sqlx::Error> {
+        let (
+            number,
+            hash,
+            parent_hash,
+            state_root,
+            extrinsics_root,
+            spec_version,
+            timestamp,
+            author,
+            finalized,
+        ): (
+            i64,
+            String,
+            String,
+            String,
+            String,
+            i64,
+            String,
+            Option<String>,
+            i64,
+        ) = FromRow::from_row(row)?;
+
+        Ok(Block {
+            number: number as u32,
+            hash,
+            parent_hash,
+            state_root,
+            extrinsics_root,
+            spec_version: spec_version as u64,
+            timestamp,
+            author,
+            finalized: finalized != 0,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, AnyRow> for Extrinsic {
+    fn from_row(row: &'r AnyRow) -> Result<Self, sqlx::Error> {
+        let (id, block_number, index, hash, pallet, call, signer, success, finalized): (
+            String,
+            i64,
+            i64,
+            String,
+            String,
+            String,
+            Option<String>,
+            Option<i64>,
+            i64,
+        ) = FromRow::from_row(row)?;
+
+        Ok(Extrinsic {
+            id,
+            block_number: block_number as u32,
+            index: index as u32,
+            hash,
+            pallet,
+            call,
+            signer,
+            success: success.map(|success| success != 0),
+            finalized: finalized != 0,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, AnyRow> for Event {
+    fn from_row(row: &'r AnyRow) -> Result<Self, sqlx::Error> {
+        let (id, block_number, index, extrinsic_id, pallet, name, params, finalized): (
+            String,
+            i64,
+            i64,
+            Option<String>,
+            String,
+            String,
+            String,
+            i64,
+        ) = FromRow::from_row(row)?;
+
+        Ok(Event {
+            id,
+            block_number: block_number as u32,
+            index: index as u32,
+            extrinsic_id,
+            pallet,
+            name,
+            params,
+            finalized: finalized != 0,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, AnyRow> for Transfer {
+    fn from_row(row: &'r AnyRow) -> Result<Self, sqlx::Error> {
+        let (event_id, block_number, extrinsic_id, sender, receiver, amount, finalized): (
+            String,
+            i64,
+            Option<String>,
+            String,
+            String,
+            String,
+            i64,
+        ) = FromRow::from_row(row)?;
+
+        Ok(Transfer {
+            event_id,
+            block_number: block_number as u32,
+            extrinsic_id,
+            sender,
+            receiver,
+            amount,
+            finalized: finalized != 0,
+        })
+    }
+}
+
+impl ApiStore {
+    pub async fn block(&self, block_id: &BlockId) -> Result<Option<Block>, ApiError> {
+        let block = match block_id {
+            BlockId::Number(number) => {
+                sqlx::query_as(&format!(
+                    "SELECT {BLOCK_COLUMNS} FROM blocks WHERE number = $1"
+                ))
+                .bind(*number as i64)
+                .fetch_optional(&self.pool)
+                .await?
+            }
+            BlockId::Hash(hash) => {
+                sqlx::query_as(&format!(
+                    "SELECT {BLOCK_COLUMNS} FROM blocks WHERE hash = $1"
+                ))
+                .bind(hash)
+                .fetch_optional(&self.pool)
+                .await?
+            }
+        };
+
+        Ok(block)
+    }
+
+    pub async fn block_extrinsics(&self, block_number: u32) -> Result<Vec<Extrinsic>, ApiError> {
+        Ok(sqlx::query_as(&format!(
+            "SELECT {EXTRINSIC_COLUMNS} FROM extrinsics WHERE block_number = $1
+            ORDER BY extrinsic_index"
+        ))
+        .bind(block_number as i64)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    pub async fn block_events(&self, block_number: u32) -> Result<Vec<Event>, ApiError> {
+        Ok(sqlx::query_as(&format!(
+            "SELECT {EVENT_COLUMNS} FROM events WHERE block_number = $1 ORDER BY event_index"
+        ))
+        .bind(block_number as i64)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Extrinsic by `{block}-{index}` id
+    pub async fn extrinsic(&self, id: &str) -> Result<Option<Extrinsic>, ApiError> {
+        Ok(sqlx::query_as(&format!(
+            "SELECT {EXTRINSIC_COLUMNS} FROM extrinsics WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?)
+    }
+
+    /// Latest extrinsic with a hash, the same extrinsic bytes may be included in several blocks
+    pub async fn extrinsic_by_hash(&self, hash: &str) -> Result<Option<Extrinsic>, ApiError> {
+        Ok(sqlx::query_as(&format!(
+            "SELECT {EXTRINSIC_COLUMNS} FROM extrinsics WHERE hash = $1
+            ORDER BY block_number DESC LIMIT 1"
+        ))
+        .bind(hash)
+        .fetch_optional(&self.pool)
+        .await?)
+    }
+
+    pub async fn extrinsic_events(&self, extrinsic_id: &str) -> Result<Vec<Event>, ApiError> {
+        Ok(sqlx::query_as(&format!(
+            "SELECT {EVENT_COLUMNS} FROM events WHERE extrinsic_id = $1 ORDER BY event_index"
+        ))
+        .bind(extrinsic_id)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Events matching the filter, latest first
+    pub async fn events(
+        &self,
+        filter: &EventFilter,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<Event>, ApiError> {
+        let mut conditions = Vec::new();
+        if filter.pallet.is_some() {
+            conditions.push(format!("pallet = ${}", conditions.len() + 1));
+        }
+        if filter.name.is_some() {
+            conditions.push(format!("name = ${}", conditions.len() + 1));
+        }
+        if filter.block_number.is_some() {
+            conditions.push(format!("block_number = ${}", conditions.len() + 1));
+        }
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+
+        let sql = format!(
+            "SELECT {EVENT_COLUMNS} FROM events {where_clause}
+            ORDER BY block_number DESC, event_index DESC LIMIT {limit} OFFSET {offset}"
+        );
+        let mut query = sqlx::query_as(&sql);
+        if let Some(pallet) = &filter.pallet {
+            query = query.bind(pallet);
+        }
+        if let Some(name) = &filter.name {
+            query = query.bind(name);
+        }
+        if let Some(block_number) = filter.block_number {
+            query = query.bind(block_number as i64);
+        }
+
+        Ok(query.fetch_all(&self.pool).await?)
+    }
+
+    /// Extrinsics signed by an account, latest first
+    pub async fn account_extrinsics(
+        &self,
+        address: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<Extrinsic>, ApiError> {
+        Ok(sqlx::query_as(&format!(
+            "SELECT {EXTRINSIC_COLUMNS} FROM extrinsics WHERE signer = $1
+            ORDER BY block_number DESC, extrinsic_index DESC LIMIT {limit} OFFSET {offset}"
+        ))
+        .bind(address)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Transfers from or to an account, latest first
+    ///
+    /// Event ids of a block share their prefix, ordering by length first sorts their indices
+    /// numerically.
+    pub async fn account_transfers(
+        &self,
+        address: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<Transfer>, ApiError> {
+        Ok(sqlx::query_as(&format!(
+            "SELECT {TRANSFER_COLUMNS} FROM transfers WHERE sender = $1 OR receiver = $1
+            ORDER BY block_number DESC, LENGTH(event_id) DESC, event_id DESC
+            LIMIT {limit} OFFSET {offset}"
+        ))
+        .bind(address)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+}
@@ -0,0 +1,138 @@
+use derive_more::Display;
+use sp_runtime::DigestItem;
+
+use crate::{
+    decoder::metadata::AnyRuntimeMetadata,
+    error::Error,
+    fetch::fetch_metadata,
+    node_rpc::{
+        client::NodeRPC,
+        models::{BlockHashHex, RuntimeVersion},
+    },
+    pallets::{
+        aura::{self, digest::AURA_KEY_TYPE_ID},
+        babe::{self, digest::BABE_KEY_TYPE_ID},
+        session,
+    },
+};
+
+/// Where the block author was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum AuthorSource {
+    #[display("Session.KeyOwner")]
+    SessionKeyOwner,
+    /// The session key has no known owner, the author is its consensus authority key
+    #[display("authority key")]
+    AuthorityKey,
+}
+
+#[derive(Debug, Clone)]
+pub struct BlockAuthor {
+    pub account_id: [u8; 32],
+    pub source: AuthorSource,
+}
+
+/// Resolve the block author from the consensus authority that claimed the slot, mapped back to its
+/// validator with `Session.KeyOwner`
+///
+/// `Authorship.Author` can't be used, the pallet clears it when the block is finalized.
+///
+/// Authority indices refer to the authority set of the slot's epoch, read from the parent block
+/// state. The first block of a BABE epoch enacts the new authority set, so its own state is used,
+/// as for the genesis block which has no parent. Blocks without a slot claim have no author.
+pub async fn find_block_author(
+    rpc: &NodeRPC,
+    digest_logs: &[DigestItem],
+    block_hash: &BlockHashHex,
+    parent_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<Option<BlockAuthor>, Error> {
+    let babe_slot_claim =
+        babe::digest::find_slot_claim(digest_logs).map_err(Error::ParsingDigestLogsFailed)?;
+    let aura_slot = aura::digest::find_slot(digest_logs).map_err(Error::ParsingDigestLogsFailed)?;
+    if babe_slot_claim.is_none() && aura_slot.is_none() {
+        return Ok(None);
+    }
+
+    let parent_hash = if parent_hash.is_zero() {
+        block_hash
+    } else {
+        parent_hash
+    };
+    let parent_runtime_version = if parent_hash == block_hash {
+        runtime_version.clone()
+    } else {
+        rpc.state_get_runtime_version(parent_hash).await?
+    };
+    let parent_metadata = if parent_runtime_version.spec_version == runtime_version.spec_version {
+        None
+    } else {
+        Some(fetch_metadata(rpc, parent_hash).await?)
+    };
+    let parent_metadata = parent_metadata
+        .as_ref()
+        .map(AnyRuntimeMetadata)
+        .unwrap_or(metadata);
+
+    let (key_type_id, public_key) = if let Some(babe_slot_claim) = babe_slot_claim {
+        let epoch_index =
+            babe::fetch_epoch_index(rpc, block_hash, metadata, runtime_version).await?;
+        let parent_epoch_index =
+            babe::fetch_epoch_index(rpc, parent_hash, parent_metadata, &parent_runtime_version)
+                .await?;
+
+        let authorities = if epoch_index == parent_epoch_index {
+            babe::fetch_authorities(rpc, parent_hash, parent_metadata, &parent_runtime_version)
+                .await?
+        } else {
+            babe::fetch_authorities(rpc, block_hash, metadata, runtime_version).await?
+        };
+
+        let authority_index = babe_slot_claim.authority_index;
+        let authority = authorities
+            .get(authority_index as usize)
+            .ok_or(Error::ValidatorNotFoundForIndex(authority_index))?;
+        (BABE_KEY_TYPE_ID, authority.0)
+    } else if let Some(slot) = aura_slot {
+        let authorities =
+            aura::fetch_authorities(rpc, parent_hash, parent_metadata, &parent_runtime_version)
+                .await?;
+        if authorities.is_empty() {
+            return Ok(None);
+        }
+
+        let authority_index = slot % authorities.len() as u64;
+        (AURA_KEY_TYPE_ID, authorities[authority_index as usize].0)
+    } else {
+        return Ok(None);
+    };
+
+    let key_owner = if parent_metadata
+        .pallet_names()?
+        .contains(session::fetch::PALLET_NAME)
+    {
+        session::fetch_key_owner(
+            rpc,
+            key_type_id,
+            &public_key,
+            parent_hash,
+            parent_metadata,
+            &parent_runtime_version,
+        )
+        .await?
+    } else {
+        None
+    };
+
+    Ok(Some(match key_owner {
+        Some(key_owner) => BlockAuthor {
+            account_id: key_owner.0,
+            source: AuthorSource::SessionKeyOwner,
+        },
+        None => BlockAuthor {
+            account_id: public_key,
+            source: AuthorSource::AuthorityKey,
+        },
+    }))
+}
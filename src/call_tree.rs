@@ -0,0 +1,425 @@
+use scale_value::{Composite, Value, ValueDef, Variant};
+
+use crate::pallets::system::decoder::Event;
+
+/// A call and the `RuntimeCall`s nested in its arguments
+#[derive(Debug, Clone)]
+pub struct CallTree {
+    pub pallet_name: String,
+    pub call_name: String,
+    pub calls: Vec<CallTree>,
+}
+
+/// Arguments holding nested calls, by pallet and call name
+fn nested_call_args(pallet_name: &str, call_name: &str) -> &'static [&'static str] {
+    match (pallet_name, call_name) {
+        ("Utility", "batch" | "batch_all" | "force_batch") => &["calls"],
+        ("Proxy", "proxy" | "proxy_announced") => &["call"],
+        ("Multisig", "as_multi" | "as_multi_threshold_1") => &["call"],
+        ("Sudo", "sudo" | "sudo_as" | "sudo_unchecked_weight") => &["call"],
+        (
+            "Scheduler",
+            "schedule" | "schedule_named" | "schedule_after" | "schedule_named_after",
+        ) => &["call"],
+        _ => &[],
+    }
+}
+
+/// `Utility` events emitted by batches, closing a batch item or the batch itself
+const BATCH_EVENTS: &[&str] = &[
+    "ItemCompleted",
+    "ItemFailed",
+    "BatchCompleted",
+    "BatchCompletedWithErrors",
+    "BatchInterrupted",
+];
+
+/// `Utility` events closing a batch, every batch which ran emits exactly one of them
+const BATCH_CLOSING_EVENTS: &[&str] = &[
+    "BatchCompleted",
+    "BatchCompletedWithErrors",
+    "BatchInterrupted",
+];
+
+fn is_utility_event(event: &Event, actions: &[&str]) -> bool {
+    event.name == "Utility" && actions.contains(&event.action.as_str())
+}
+
+impl CallTree {
+    /// Build the tree of a call from its decoded arguments
+    pub fn new<T>(pallet_name: &str, call_name: &str, args: Vec<(String, Value<T>)>) -> Self {
+        let nested_call_args = nested_call_args(pallet_name, call_name);
+        let calls = args
+            .into_iter()
+            .filter(|(name, _)| nested_call_args.contains(&name.as_str()))
+            .flat_map(|(_, value)| match value.value {
+                // `Vec<RuntimeCall>` of the batches
+                ValueDef::Composite(Composite::Unnamed(values)) => {
+                    values.into_iter().filter_map(Self::from_value).collect()
+                }
+                value_def => Self::from_value(Value {
+                    value: value_def,
+                    context: value.context,
+                })
+                .into_iter()
+                .collect::<Vec<_>>(),
+            })
+            .collect();
+
+        Self {
+            pallet_name: pallet_name.to_string(),
+            call_name: call_name.to_string(),
+            calls,
+        }
+    }
+
+    /// Decode a `RuntimeCall` value, a pallet variant wrapping a call variant
+    ///
+    /// Opaque calls, like the encoded calls of old `Multisig.as_multi` versions, aren't expanded.
+    fn from_value<T>(value: Value<T>) -> Option<Self> {
+        let ValueDef::Variant(Variant {
+            name: pallet_name,
+            values: Composite::Unnamed(mut values),
+        }) = value.value
+        else {
+            return None;
+        };
+        let call = values.pop().filter(|_| values.is_empty())?;
+        let ValueDef::Variant(Variant {
+            name: call_name,
+            values: args,
+        }) = call.value
+        else {
+            return None;
+        };
+
+        let args = match args {
+            Composite::Named(args) => args,
+            Composite::Unnamed(_) => Vec::new(),
+        };
+        Some(Self::new(&pallet_name, &call_name, args))
+    }
+
+    fn is_batch(&self) -> bool {
+        self.pallet_name == "Utility" && self.call_name.contains("batch")
+    }
+
+    /// Whether the nested call doesn't run in this extrinsic, `Scheduler.schedule*` only stores it
+    /// and `Multisig.as_multi` runs it once an approval reaches the threshold
+    fn defers_nested_call(&self, events: &[&Event]) -> bool {
+        match (self.pallet_name.as_str(), self.call_name.as_str()) {
+            ("Scheduler", _) => true,
+            ("Multisig", "as_multi") => !events
+                .iter()
+                .any(|event| event.name == "Multisig" && event.action == "MultisigExecuted"),
+            _ => false,
+        }
+    }
+
+    /// Path of the nested call which emitted each event of the extrinsic, empty for the outer call
+    ///
+    /// Batches emit `Utility.ItemCompleted` or `Utility.ItemFailed` after each call, the events
+    /// up to it belong to that call. A batch nested in a batch item emits its own item events and
+    /// ends with `Utility.BatchCompleted`, `Utility.BatchCompletedWithErrors` or
+    /// `Utility.BatchInterrupted` before the item event of the enclosing batch. Events of other
+    /// calls can't be told apart and stay with the closest batch item or the outer call.
+    ///
+    /// Calls which didn't run in the extrinsic, scheduled calls, multisig calls waiting for
+    /// approvals or batches reverted with their enclosing call, get no events.
+    pub fn attribute_events(&self, events: &[&Event]) -> Vec<Vec<usize>> {
+        let mut paths = vec![Vec::new(); events.len()];
+        let mut cursor = 0;
+        self.attribute(&[], &[], 0, events, &mut cursor, &mut paths);
+        paths
+    }
+
+    /// Attribute the events from `cursor` to `owner`, the path of the closest batch item, up to the
+    /// next event of an enclosing batch
+    ///
+    /// `depth` is the number of enclosing batches, a batch only ran if a closing event is left
+    /// besides theirs.
+    fn attribute(
+        &self,
+        path: &[usize],
+        owner: &[usize],
+        depth: usize,
+        events: &[&Event],
+        cursor: &mut usize,
+        paths: &mut [Vec<usize>],
+    ) {
+        let closing_events = events[*cursor..]
+            .iter()
+            .filter(|event| is_utility_event(event, BATCH_CLOSING_EVENTS))
+            .count();
+
+        if self.is_batch() && closing_events > depth {
+            for (item, call) in self.calls.iter().enumerate() {
+                let item_path = [path, &[item]].concat();
+                call.attribute(&item_path, &item_path, depth + 1, events, cursor, paths);
+
+                match events.get(*cursor) {
+                    Some(event) if is_utility_event(event, &["ItemCompleted", "ItemFailed"]) => {
+                        paths[*cursor] = item_path;
+                        *cursor += 1;
+                    }
+                    // `batch` stops at the first failed call
+                    _ => break,
+                }
+            }
+
+            if let Some(event) = events.get(*cursor)
+                && is_utility_event(event, BATCH_CLOSING_EVENTS)
+            {
+                paths[*cursor] = owner.to_vec();
+                *cursor += 1;
+            }
+        } else if let [call] = self.calls.as_slice()
+            && !self.is_batch()
+            && !self.defers_nested_call(&events[*cursor..])
+        {
+            // The events of a wrapped call can only be split further by a batch inside it
+            call.attribute(&[path, &[0]].concat(), owner, depth, events, cursor, paths);
+        }
+
+        while let Some(event) = events.get(*cursor)
+            && !is_utility_event(event, BATCH_EVENTS)
+        {
+            paths[*cursor] = owner.to_vec();
+            *cursor += 1;
+        }
+    }
+
+    /// Render the tree, one call per line, followed by the suffix of its path
+    pub fn format(&self, suffix: &dyn Fn(&[usize]) -> String) -> String {
+        let mut lines = vec![format!(
+            "{} ({}){}",
+            self.pallet_name,
+            self.call_name,
+            suffix(&[])
+        )];
+        self.format_calls(&mut Vec::new(), "", suffix, &mut lines);
+        lines.join("\n")
+    }
+
+    fn format_calls(
+        &self,
+        path: &mut Vec<usize>,
+        indent: &str,
+        suffix: &dyn Fn(&[usize]) -> String,
+        lines: &mut Vec<String>,
+    ) {
+        for (i, call) in self.calls.iter().enumerate() {
+            let is_last = i + 1 == self.calls.len();
+            path.push(i);
+            lines.push(format!(
+                "{indent}{}{} ({}){}",
+                if is_last { "└─ " } else { "├─ " },
+                call.pallet_name,
+                call.call_name,
+                suffix(path)
+            ));
+            let indent = format!("{indent}{}", if is_last { "   " } else { "│  " });
+            call.format_calls(path, &indent, suffix, lines);
+            path.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(pallet_name: &str, call_name: &str, calls: Vec<CallTree>) -> CallTree {
+        CallTree {
+            pallet_name: pallet_name.to_string(),
+            call_name: call_name.to_string(),
+            calls,
+        }
+    }
+
+    fn event(name: &str, action: &str) -> Event {
+        Event {
+            name: name.to_string(),
+            action: action.to_string(),
+            params: String::new(),
+            values: Composite::Unnamed(Vec::new()),
+        }
+    }
+
+    #[test]
+    fn nested_batch_consumes_its_own_item_events() {
+        // batch([transfer, batch([remark, transfer]), transfer])
+        let tree = call(
+            "Utility",
+            "batch",
+            vec![
+                call("Balances", "transfer", vec![]),
+                call(
+                    "Utility",
+                    "batch",
+                    vec![
+                        call("System", "remark", vec![]),
+                        call("Balances", "transfer", vec![]),
+                    ],
+                ),
+                call("Balances", "transfer", vec![]),
+            ],
+        );
+        let events = [
+            event("Balances", "Transfer"),
+            event("Utility", "ItemCompleted"),
+            event("Utility", "ItemCompleted"),
+            event("Balances", "Transfer"),
+            event("Utility", "ItemCompleted"),
+            event("Utility", "BatchCompleted"),
+            event("Utility", "ItemCompleted"),
+            event("Balances", "Transfer"),
+            event("Utility", "ItemCompleted"),
+            event("Utility", "BatchCompleted"),
+            event("System", "ExtrinsicSuccess"),
+        ];
+
+        let paths = tree.attribute_events(&events.iter().collect::<Vec<_>>());
+        assert_eq!(
+            paths,
+            vec![
+                vec![0],
+                vec![0],
+                vec![1, 0],
+                vec![1, 1],
+                vec![1, 1],
+                vec![1],
+                vec![1],
+                vec![2],
+                vec![2],
+                vec![],
+                vec![],
+            ]
+        );
+    }
+
+    #[test]
+    fn interrupted_nested_batch_ends_at_its_interruption() {
+        // force_batch([proxy(batch([transfer, transfer])), transfer])
+        let tree = call(
+            "Utility",
+            "force_batch",
+            vec![
+                call(
+                    "Proxy",
+                    "proxy",
+                    vec![call(
+                        "Utility",
+                        "batch",
+                        vec![
+                            call("Balances", "transfer", vec![]),
+                            call("Balances", "transfer", vec![]),
+                        ],
+                    )],
+                ),
+                call("Balances", "transfer", vec![]),
+            ],
+        );
+        let events = [
+            event("Balances", "Transfer"),
+            event("Utility", "ItemCompleted"),
+            event("Utility", "BatchInterrupted"),
+            event("Proxy", "ProxyExecuted"),
+            event("Utility", "ItemCompleted"),
+            event("Utility", "ItemFailed"),
+            event("Utility", "BatchCompletedWithErrors"),
+        ];
+
+        let paths = tree.attribute_events(&events.iter().collect::<Vec<_>>());
+        assert_eq!(
+            paths,
+            vec![
+                vec![0, 0, 0],
+                vec![0, 0, 0],
+                vec![0],
+                vec![0],
+                vec![0],
+                vec![1],
+                vec![],
+            ]
+        );
+    }
+
+    #[test]
+    fn scheduled_batch_gets_no_events() {
+        // force_batch([schedule(batch([transfer])), batch([transfer])], the scheduled batch runs in
+        // a later block
+        let tree = call(
+            "Utility",
+            "force_batch",
+            vec![
+                call(
+                    "Scheduler",
+                    "schedule",
+                    vec![call(
+                        "Utility",
+                        "batch",
+                        vec![call("Balances", "transfer", vec![])],
+                    )],
+                ),
+                call(
+                    "Utility",
+                    "batch",
+                    vec![call("Balances", "transfer", vec![])],
+                ),
+            ],
+        );
+        let events = [
+            event("Scheduler", "Scheduled"),
+            event("Utility", "ItemCompleted"),
+            event("Balances", "Transfer"),
+            event("Utility", "ItemCompleted"),
+            event("Utility", "BatchCompleted"),
+            event("Utility", "ItemCompleted"),
+            event("Utility", "BatchCompleted"),
+            event("System", "ExtrinsicSuccess"),
+        ];
+
+        let paths = tree.attribute_events(&events.iter().collect::<Vec<_>>());
+        assert_eq!(
+            paths,
+            vec![
+                vec![0],
+                vec![0],
+                vec![1, 0],
+                vec![1, 0],
+                vec![1],
+                vec![1],
+                vec![],
+                vec![],
+            ]
+        );
+    }
+
+    #[test]
+    fn reverted_batch_all_gets_no_events() {
+        // batch([batch_all([transfer, transfer]), transfer]), the failed batch_all is reverted
+        let tree = call(
+            "Utility",
+            "batch",
+            vec![
+                call(
+                    "Utility",
+                    "batch_all",
+                    vec![
+                        call("Balances", "transfer", vec![]),
+                        call("Balances", "transfer", vec![]),
+                    ],
+                ),
+                call("Balances", "transfer", vec![]),
+            ],
+        );
+        let events = [
+            event("Utility", "BatchInterrupted"),
+            event("System", "ExtrinsicSuccess"),
+        ];
+
+        let paths = tree.attribute_events(&events.iter().collect::<Vec<_>>());
+        assert_eq!(paths, vec![Vec::<usize>::new(); 2]);
+    }
+}
@@ -0,0 +1,134 @@
+use frame_decode::helpers::type_registry_from_metadata;
+use frame_metadata::RuntimeMetadata;
+use scale_info_legacy::LookupName;
+use scale_value::Value;
+use thiserror::Error;
+
+use crate::decoder::metadata::{AnyPalletConstant, AnyRuntimeMetadata, MetadataError};
+
+#[derive(Debug)]
+pub enum AnyConstantValue {
+    Legacy(Box<Value<LookupName>>),
+    Modern(Value<u32>),
+}
+
+#[derive(Debug, Error)]
+pub enum ConstantDecoderError {
+    #[error(transparent)]
+    ScaleDecoderFailed(#[from] scale_value::scale::DecodeError),
+
+    #[error(transparent)]
+    CantBuldTypeRegistry(#[from] scale_info_legacy::lookup_name::ParseError),
+
+    #[error(transparent)]
+    MetadataError(#[from] MetadataError),
+
+    #[error("This metadata version is unsupported: {version}")]
+    UnsupportedMetadataVersion { version: u32 },
+}
+
+/// Decodes any version of pallet constant
+pub fn decode_constant_any(
+    pallet_name: &str,
+    constant_name: &str,
+    metadata: AnyRuntimeMetadata<'_>,
+    spec_version: u64,
+) -> Result<AnyConstantValue, ConstantDecoderError> {
+    let constant = metadata
+        .pallet_metadata(pallet_name)?
+        .constant(constant_name)?;
+
+    decode_pallet_constant_any(pallet_name, &constant, metadata, spec_version)
+}
+
+/// Decodes the value of a constant from the metadata of a pallet
+pub fn decode_pallet_constant_any(
+    pallet_name: &str,
+    constant: &AnyPalletConstant<'_>,
+    metadata: AnyRuntimeMetadata<'_>,
+    spec_version: u64,
+) -> Result<AnyConstantValue, ConstantDecoderError> {
+    let historic_types = frame_decode::legacy_types::polkadot::relay_chain();
+    let value = &mut constant.value()?;
+
+    match metadata.0 {
+        RuntimeMetadata::V8(metadata) => {
+            let mut historic_types_for_spec = historic_types.for_spec_version(spec_version);
+            let types_from_metadata = type_registry_from_metadata(metadata)?;
+            historic_types_for_spec.prepend(types_from_metadata);
+
+            let type_id = LookupName::parse(&constant.type_as_str(None)?)?.in_pallet(pallet_name);
+
+            Ok(AnyConstantValue::Legacy(Box::new(
+                scale_value::scale::decode_as_type(value, type_id, &historic_types_for_spec)?,
+            )))
+        }
+        RuntimeMetadata::V9(metadata) => {
+            let mut historic_types_for_spec = historic_types.for_spec_version(spec_version);
+            let types_from_metadata = type_registry_from_metadata(metadata)?;
+            historic_types_for_spec.prepend(types_from_metadata);
+
+            let type_id = LookupName::parse(&constant.type_as_str(None)?)?.in_pallet(pallet_name);
+
+            Ok(AnyConstantValue::Legacy(Box::new(
+                scale_value::scale::decode_as_type(value, type_id, &historic_types_for_spec)?,
+            )))
+        }
+        RuntimeMetadata::V10(metadata) => {
+            let mut historic_types_for_spec = historic_types.for_spec_version(spec_version);
+            let types_from_metadata = type_registry_from_metadata(metadata)?;
+            historic_types_for_spec.prepend(types_from_metadata);
+
+            let type_id = LookupName::parse(&constant.type_as_str(None)?)?.in_pallet(pallet_name);
+
+            Ok(AnyConstantValue::Legacy(Box::new(
+                scale_value::scale::decode_as_type(value, type_id, &historic_types_for_spec)?,
+            )))
+        }
+        RuntimeMetadata::V11(metadata) => {
+            let mut historic_types_for_spec = historic_types.for_spec_version(spec_version);
+            let types_from_metadata = type_registry_from_metadata(metadata)?;
+            historic_types_for_spec.prepend(types_from_metadata);
+
+            let type_id = LookupName::parse(&constant.type_as_str(None)?)?.in_pallet(pallet_name);
+
+            Ok(AnyConstantValue::Legacy(Box::new(
+                scale_value::scale::decode_as_type(value, type_id, &historic_types_for_spec)?,
+            )))
+        }
+        RuntimeMetadata::V12(metadata) => {
+            let mut historic_types_for_spec = historic_types.for_spec_version(spec_version);
+            let types_from_metadata = type_registry_from_metadata(metadata)?;
+            historic_types_for_spec.prepend(types_from_metadata);
+
+            let type_id = LookupName::parse(&constant.type_as_str(None)?)?.in_pallet(pallet_name);
+
+            Ok(AnyConstantValue::Legacy(Box::new(
+                scale_value::scale::decode_as_type(value, type_id, &historic_types_for_spec)?,
+            )))
+        }
+        RuntimeMetadata::V13(metadata) => {
+            let mut historic_types_for_spec = historic_types.for_spec_version(spec_version);
+            let types_from_metadata = type_registry_from_metadata(metadata)?;
+            historic_types_for_spec.prepend(types_from_metadata);
+
+            let type_id = LookupName::parse(&constant.type_as_str(None)?)?.in_pallet(pallet_name);
+
+            Ok(AnyConstantValue::Legacy(Box::new(
+                scale_value::scale::decode_as_type(value, type_id, &historic_types_for_spec)?,
+            )))
+        }
+        RuntimeMetadata::V14(metadata) => Ok(AnyConstantValue::Modern(
+            scale_value::scale::decode_as_type(value, constant.type_id()?, &metadata.types)?,
+        )),
+        RuntimeMetadata::V15(metadata) => Ok(AnyConstantValue::Modern(
+            scale_value::scale::decode_as_type(value, constant.type_id()?, &metadata.types)?,
+        )),
+        RuntimeMetadata::V16(metadata) => Ok(AnyConstantValue::Modern(
+            scale_value::scale::decode_as_type(value, constant.type_id()?, &metadata.types)?,
+        )),
+        _ => Err(ConstantDecoderError::UnsupportedMetadataVersion {
+            version: metadata.version(),
+        }),
+    }
+}
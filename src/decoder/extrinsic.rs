@@ -3,7 +3,10 @@ use frame_decode::{
     helpers::type_registry_from_metadata,
 };
 use frame_metadata::RuntimeMetadata;
+use scale_decode::{DecodeAsFields, Field};
+use scale_info::PortableRegistry;
 use scale_info_legacy::LookupName;
+use scale_value::Value;
 use thiserror::Error;
 
 use crate::decoder::metadata::AnyRuntimeMetadata;
@@ -29,6 +32,91 @@ impl<'info> AnyExtrinsic<'info> {
         }
     }
 
+    /// Decode the call arguments from the bytes the extrinsic was decoded from
+    ///
+    /// Only extrinsics decoded with the portable type registry of V14+ metadata are supported.
+    pub fn call_args(
+        &self,
+        ext: impl AsRef<[u8]>,
+        metadata: AnyRuntimeMetadata<'_>,
+    ) -> Result<Vec<(String, Value<u32>)>, ExtrinsicDecoderError> {
+        match self {
+            AnyExtrinsic::Legacy(_) => Err(ExtrinsicDecoderError::LegacyValueDecodingUnsupported),
+            AnyExtrinsic::Modern(extrinsic) => {
+                let types = metadata
+                    .type_registry()
+                    .ok_or(ExtrinsicDecoderError::LegacyValueDecodingUnsupported)?;
+
+                extrinsic
+                    .call_data()
+                    .map(|arg| {
+                        let value = scale_value::scale::decode_as_type(
+                            &mut &ext.as_ref()[arg.range()],
+                            *arg.ty(),
+                            types,
+                        )?;
+                        Ok((arg.name().to_string(), value))
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Decode the call arguments from the bytes the extrinsic was decoded from straight into `R`,
+    /// whose fields are matched to the arguments by name
+    ///
+    /// Only extrinsics decoded with the portable type registry of V14+ metadata are supported.
+    pub fn call_args_as<R: DecodeAsFields>(
+        &self,
+        ext: impl AsRef<[u8]>,
+        metadata: AnyRuntimeMetadata<'_>,
+    ) -> Result<R, ExtrinsicDecoderError> {
+        match self {
+            AnyExtrinsic::Legacy(_) => Err(ExtrinsicDecoderError::LegacyValueDecodingUnsupported),
+            AnyExtrinsic::Modern(extrinsic) => {
+                let types = metadata
+                    .type_registry()
+                    .ok_or(ExtrinsicDecoderError::LegacyValueDecodingUnsupported)?;
+
+                Ok(decode_args_as(
+                    &ext.as_ref()[extrinsic.call_data_args_range()],
+                    extrinsic.call_data().map(|arg| (arg.name(), *arg.ty())),
+                    types,
+                )?)
+            }
+        }
+    }
+
+    /// Decode the address of the signer, `None` for unsigned extrinsics
+    ///
+    /// Only extrinsics decoded with the portable type registry of V14+ metadata are supported.
+    pub fn signer(
+        &self,
+        ext: impl AsRef<[u8]>,
+        metadata: AnyRuntimeMetadata<'_>,
+    ) -> Result<Option<Value<u32>>, ExtrinsicDecoderError> {
+        match self {
+            AnyExtrinsic::Legacy(_) => Err(ExtrinsicDecoderError::LegacyValueDecodingUnsupported),
+            AnyExtrinsic::Modern(extrinsic) => {
+                let types = metadata
+                    .type_registry()
+                    .ok_or(ExtrinsicDecoderError::LegacyValueDecodingUnsupported)?;
+
+                extrinsic
+                    .signature_payload()
+                    .map(|signature| {
+                        scale_value::scale::decode_as_type(
+                            &mut &ext.as_ref()[signature.address_range()],
+                            *signature.address_type(),
+                            types,
+                        )
+                        .map_err(ExtrinsicDecoderError::from)
+                    })
+                    .transpose()
+            }
+        }
+    }
+
     // pub fn hash(&self) -> Option<&str> {
     //     match self {
     //         AnyExtrinsic::Legacy(_) => None,
@@ -47,6 +135,25 @@ pub enum ExtrinsicDecoderError {
 
     #[error("This metadata version is unsupported: {version}")]
     UnsupportedMetadataVersion { version: u32 },
+
+    #[error(transparent)]
+    CallArgDecoderFailed(#[from] scale_value::scale::DecodeError),
+
+    #[error("Call argument decoder error: {0}")]
+    CallArgsAsTypeDecoderFailed(#[from] scale_decode::Error),
+
+    #[error("Decoding the call arguments and addresses of legacy extrinsics is unsupported")]
+    LegacyValueDecodingUnsupported,
+}
+
+/// Decode named arguments, SCALE encoded one after the other, into `R`
+pub fn decode_args_as<'a, R: DecodeAsFields>(
+    mut bytes: &[u8],
+    args: impl ExactSizeIterator<Item = (&'a str, u32)>,
+    types: &'a PortableRegistry,
+) -> Result<R, scale_decode::Error> {
+    let mut fields = args.map(|(name, type_id)| Field::new(type_id, Some(name)));
+    R::decode_as_fields(&mut bytes, &mut fields, types)
 }
 
 /// Decodes any version of extrinsic
@@ -1,9 +1,8 @@
 use std::collections::HashSet;
 
-use frame_metadata::{RuntimeMetadata, decode_different::DecodeDifferent};
-use scale_info::PortableRegistry;
+use frame_metadata::{RuntimeMetadata, decode_different::DecodeDifferent, v14::StorageHasher};
+use scale_info::{PortableRegistry, TypeDef, TypeDefPrimitive};
 use thiserror::Error;
-use tracing::debug;
 
 #[derive(Debug, Error)]
 pub enum MetadataError {
@@ -23,19 +22,9 @@ pub enum MetadataError {
     CantBuldTypeRegistry(#[from] scale_info_legacy::lookup_name::ParseError),
 
     #[error(
-        "Expected a pallet metadata storage value of {expected} but got {got} for {pallet_name} {storage_entry_name}"
+        "Expected pallet metadata storage types of {expected} but got {got} for {pallet_name} {storage_entry_name}"
     )]
-    UnexpectedStorageValueType {
-        expected: String,
-        got: String,
-        pallet_name: String,
-        storage_entry_name: String,
-    },
-
-    #[error(
-        "Expected a pallet metadata storage key of {expected} but got {got} for {pallet_name} {storage_entry_name}"
-    )]
-    UnexpectedStorageKeyType {
+    UnexpectedStorageType {
         expected: String,
         got: String,
         pallet_name: String,
@@ -50,6 +39,12 @@ pub enum UnresolvableTypeError {
 
     #[error("Type resolver cannot find type id {0}")]
     TypeIdNotFound(u32),
+
+    #[error("Expected type id {0} to be a variant type")]
+    NotAVariantType(u32),
+
+    #[error("Storage key type id {type_id} doesn't match the number of hashers ({hashers_len})")]
+    StorageKeysMismatch { type_id: u32, hashers_len: usize },
 }
 
 pub trait UnwrapDecodeDifferent<O> {
@@ -65,6 +60,19 @@ impl<B, O> UnwrapDecodeDifferent<O> for DecodeDifferent<B, O> {
     }
 }
 
+/// Name and field types of a call, event or error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantSignature {
+    pub name: String,
+    pub fields: Vec<String>,
+}
+
+impl std::fmt::Display for VariantSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({})", self.name, self.fields.join(", "))
+    }
+}
+
 pub enum AnyPalletMetadata<'a> {
     V8(&'a frame_metadata::v8::ModuleMetadata),
     V9(&'a frame_metadata::v9::ModuleMetadata),
@@ -78,6 +86,7 @@ pub enum AnyPalletMetadata<'a> {
 }
 
 impl<'a> AnyPalletMetadata<'a> {
+    #[allow(dead_code)]
     pub fn version(&self) -> u32 {
         match self {
             Self::V8(_) => 8,
@@ -92,7 +101,463 @@ impl<'a> AnyPalletMetadata<'a> {
         }
     }
 
-    pub fn storage_entries(&self) -> Result<Vec<AnyStorageEntry>, MetadataError> {
+    pub fn name(&self) -> Result<&'a str, MetadataError> {
+        match self {
+            Self::V8(metadata) => Ok(&metadata.name.unwrap_decode_different()?[..]),
+            Self::V9(metadata) => Ok(&metadata.name.unwrap_decode_different()?[..]),
+            Self::V10(metadata) => Ok(&metadata.name.unwrap_decode_different()?[..]),
+            Self::V11(metadata) => Ok(&metadata.name.unwrap_decode_different()?[..]),
+            Self::V12(metadata) => Ok(&metadata.name.unwrap_decode_different()?[..]),
+            Self::V13(metadata) => Ok(&metadata.name.unwrap_decode_different()?[..]),
+            Self::V14(metadata) => Ok(&metadata.name[..]),
+            Self::V15(metadata) => Ok(&metadata.name[..]),
+            Self::V16(metadata) => Ok(&metadata.name[..]),
+        }
+    }
+
+    /// Returns the name and arguments of every call of the pallet
+    pub fn calls(
+        &self,
+        type_registry: Option<&PortableRegistry>,
+    ) -> Result<Vec<VariantSignature>, MetadataError> {
+        match self {
+            Self::V8(metadata) => match &metadata.calls {
+                Some(calls) => calls
+                    .unwrap_decode_different()?
+                    .iter()
+                    .map(|call| {
+                        Ok(VariantSignature {
+                            name: call.name.unwrap_decode_different()?.to_owned(),
+                            fields: call
+                                .arguments
+                                .unwrap_decode_different()?
+                                .iter()
+                                .map(|arg| {
+                                    Ok(format!(
+                                        "{}: {}",
+                                        arg.name.unwrap_decode_different()?,
+                                        arg.ty.unwrap_decode_different()?
+                                    ))
+                                })
+                                .collect::<Result<_, MetadataError>>()?,
+                        })
+                    })
+                    .collect(),
+                None => Ok(Vec::new()),
+            },
+            Self::V9(metadata) => match &metadata.calls {
+                Some(calls) => calls
+                    .unwrap_decode_different()?
+                    .iter()
+                    .map(|call| {
+                        Ok(VariantSignature {
+                            name: call.name.unwrap_decode_different()?.to_owned(),
+                            fields: call
+                                .arguments
+                                .unwrap_decode_different()?
+                                .iter()
+                                .map(|arg| {
+                                    Ok(format!(
+                                        "{}: {}",
+                                        arg.name.unwrap_decode_different()?,
+                                        arg.ty.unwrap_decode_different()?
+                                    ))
+                                })
+                                .collect::<Result<_, MetadataError>>()?,
+                        })
+                    })
+                    .collect(),
+                None => Ok(Vec::new()),
+            },
+            Self::V10(metadata) => match &metadata.calls {
+                Some(calls) => calls
+                    .unwrap_decode_different()?
+                    .iter()
+                    .map(|call| {
+                        Ok(VariantSignature {
+                            name: call.name.unwrap_decode_different()?.to_owned(),
+                            fields: call
+                                .arguments
+                                .unwrap_decode_different()?
+                                .iter()
+                                .map(|arg| {
+                                    Ok(format!(
+                                        "{}: {}",
+                                        arg.name.unwrap_decode_different()?,
+                                        arg.ty.unwrap_decode_different()?
+                                    ))
+                                })
+                                .collect::<Result<_, MetadataError>>()?,
+                        })
+                    })
+                    .collect(),
+                None => Ok(Vec::new()),
+            },
+            Self::V11(metadata) => match &metadata.calls {
+                Some(calls) => calls
+                    .unwrap_decode_different()?
+                    .iter()
+                    .map(|call| {
+                        Ok(VariantSignature {
+                            name: call.name.unwrap_decode_different()?.to_owned(),
+                            fields: call
+                                .arguments
+                                .unwrap_decode_different()?
+                                .iter()
+                                .map(|arg| {
+                                    Ok(format!(
+                                        "{}: {}",
+                                        arg.name.unwrap_decode_different()?,
+                                        arg.ty.unwrap_decode_different()?
+                                    ))
+                                })
+                                .collect::<Result<_, MetadataError>>()?,
+                        })
+                    })
+                    .collect(),
+                None => Ok(Vec::new()),
+            },
+            Self::V12(metadata) => match &metadata.calls {
+                Some(calls) => calls
+                    .unwrap_decode_different()?
+                    .iter()
+                    .map(|call| {
+                        Ok(VariantSignature {
+                            name: call.name.unwrap_decode_different()?.to_owned(),
+                            fields: call
+                                .arguments
+                                .unwrap_decode_different()?
+                                .iter()
+                                .map(|arg| {
+                                    Ok(format!(
+                                        "{}: {}",
+                                        arg.name.unwrap_decode_different()?,
+                                        arg.ty.unwrap_decode_different()?
+                                    ))
+                                })
+                                .collect::<Result<_, MetadataError>>()?,
+                        })
+                    })
+                    .collect(),
+                None => Ok(Vec::new()),
+            },
+            Self::V13(metadata) => match &metadata.calls {
+                Some(calls) => calls
+                    .unwrap_decode_different()?
+                    .iter()
+                    .map(|call| {
+                        Ok(VariantSignature {
+                            name: call.name.unwrap_decode_different()?.to_owned(),
+                            fields: call
+                                .arguments
+                                .unwrap_decode_different()?
+                                .iter()
+                                .map(|arg| {
+                                    Ok(format!(
+                                        "{}: {}",
+                                        arg.name.unwrap_decode_different()?,
+                                        arg.ty.unwrap_decode_different()?
+                                    ))
+                                })
+                                .collect::<Result<_, MetadataError>>()?,
+                        })
+                    })
+                    .collect(),
+                None => Ok(Vec::new()),
+            },
+            Self::V14(metadata) => match &metadata.calls {
+                Some(calls) => Ok(resolve_variant_signatures(
+                    calls.ty.id,
+                    type_registry.ok_or(UnresolvableTypeError::RegistryNotFound)?,
+                )?),
+                None => Ok(Vec::new()),
+            },
+            Self::V15(metadata) => match &metadata.calls {
+                Some(calls) => Ok(resolve_variant_signatures(
+                    calls.ty.id,
+                    type_registry.ok_or(UnresolvableTypeError::RegistryNotFound)?,
+                )?),
+                None => Ok(Vec::new()),
+            },
+            Self::V16(metadata) => match &metadata.calls {
+                Some(calls) => Ok(resolve_variant_signatures(
+                    calls.ty.id,
+                    type_registry.ok_or(UnresolvableTypeError::RegistryNotFound)?,
+                )?),
+                None => Ok(Vec::new()),
+            },
+        }
+    }
+
+    /// Returns the name and fields of every event of the pallet
+    pub fn events(
+        &self,
+        type_registry: Option<&PortableRegistry>,
+    ) -> Result<Vec<VariantSignature>, MetadataError> {
+        match self {
+            Self::V8(metadata) => match &metadata.event {
+                Some(events) => events
+                    .unwrap_decode_different()?
+                    .iter()
+                    .map(|event| {
+                        Ok(VariantSignature {
+                            name: event.name.unwrap_decode_different()?.to_owned(),
+                            fields: event.arguments.unwrap_decode_different()?.to_owned(),
+                        })
+                    })
+                    .collect(),
+                None => Ok(Vec::new()),
+            },
+            Self::V9(metadata) => match &metadata.event {
+                Some(events) => events
+                    .unwrap_decode_different()?
+                    .iter()
+                    .map(|event| {
+                        Ok(VariantSignature {
+                            name: event.name.unwrap_decode_different()?.to_owned(),
+                            fields: event.arguments.unwrap_decode_different()?.to_owned(),
+                        })
+                    })
+                    .collect(),
+                None => Ok(Vec::new()),
+            },
+            Self::V10(metadata) => match &metadata.event {
+                Some(events) => events
+                    .unwrap_decode_different()?
+                    .iter()
+                    .map(|event| {
+                        Ok(VariantSignature {
+                            name: event.name.unwrap_decode_different()?.to_owned(),
+                            fields: event.arguments.unwrap_decode_different()?.to_owned(),
+                        })
+                    })
+                    .collect(),
+                None => Ok(Vec::new()),
+            },
+            Self::V11(metadata) => match &metadata.event {
+                Some(events) => events
+                    .unwrap_decode_different()?
+                    .iter()
+                    .map(|event| {
+                        Ok(VariantSignature {
+                            name: event.name.unwrap_decode_different()?.to_owned(),
+                            fields: event.arguments.unwrap_decode_different()?.to_owned(),
+                        })
+                    })
+                    .collect(),
+                None => Ok(Vec::new()),
+            },
+            Self::V12(metadata) => match &metadata.event {
+                Some(events) => events
+                    .unwrap_decode_different()?
+                    .iter()
+                    .map(|event| {
+                        Ok(VariantSignature {
+                            name: event.name.unwrap_decode_different()?.to_owned(),
+                            fields: event.arguments.unwrap_decode_different()?.to_owned(),
+                        })
+                    })
+                    .collect(),
+                None => Ok(Vec::new()),
+            },
+            Self::V13(metadata) => match &metadata.event {
+                Some(events) => events
+                    .unwrap_decode_different()?
+                    .iter()
+                    .map(|event| {
+                        Ok(VariantSignature {
+                            name: event.name.unwrap_decode_different()?.to_owned(),
+                            fields: event.arguments.unwrap_decode_different()?.to_owned(),
+                        })
+                    })
+                    .collect(),
+                None => Ok(Vec::new()),
+            },
+            Self::V14(metadata) => match &metadata.event {
+                Some(event) => Ok(resolve_variant_signatures(
+                    event.ty.id,
+                    type_registry.ok_or(UnresolvableTypeError::RegistryNotFound)?,
+                )?),
+                None => Ok(Vec::new()),
+            },
+            Self::V15(metadata) => match &metadata.event {
+                Some(event) => Ok(resolve_variant_signatures(
+                    event.ty.id,
+                    type_registry.ok_or(UnresolvableTypeError::RegistryNotFound)?,
+                )?),
+                None => Ok(Vec::new()),
+            },
+            Self::V16(metadata) => match &metadata.event {
+                Some(event) => Ok(resolve_variant_signatures(
+                    event.ty.id,
+                    type_registry.ok_or(UnresolvableTypeError::RegistryNotFound)?,
+                )?),
+                None => Ok(Vec::new()),
+            },
+        }
+    }
+
+    /// Returns the name and fields of every error of the pallet
+    /// Errors had no fields before V14
+    pub fn errors(
+        &self,
+        type_registry: Option<&PortableRegistry>,
+    ) -> Result<Vec<VariantSignature>, MetadataError> {
+        match self {
+            Self::V8(metadata) => metadata
+                .errors
+                .unwrap_decode_different()?
+                .iter()
+                .map(|error| {
+                    Ok(VariantSignature {
+                        name: error.name.unwrap_decode_different()?.to_owned(),
+                        fields: Vec::new(),
+                    })
+                })
+                .collect(),
+            Self::V9(metadata) => metadata
+                .errors
+                .unwrap_decode_different()?
+                .iter()
+                .map(|error| {
+                    Ok(VariantSignature {
+                        name: error.name.unwrap_decode_different()?.to_owned(),
+                        fields: Vec::new(),
+                    })
+                })
+                .collect(),
+            Self::V10(metadata) => metadata
+                .errors
+                .unwrap_decode_different()?
+                .iter()
+                .map(|error| {
+                    Ok(VariantSignature {
+                        name: error.name.unwrap_decode_different()?.to_owned(),
+                        fields: Vec::new(),
+                    })
+                })
+                .collect(),
+            Self::V11(metadata) => metadata
+                .errors
+                .unwrap_decode_different()?
+                .iter()
+                .map(|error| {
+                    Ok(VariantSignature {
+                        name: error.name.unwrap_decode_different()?.to_owned(),
+                        fields: Vec::new(),
+                    })
+                })
+                .collect(),
+            Self::V12(metadata) => metadata
+                .errors
+                .unwrap_decode_different()?
+                .iter()
+                .map(|error| {
+                    Ok(VariantSignature {
+                        name: error.name.unwrap_decode_different()?.to_owned(),
+                        fields: Vec::new(),
+                    })
+                })
+                .collect(),
+            Self::V13(metadata) => metadata
+                .errors
+                .unwrap_decode_different()?
+                .iter()
+                .map(|error| {
+                    Ok(VariantSignature {
+                        name: error.name.unwrap_decode_different()?.to_owned(),
+                        fields: Vec::new(),
+                    })
+                })
+                .collect(),
+            Self::V14(metadata) => match &metadata.error {
+                Some(error) => Ok(resolve_variant_signatures(
+                    error.ty.id,
+                    type_registry.ok_or(UnresolvableTypeError::RegistryNotFound)?,
+                )?),
+                None => Ok(Vec::new()),
+            },
+            Self::V15(metadata) => match &metadata.error {
+                Some(error) => Ok(resolve_variant_signatures(
+                    error.ty.id,
+                    type_registry.ok_or(UnresolvableTypeError::RegistryNotFound)?,
+                )?),
+                None => Ok(Vec::new()),
+            },
+            Self::V16(metadata) => match &metadata.error {
+                Some(error) => Ok(resolve_variant_signatures(
+                    error.ty.id,
+                    type_registry.ok_or(UnresolvableTypeError::RegistryNotFound)?,
+                )?),
+                None => Ok(Vec::new()),
+            },
+        }
+    }
+
+    pub fn constants(&self) -> Result<Vec<AnyPalletConstant<'a>>, MetadataError> {
+        match self {
+            Self::V8(metadata) => Ok(metadata
+                .constants
+                .unwrap_decode_different()?
+                .iter()
+                .map(AnyPalletConstant::V8)
+                .collect()),
+            Self::V9(metadata) => Ok(metadata
+                .constants
+                .unwrap_decode_different()?
+                .iter()
+                .map(AnyPalletConstant::V9)
+                .collect()),
+            Self::V10(metadata) => Ok(metadata
+                .constants
+                .unwrap_decode_different()?
+                .iter()
+                .map(AnyPalletConstant::V10)
+                .collect()),
+            Self::V11(metadata) => Ok(metadata
+                .constants
+                .unwrap_decode_different()?
+                .iter()
+                .map(AnyPalletConstant::V11)
+                .collect()),
+            Self::V12(metadata) => Ok(metadata
+                .constants
+                .unwrap_decode_different()?
+                .iter()
+                .map(AnyPalletConstant::V12)
+                .collect()),
+            Self::V13(metadata) => Ok(metadata
+                .constants
+                .unwrap_decode_different()?
+                .iter()
+                .map(AnyPalletConstant::V13)
+                .collect()),
+            Self::V14(metadata) => Ok(metadata
+                .constants
+                .iter()
+                .map(AnyPalletConstant::V14)
+                .collect()),
+            Self::V15(metadata) => Ok(metadata
+                .constants
+                .iter()
+                .map(AnyPalletConstant::V15)
+                .collect()),
+            Self::V16(metadata) => Ok(metadata
+                .constants
+                .iter()
+                .map(AnyPalletConstant::V16)
+                .collect()),
+        }
+    }
+
+    pub fn constant(&self, constant_name: &str) -> Result<AnyPalletConstant<'a>, MetadataError> {
+        self.constants()?
+            .into_iter()
+            .try_find(|constant| Ok::<bool, MetadataError>(constant.name()? == constant_name))?
+            .ok_or(MetadataError::MetadataNotFound(constant_name.to_string()))
+    }
+
+    pub fn storage_entries(&self) -> Result<Vec<AnyStorageEntry<'a>>, MetadataError> {
         match self {
             Self::V8(metadata) => Ok(metadata
                 .storage
@@ -181,7 +646,7 @@ impl<'a> AnyPalletMetadata<'a> {
         }
     }
 
-    pub fn storage_entry<'b>(&self, entry_name: &'b str) -> Result<AnyStorageEntry, MetadataError> {
+    pub fn storage_entry(&self, entry_name: &str) -> Result<AnyStorageEntry<'a>, MetadataError> {
         let x = self.storage_entries()?.into_iter();
 
         x.try_find(|entry| Ok::<bool, MetadataError>(entry.name()? == entry_name))?
@@ -189,6 +654,101 @@ impl<'a> AnyPalletMetadata<'a> {
     }
 }
 
+pub enum AnyPalletConstant<'a> {
+    V8(&'a frame_metadata::v8::ModuleConstantMetadata),
+    V9(&'a frame_metadata::v9::ModuleConstantMetadata),
+    V10(&'a frame_metadata::v10::ModuleConstantMetadata),
+    V11(&'a frame_metadata::v11::ModuleConstantMetadata),
+    V12(&'a frame_metadata::v12::ModuleConstantMetadata),
+    V13(&'a frame_metadata::v13::ModuleConstantMetadata),
+    V14(&'a frame_metadata::v14::PalletConstantMetadata<scale_info::form::PortableForm>),
+    V15(&'a frame_metadata::v15::PalletConstantMetadata<scale_info::form::PortableForm>),
+    V16(&'a frame_metadata::v16::PalletConstantMetadata<scale_info::form::PortableForm>),
+}
+
+impl<'a> AnyPalletConstant<'a> {
+    pub fn version(&self) -> u32 {
+        match self {
+            Self::V8(_) => 8,
+            Self::V9(_) => 9,
+            Self::V10(_) => 10,
+            Self::V11(_) => 11,
+            Self::V12(_) => 12,
+            Self::V13(_) => 13,
+            Self::V14(_) => 14,
+            Self::V15(_) => 15,
+            Self::V16(_) => 16,
+        }
+    }
+
+    pub fn name(&self) -> Result<&'a str, MetadataError> {
+        match self {
+            Self::V8(constant) => Ok(&constant.name.unwrap_decode_different()?[..]),
+            Self::V9(constant) => Ok(&constant.name.unwrap_decode_different()?[..]),
+            Self::V10(constant) => Ok(&constant.name.unwrap_decode_different()?[..]),
+            Self::V11(constant) => Ok(&constant.name.unwrap_decode_different()?[..]),
+            Self::V12(constant) => Ok(&constant.name.unwrap_decode_different()?[..]),
+            Self::V13(constant) => Ok(&constant.name.unwrap_decode_different()?[..]),
+            Self::V14(constant) => Ok(&constant.name[..]),
+            Self::V15(constant) => Ok(&constant.name[..]),
+            Self::V16(constant) => Ok(&constant.name[..]),
+        }
+    }
+
+    pub fn type_as_str(
+        &self,
+        type_registry: Option<&PortableRegistry>,
+    ) -> Result<String, MetadataError> {
+        match self {
+            Self::V8(constant) => Ok(constant.ty.unwrap_decode_different()?.to_owned()),
+            Self::V9(constant) => Ok(constant.ty.unwrap_decode_different()?.to_owned()),
+            Self::V10(constant) => Ok(constant.ty.unwrap_decode_different()?.to_owned()),
+            Self::V11(constant) => Ok(constant.ty.unwrap_decode_different()?.to_owned()),
+            Self::V12(constant) => Ok(constant.ty.unwrap_decode_different()?.to_owned()),
+            Self::V13(constant) => Ok(constant.ty.unwrap_decode_different()?.to_owned()),
+            Self::V14(constant) => Ok(resolve_type_to_str(
+                constant.ty.id,
+                type_registry.ok_or(UnresolvableTypeError::RegistryNotFound)?,
+            )?),
+            Self::V15(constant) => Ok(resolve_type_to_str(
+                constant.ty.id,
+                type_registry.ok_or(UnresolvableTypeError::RegistryNotFound)?,
+            )?),
+            Self::V16(constant) => Ok(resolve_type_to_str(
+                constant.ty.id,
+                type_registry.ok_or(UnresolvableTypeError::RegistryNotFound)?,
+            )?),
+        }
+    }
+
+    /// Type id of the constant in the portable type registry (V14+ only)
+    pub fn type_id(&self) -> Result<u32, MetadataError> {
+        match self {
+            Self::V14(constant) => Ok(constant.ty.id),
+            Self::V15(constant) => Ok(constant.ty.id),
+            Self::V16(constant) => Ok(constant.ty.id),
+            _ => Err(MetadataError::UnsupportedMetadataVersion {
+                version: self.version(),
+            }),
+        }
+    }
+
+    /// SCALE encoded value of the constant
+    pub fn value(&self) -> Result<&'a [u8], MetadataError> {
+        match self {
+            Self::V8(constant) => Ok(&constant.value.unwrap_decode_different()?[..]),
+            Self::V9(constant) => Ok(&constant.value.unwrap_decode_different()?[..]),
+            Self::V10(constant) => Ok(&constant.value.unwrap_decode_different()?[..]),
+            Self::V11(constant) => Ok(&constant.value.unwrap_decode_different()?[..]),
+            Self::V12(constant) => Ok(&constant.value.unwrap_decode_different()?[..]),
+            Self::V13(constant) => Ok(&constant.value.unwrap_decode_different()?[..]),
+            Self::V14(constant) => Ok(&constant.value[..]),
+            Self::V15(constant) => Ok(&constant.value[..]),
+            Self::V16(constant) => Ok(&constant.value[..]),
+        }
+    }
+}
+
 pub enum AnyStorageEntry<'a> {
     V8(&'a frame_metadata::v8::StorageEntryMetadata),
     V9(&'a frame_metadata::v9::StorageEntryMetadata),
@@ -202,6 +762,7 @@ pub enum AnyStorageEntry<'a> {
 }
 
 impl<'a> AnyStorageEntry<'a> {
+    #[allow(dead_code)]
     pub fn version(&self) -> u32 {
         match self {
             Self::V8(_) => 8,
@@ -230,6 +791,95 @@ impl<'a> AnyStorageEntry<'a> {
         }
     }
 
+    /// Returns the hashers of the storage keys, one for each key returned by `types_as_str`
+    pub fn hashers(&self) -> Result<Vec<StorageHasher>, MetadataError> {
+        match self {
+            Self::V8(entry) => match &entry.ty {
+                frame_metadata::v8::StorageEntryType::Plain(_) => Ok(vec![]),
+                frame_metadata::v8::StorageEntryType::Map { hasher, .. } => {
+                    Ok(vec![hasher_from_v8(hasher)])
+                }
+                frame_metadata::v8::StorageEntryType::DoubleMap {
+                    hasher,
+                    key2_hasher,
+                    ..
+                } => Ok(vec![hasher_from_v8(hasher), hasher_from_v8(key2_hasher)]),
+            },
+            Self::V9(entry) => match &entry.ty {
+                frame_metadata::v9::StorageEntryType::Plain(_) => Ok(vec![]),
+                frame_metadata::v9::StorageEntryType::Map { hasher, .. } => {
+                    Ok(vec![hasher_from_v9(hasher)])
+                }
+                frame_metadata::v9::StorageEntryType::DoubleMap {
+                    hasher,
+                    key2_hasher,
+                    ..
+                } => Ok(vec![hasher_from_v9(hasher), hasher_from_v9(key2_hasher)]),
+            },
+            Self::V10(entry) => match &entry.ty {
+                frame_metadata::v10::StorageEntryType::Plain(_) => Ok(vec![]),
+                frame_metadata::v10::StorageEntryType::Map { hasher, .. } => {
+                    Ok(vec![hasher_from_v10(hasher)])
+                }
+                frame_metadata::v10::StorageEntryType::DoubleMap {
+                    hasher,
+                    key2_hasher,
+                    ..
+                } => Ok(vec![hasher_from_v10(hasher), hasher_from_v10(key2_hasher)]),
+            },
+            Self::V11(entry) => match &entry.ty {
+                frame_metadata::v11::StorageEntryType::Plain(_) => Ok(vec![]),
+                frame_metadata::v11::StorageEntryType::Map { hasher, .. } => {
+                    Ok(vec![hasher_from_v11(hasher)])
+                }
+                frame_metadata::v11::StorageEntryType::DoubleMap {
+                    hasher,
+                    key2_hasher,
+                    ..
+                } => Ok(vec![hasher_from_v11(hasher), hasher_from_v11(key2_hasher)]),
+            },
+            Self::V12(entry) => match &entry.ty {
+                frame_metadata::v12::StorageEntryType::Plain(_) => Ok(vec![]),
+                frame_metadata::v12::StorageEntryType::Map { hasher, .. } => {
+                    Ok(vec![hasher_from_v12(hasher)])
+                }
+                frame_metadata::v12::StorageEntryType::DoubleMap {
+                    hasher,
+                    key2_hasher,
+                    ..
+                } => Ok(vec![hasher_from_v12(hasher), hasher_from_v12(key2_hasher)]),
+            },
+            Self::V13(entry) => match &entry.ty {
+                frame_metadata::v13::StorageEntryType::Plain(_) => Ok(vec![]),
+                frame_metadata::v13::StorageEntryType::Map { hasher, .. } => {
+                    Ok(vec![hasher_from_v13(hasher)])
+                }
+                frame_metadata::v13::StorageEntryType::DoubleMap {
+                    hasher,
+                    key2_hasher,
+                    ..
+                } => Ok(vec![hasher_from_v13(hasher), hasher_from_v13(key2_hasher)]),
+                frame_metadata::v13::StorageEntryType::NMap { hashers, .. } => Ok(hashers
+                    .unwrap_decode_different()?
+                    .iter()
+                    .map(hasher_from_v13)
+                    .collect()),
+            },
+            Self::V14(entry) => match &entry.ty {
+                frame_metadata::v14::StorageEntryType::Plain(_) => Ok(vec![]),
+                frame_metadata::v14::StorageEntryType::Map { hashers, .. } => Ok(hashers.clone()),
+            },
+            Self::V15(entry) => match &entry.ty {
+                frame_metadata::v14::StorageEntryType::Plain(_) => Ok(vec![]),
+                frame_metadata::v14::StorageEntryType::Map { hashers, .. } => Ok(hashers.clone()),
+            },
+            Self::V16(entry) => match &entry.ty {
+                frame_metadata::v14::StorageEntryType::Plain(_) => Ok(vec![]),
+                frame_metadata::v14::StorageEntryType::Map { hashers, .. } => Ok(hashers.clone()),
+            },
+        }
+    }
+
     /// Returns the key and value types as a tuple
     /// DoubleMap and NMap contain multiple keys hence the use of a Vector
     pub fn types_as_str(
@@ -386,16 +1036,16 @@ impl<'a> AnyStorageEntry<'a> {
             Self::V14(entry) => {
                 let type_registry = type_registry.ok_or(UnresolvableTypeError::RegistryNotFound)?;
 
-                match entry.ty {
+                match &entry.ty {
                     frame_metadata::v14::StorageEntryType::Plain(value) => {
-                        // let resolved = type_registry.resolve(value.id);
-                        // resolved.map(|x| x.)
-
-                        debug!(ty = ?type_registry.resolve(value.id).unwrap().type_def);
                         Ok((vec![], resolve_type_to_str(value.id, type_registry)?))
                     }
-                    frame_metadata::v14::StorageEntryType::Map { key, value, .. } => Ok((
-                        vec![resolve_type_to_str(key.id, type_registry)?],
+                    frame_metadata::v14::StorageEntryType::Map {
+                        hashers,
+                        key,
+                        value,
+                    } => Ok((
+                        resolve_storage_keys_to_str(hashers.len(), key.id, type_registry)?,
                         resolve_type_to_str(value.id, type_registry)?,
                     )),
                 }
@@ -403,12 +1053,16 @@ impl<'a> AnyStorageEntry<'a> {
             Self::V15(entry) => {
                 let type_registry = type_registry.ok_or(UnresolvableTypeError::RegistryNotFound)?;
 
-                match entry.ty {
+                match &entry.ty {
                     frame_metadata::v14::StorageEntryType::Plain(value) => {
                         Ok((vec![], resolve_type_to_str(value.id, type_registry)?))
                     }
-                    frame_metadata::v14::StorageEntryType::Map { key, value, .. } => Ok((
-                        vec![resolve_type_to_str(key.id, type_registry)?],
+                    frame_metadata::v14::StorageEntryType::Map {
+                        hashers,
+                        key,
+                        value,
+                    } => Ok((
+                        resolve_storage_keys_to_str(hashers.len(), key.id, type_registry)?,
                         resolve_type_to_str(value.id, type_registry)?,
                     )),
                 }
@@ -416,12 +1070,16 @@ impl<'a> AnyStorageEntry<'a> {
             Self::V16(entry) => {
                 let type_registry = type_registry.ok_or(UnresolvableTypeError::RegistryNotFound)?;
 
-                match entry.ty {
+                match &entry.ty {
                     frame_metadata::v14::StorageEntryType::Plain(value) => {
                         Ok((vec![], resolve_type_to_str(value.id, type_registry)?))
                     }
-                    frame_metadata::v14::StorageEntryType::Map { key, value, .. } => Ok((
-                        vec![resolve_type_to_str(key.id, type_registry)?],
+                    frame_metadata::v14::StorageEntryType::Map {
+                        hashers,
+                        key,
+                        value,
+                    } => Ok((
+                        resolve_storage_keys_to_str(hashers.len(), key.id, type_registry)?,
                         resolve_type_to_str(value.id, type_registry)?,
                     )),
                 }
@@ -430,7 +1088,76 @@ impl<'a> AnyStorageEntry<'a> {
     }
 }
 
-/// Resolve types to a string (does not handle builtin types)
+fn hasher_from_v8(hasher: &frame_metadata::v8::StorageHasher) -> StorageHasher {
+    match hasher {
+        frame_metadata::v8::StorageHasher::Blake2_128 => StorageHasher::Blake2_128,
+        frame_metadata::v8::StorageHasher::Blake2_256 => StorageHasher::Blake2_256,
+        frame_metadata::v8::StorageHasher::Twox128 => StorageHasher::Twox128,
+        frame_metadata::v8::StorageHasher::Twox256 => StorageHasher::Twox256,
+        frame_metadata::v8::StorageHasher::Twox64Concat => StorageHasher::Twox64Concat,
+    }
+}
+
+fn hasher_from_v9(hasher: &frame_metadata::v9::StorageHasher) -> StorageHasher {
+    match hasher {
+        frame_metadata::v9::StorageHasher::Blake2_128 => StorageHasher::Blake2_128,
+        frame_metadata::v9::StorageHasher::Blake2_256 => StorageHasher::Blake2_256,
+        frame_metadata::v9::StorageHasher::Blake2_128Concat => StorageHasher::Blake2_128Concat,
+        frame_metadata::v9::StorageHasher::Twox128 => StorageHasher::Twox128,
+        frame_metadata::v9::StorageHasher::Twox256 => StorageHasher::Twox256,
+        frame_metadata::v9::StorageHasher::Twox64Concat => StorageHasher::Twox64Concat,
+    }
+}
+
+fn hasher_from_v10(hasher: &frame_metadata::v10::StorageHasher) -> StorageHasher {
+    match hasher {
+        frame_metadata::v10::StorageHasher::Blake2_128 => StorageHasher::Blake2_128,
+        frame_metadata::v10::StorageHasher::Blake2_256 => StorageHasher::Blake2_256,
+        frame_metadata::v10::StorageHasher::Blake2_128Concat => StorageHasher::Blake2_128Concat,
+        frame_metadata::v10::StorageHasher::Twox128 => StorageHasher::Twox128,
+        frame_metadata::v10::StorageHasher::Twox256 => StorageHasher::Twox256,
+        frame_metadata::v10::StorageHasher::Twox64Concat => StorageHasher::Twox64Concat,
+    }
+}
+
+fn hasher_from_v11(hasher: &frame_metadata::v11::StorageHasher) -> StorageHasher {
+    match hasher {
+        frame_metadata::v11::StorageHasher::Blake2_128 => StorageHasher::Blake2_128,
+        frame_metadata::v11::StorageHasher::Blake2_256 => StorageHasher::Blake2_256,
+        frame_metadata::v11::StorageHasher::Blake2_128Concat => StorageHasher::Blake2_128Concat,
+        frame_metadata::v11::StorageHasher::Twox128 => StorageHasher::Twox128,
+        frame_metadata::v11::StorageHasher::Twox256 => StorageHasher::Twox256,
+        frame_metadata::v11::StorageHasher::Twox64Concat => StorageHasher::Twox64Concat,
+        frame_metadata::v11::StorageHasher::Identity => StorageHasher::Identity,
+    }
+}
+
+fn hasher_from_v12(hasher: &frame_metadata::v12::StorageHasher) -> StorageHasher {
+    match hasher {
+        frame_metadata::v12::StorageHasher::Blake2_128 => StorageHasher::Blake2_128,
+        frame_metadata::v12::StorageHasher::Blake2_256 => StorageHasher::Blake2_256,
+        frame_metadata::v12::StorageHasher::Blake2_128Concat => StorageHasher::Blake2_128Concat,
+        frame_metadata::v12::StorageHasher::Twox128 => StorageHasher::Twox128,
+        frame_metadata::v12::StorageHasher::Twox256 => StorageHasher::Twox256,
+        frame_metadata::v12::StorageHasher::Twox64Concat => StorageHasher::Twox64Concat,
+        frame_metadata::v12::StorageHasher::Identity => StorageHasher::Identity,
+    }
+}
+
+fn hasher_from_v13(hasher: &frame_metadata::v13::StorageHasher) -> StorageHasher {
+    match hasher {
+        frame_metadata::v13::StorageHasher::Blake2_128 => StorageHasher::Blake2_128,
+        frame_metadata::v13::StorageHasher::Blake2_256 => StorageHasher::Blake2_256,
+        frame_metadata::v13::StorageHasher::Blake2_128Concat => StorageHasher::Blake2_128Concat,
+        frame_metadata::v13::StorageHasher::Twox128 => StorageHasher::Twox128,
+        frame_metadata::v13::StorageHasher::Twox256 => StorageHasher::Twox256,
+        frame_metadata::v13::StorageHasher::Twox64Concat => StorageHasher::Twox64Concat,
+        frame_metadata::v13::StorageHasher::Identity => StorageHasher::Identity,
+    }
+}
+
+/// Resolve types to a string, including builtin types such as sequences, arrays, tuples,
+/// compact encoded values and primitives
 fn resolve_type_to_str(
     type_id: u32,
     type_registry: &PortableRegistry,
@@ -439,27 +1166,155 @@ fn resolve_type_to_str(
         .resolve(type_id)
         .ok_or(UnresolvableTypeError::TypeIdNotFound(type_id))?;
 
-    let ty_params = resolved_ty
-        .type_params
-        .iter()
-        .filter_map(|ty_param| {
-            ty_param.ty.map(|ty_id| {
-                let meta = type_registry
-                    .resolve(ty_id.id)
-                    .ok_or(UnresolvableTypeError::TypeIdNotFound(ty_id.id))?;
+    Ok(match &resolved_ty.type_def {
+        TypeDef::Composite(_) | TypeDef::Variant(_) if !resolved_ty.path.is_empty() => {
+            let ty_params = resolved_ty
+                .type_params
+                .iter()
+                .filter_map(|ty_param| {
+                    ty_param
+                        .ty
+                        .map(|ty_id| resolve_type_to_str(ty_id.id, type_registry))
+                })
+                .collect::<Result<Vec<String>, UnresolvableTypeError>>()?;
 
-                Ok(meta.path.segments.join("::"))
-            })
-        })
-        .collect::<Result<Vec<String>, UnresolvableTypeError>>()?;
+            let mut ty_as_str = resolved_ty.path.segments.join("::");
+            if !ty_params.is_empty() {
+                ty_as_str.push('<');
+                ty_as_str.push_str(&ty_params.join(", "));
+                ty_as_str.push('>');
+            }
+            ty_as_str
+        }
+        // Anonymous composite, rendered as a tuple of its fields
+        TypeDef::Composite(composite) => {
+            let fields = composite
+                .fields
+                .iter()
+                .map(|field| resolve_type_to_str(field.ty.id, type_registry))
+                .collect::<Result<Vec<String>, UnresolvableTypeError>>()?;
+            format!("({})", fields.join(", "))
+        }
+        // Anonymous variant, rendered as the list of its variant names
+        TypeDef::Variant(variant) => variant
+            .variants
+            .iter()
+            .map(|variant| variant.name.as_str())
+            .collect::<Vec<_>>()
+            .join(" | "),
+        TypeDef::Sequence(sequence) => {
+            format!(
+                "Vec<{}>",
+                resolve_type_to_str(sequence.type_param.id, type_registry)?
+            )
+        }
+        TypeDef::Array(array) => format!(
+            "[{}; {}]",
+            resolve_type_to_str(array.type_param.id, type_registry)?,
+            array.len
+        ),
+        TypeDef::Tuple(tuple) => {
+            let fields = tuple
+                .fields
+                .iter()
+                .map(|field| resolve_type_to_str(field.id, type_registry))
+                .collect::<Result<Vec<String>, UnresolvableTypeError>>()?;
+            format!("({})", fields.join(", "))
+        }
+        TypeDef::Primitive(primitive) => primitive_to_str(primitive).to_string(),
+        TypeDef::Compact(compact) => format!(
+            "Compact<{}>",
+            resolve_type_to_str(compact.type_param.id, type_registry)?
+        ),
+        TypeDef::BitSequence(bit_sequence) => format!(
+            "BitVec<{}, {}>",
+            resolve_type_to_str(bit_sequence.bit_store_type.id, type_registry)?,
+            resolve_type_to_str(bit_sequence.bit_order_type.id, type_registry)?
+        ),
+    })
+}
+
+fn primitive_to_str(primitive: &TypeDefPrimitive) -> &'static str {
+    match primitive {
+        TypeDefPrimitive::Bool => "bool",
+        TypeDefPrimitive::Char => "char",
+        TypeDefPrimitive::Str => "String",
+        TypeDefPrimitive::U8 => "u8",
+        TypeDefPrimitive::U16 => "u16",
+        TypeDefPrimitive::U32 => "u32",
+        TypeDefPrimitive::U64 => "u64",
+        TypeDefPrimitive::U128 => "u128",
+        TypeDefPrimitive::U256 => "u256",
+        TypeDefPrimitive::I8 => "i8",
+        TypeDefPrimitive::I16 => "i16",
+        TypeDefPrimitive::I32 => "i32",
+        TypeDefPrimitive::I64 => "i64",
+        TypeDefPrimitive::I128 => "i128",
+        TypeDefPrimitive::I256 => "i256",
+    }
+}
+
+/// Resolve the key types of a V14+ storage map
+/// Maps with multiple hashers (DoubleMap and NMap) use a tuple key with one element per hasher,
+/// these are split into separate keys
+fn resolve_storage_keys_to_str(
+    hashers_len: usize,
+    key_id: u32,
+    type_registry: &PortableRegistry,
+) -> Result<Vec<String>, UnresolvableTypeError> {
+    if hashers_len == 1 {
+        return Ok(vec![resolve_type_to_str(key_id, type_registry)?]);
+    }
+
+    let resolved_ty = type_registry
+        .resolve(key_id)
+        .ok_or(UnresolvableTypeError::TypeIdNotFound(key_id))?;
 
-    let mut ty_as_str = resolved_ty.path.segments.join("::");
-    if !ty_params.is_empty() {
-        ty_as_str.push('<');
-        ty_as_str.push_str(&ty_params.join(", "));
-        ty_as_str.push('>');
+    match &resolved_ty.type_def {
+        TypeDef::Tuple(tuple) if tuple.fields.len() == hashers_len => tuple
+            .fields
+            .iter()
+            .map(|field| resolve_type_to_str(field.id, type_registry))
+            .collect(),
+        _ => Err(UnresolvableTypeError::StorageKeysMismatch {
+            type_id: key_id,
+            hashers_len,
+        }),
+    }
+}
+
+/// Resolve the variants of an enum type (calls, events and errors in V14+) to their signatures
+fn resolve_variant_signatures(
+    type_id: u32,
+    type_registry: &PortableRegistry,
+) -> Result<Vec<VariantSignature>, UnresolvableTypeError> {
+    let resolved_ty = type_registry
+        .resolve(type_id)
+        .ok_or(UnresolvableTypeError::TypeIdNotFound(type_id))?;
+
+    match &resolved_ty.type_def {
+        TypeDef::Variant(type_def_variant) => type_def_variant
+            .variants
+            .iter()
+            .map(|variant| {
+                Ok(VariantSignature {
+                    name: variant.name.clone(),
+                    fields: variant
+                        .fields
+                        .iter()
+                        .map(|field| {
+                            let ty = resolve_type_to_str(field.ty.id, type_registry)?;
+                            Ok(match &field.name {
+                                Some(name) => format!("{name}: {ty}"),
+                                None => ty,
+                            })
+                        })
+                        .collect::<Result<_, UnresolvableTypeError>>()?,
+                })
+            })
+            .collect(),
+        _ => Err(UnresolvableTypeError::NotAVariantType(type_id)),
     }
-    Ok(ty_as_str)
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -529,6 +1384,65 @@ impl<'a> AnyRuntimeMetadata<'a> {
         }
     }
 
+    pub fn pallets(&self) -> Result<Vec<AnyPalletMetadata<'a>>, MetadataError> {
+        match self.0 {
+            RuntimeMetadata::V8(metadata) => Ok(metadata
+                .modules
+                .unwrap_decode_different()?
+                .iter()
+                .map(AnyPalletMetadata::V8)
+                .collect()),
+            RuntimeMetadata::V9(metadata) => Ok(metadata
+                .modules
+                .unwrap_decode_different()?
+                .iter()
+                .map(AnyPalletMetadata::V9)
+                .collect()),
+            RuntimeMetadata::V10(metadata) => Ok(metadata
+                .modules
+                .unwrap_decode_different()?
+                .iter()
+                .map(AnyPalletMetadata::V10)
+                .collect()),
+            RuntimeMetadata::V11(metadata) => Ok(metadata
+                .modules
+                .unwrap_decode_different()?
+                .iter()
+                .map(AnyPalletMetadata::V11)
+                .collect()),
+            RuntimeMetadata::V12(metadata) => Ok(metadata
+                .modules
+                .unwrap_decode_different()?
+                .iter()
+                .map(AnyPalletMetadata::V12)
+                .collect()),
+            RuntimeMetadata::V13(metadata) => Ok(metadata
+                .modules
+                .unwrap_decode_different()?
+                .iter()
+                .map(AnyPalletMetadata::V13)
+                .collect()),
+            RuntimeMetadata::V14(metadata) => Ok(metadata
+                .pallets
+                .iter()
+                .map(AnyPalletMetadata::V14)
+                .collect()),
+            RuntimeMetadata::V15(metadata) => Ok(metadata
+                .pallets
+                .iter()
+                .map(AnyPalletMetadata::V15)
+                .collect()),
+            RuntimeMetadata::V16(metadata) => Ok(metadata
+                .pallets
+                .iter()
+                .map(AnyPalletMetadata::V16)
+                .collect()),
+            _ => Err(MetadataError::UnsupportedMetadataVersion {
+                version: self.version(),
+            }),
+        }
+    }
+
     pub fn pallet_metadata(
         &self,
         pallet_name: &str,
@@ -1,3 +1,4 @@
+pub mod constant;
 pub mod extrinsic;
 pub mod metadata;
 pub mod storage;
@@ -3,6 +3,7 @@ use frame_decode::{
     storage::{IntoStorageKeys, decode_storage_value, encode_storage_key},
 };
 use frame_metadata::RuntimeMetadata;
+use scale_decode::IntoVisitor;
 use scale_info_legacy::LookupName;
 use scale_value::{Value, scale::ValueVisitor};
 use thiserror::Error;
@@ -226,11 +227,6 @@ pub fn decode_storage_value_any(
             let types_from_metadata = type_registry_from_metadata(metadata)?;
             historic_types_for_spec.prepend(types_from_metadata);
 
-            let keys: [u8; 0] = [];
-            let key =
-                encode_storage_key("System", "Events", keys, metadata, &historic_types_for_spec)
-                    .unwrap();
-
             Ok(AnyStorageValue::Legacy(Box::new(decode_storage_value(
                 pallet_name,
                 storage_entry_name,
@@ -297,3 +293,129 @@ pub fn decode_storage_value_any(
         }),
     }
 }
+
+/// Decodes any version of storage value directly into `R`, without an intermediate `Value`
+pub fn decode_storage_value_as<R: IntoVisitor>(
+    value: impl AsRef<[u8]>,
+    pallet_name: &str,
+    storage_entry_name: &str,
+    metadata: AnyRuntimeMetadata<'_>,
+    spec_version: u64,
+) -> Result<R, StorageValueDecoderError> {
+    let historic_types = frame_decode::legacy_types::polkadot::relay_chain();
+    let value = &mut value.as_ref();
+
+    match metadata.0 {
+        RuntimeMetadata::V8(metadata) => {
+            let mut historic_types_for_spec = historic_types.for_spec_version(spec_version);
+            let types_from_metadata = type_registry_from_metadata(metadata)?;
+            historic_types_for_spec.prepend(types_from_metadata);
+
+            Ok(decode_storage_value(
+                pallet_name,
+                storage_entry_name,
+                value,
+                metadata,
+                &historic_types_for_spec,
+                R::into_visitor(),
+            )?)
+        }
+        RuntimeMetadata::V9(metadata) => {
+            let mut historic_types_for_spec = historic_types.for_spec_version(spec_version);
+            let types_from_metadata = type_registry_from_metadata(metadata)?;
+            historic_types_for_spec.prepend(types_from_metadata);
+
+            Ok(decode_storage_value(
+                pallet_name,
+                storage_entry_name,
+                value,
+                metadata,
+                &historic_types_for_spec,
+                R::into_visitor(),
+            )?)
+        }
+        RuntimeMetadata::V10(metadata) => {
+            let mut historic_types_for_spec = historic_types.for_spec_version(spec_version);
+            let types_from_metadata = type_registry_from_metadata(metadata)?;
+            historic_types_for_spec.prepend(types_from_metadata);
+
+            Ok(decode_storage_value(
+                pallet_name,
+                storage_entry_name,
+                value,
+                metadata,
+                &historic_types_for_spec,
+                R::into_visitor(),
+            )?)
+        }
+        RuntimeMetadata::V11(metadata) => {
+            let mut historic_types_for_spec = historic_types.for_spec_version(spec_version);
+            let types_from_metadata = type_registry_from_metadata(metadata)?;
+            historic_types_for_spec.prepend(types_from_metadata);
+
+            Ok(decode_storage_value(
+                pallet_name,
+                storage_entry_name,
+                value,
+                metadata,
+                &historic_types_for_spec,
+                R::into_visitor(),
+            )?)
+        }
+        RuntimeMetadata::V12(metadata) => {
+            let mut historic_types_for_spec = historic_types.for_spec_version(spec_version);
+            let types_from_metadata = type_registry_from_metadata(metadata)?;
+            historic_types_for_spec.prepend(types_from_metadata);
+
+            Ok(decode_storage_value(
+                pallet_name,
+                storage_entry_name,
+                value,
+                metadata,
+                &historic_types_for_spec,
+                R::into_visitor(),
+            )?)
+        }
+        RuntimeMetadata::V13(metadata) => {
+            let mut historic_types_for_spec = historic_types.for_spec_version(spec_version);
+            let types_from_metadata = type_registry_from_metadata(metadata)?;
+            historic_types_for_spec.prepend(types_from_metadata);
+
+            Ok(decode_storage_value(
+                pallet_name,
+                storage_entry_name,
+                value,
+                metadata,
+                &historic_types_for_spec,
+                R::into_visitor(),
+            )?)
+        }
+        RuntimeMetadata::V14(metadata) => Ok(decode_storage_value(
+            pallet_name,
+            storage_entry_name,
+            value,
+            metadata,
+            &metadata.types,
+            R::into_visitor(),
+        )?),
+        RuntimeMetadata::V15(metadata) => Ok(decode_storage_value(
+            pallet_name,
+            storage_entry_name,
+            value,
+            metadata,
+            &metadata.types,
+            R::into_visitor(),
+        )?),
+        RuntimeMetadata::V16(metadata) => Ok(decode_storage_value(
+            pallet_name,
+            storage_entry_name,
+            value,
+            metadata,
+            &metadata.types,
+            R::into_visitor(),
+        )?),
+        _ => Err(StorageValueDecoderError::UnsupportedMetadataVersion {
+            version: metadata.version(),
+        }),
+    }
+}
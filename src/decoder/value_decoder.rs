@@ -1,11 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use chrono::{DateTime, Utc};
-use scale_value::{Composite, Primitive, Value, ValueDef};
+use scale_value::{
+    BitSequence, Composite, Primitive, Value, ValueDef, Variant,
+    stringify::custom_formatters::format_hex,
+};
+use serde::de::DeserializeOwned;
 use thiserror::Error;
 
 use crate::decoder::storage::StorageValueDecoderError;
 
+pub use submarine_derive::ValueDecoder;
+
 pub trait ValueDecoder<T> {
     fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
     where
@@ -49,6 +55,16 @@ pub enum ValueDecoderError {
 
     #[error("Timestamp storage value has an invalid value")]
     TimestampValueInvalid { span: String },
+
+    #[error("Transaction signature is invalid at {span}")]
+    TransactionSignatureInvalid { span: String },
+
+    #[error("Integer {value} doesn't fit into {target} at {span}")]
+    IntegerOverflow {
+        value: String,
+        target: String,
+        span: String,
+    },
 }
 
 impl From<StorageValueDecoderError> for ValueDecoderError {
@@ -98,6 +114,12 @@ impl<T> WithErrorSpan for Result<T, ValueDecoderError> {
                 ValueDecoderError::TimestampValueInvalid { span, .. } => {
                     *span = append_span(span, outer_span);
                 }
+                ValueDecoderError::TransactionSignatureInvalid { span, .. } => {
+                    *span = append_span(span, outer_span);
+                }
+                ValueDecoderError::IntegerOverflow { span, .. } => {
+                    *span = append_span(span, outer_span);
+                }
             };
             err
         })
@@ -120,16 +142,127 @@ where
     R: ValueDecoder<T>,
     T: std::fmt::Debug,
 {
+    get_field_with(field_name, record, R::decode)
+}
+
+/// Remove a record field and decode it with a custom decoder function
+pub fn get_field_with<T, R>(
+    field_name: &str,
+    record: &mut HashMap<String, Value<T>>,
+    decode: impl FnOnce(Value<T>) -> Result<R, ValueDecoderError>,
+) -> Result<R, ValueDecoderError> {
     record
         .remove(field_name)
         .ok_or(ValueDecoderError::RecordFieldNotFound {
             field_name: field_name.to_string(),
             span: String::new(),
         })
-        .and_then(R::decode)
+        .and_then(decode)
         .add_error_span(field_name)
 }
 
+/// Decode the next element of a tuple, `index` is only used for the error span
+pub fn get_element<T, R>(
+    index: usize,
+    elements: &mut std::vec::IntoIter<Value<T>>,
+) -> Result<R, ValueDecoderError>
+where
+    R: ValueDecoder<T>,
+    T: std::fmt::Debug,
+{
+    get_element_with(index, elements, R::decode)
+}
+
+/// Decode the next element of a tuple with a custom decoder function
+pub fn get_element_with<T, R>(
+    index: usize,
+    elements: &mut std::vec::IntoIter<Value<T>>,
+    decode: impl FnOnce(Value<T>) -> Result<R, ValueDecoderError>,
+) -> Result<R, ValueDecoderError> {
+    elements
+        .next()
+        .ok_or(ValueDecoderError::UnexpectedVectorLength {
+            expected: index + 1,
+            got: index,
+            span: String::new(),
+        })
+        .and_then(decode)
+        .add_error_span(&index.to_string())
+}
+
+/// Decode a `ValueDef::Composite(_)` Value
+pub fn decode_composite<T>(value: Value<T>) -> Result<Composite<T>, ValueDecoderError>
+where
+    T: std::fmt::Debug,
+{
+    match value.value {
+        ValueDef::Composite(composite) => Ok(composite),
+        other => Err(ValueDecoderError::UnexpectedValueType {
+            span: String::new(),
+            expected: "ValueDef::Composite(_)".to_string(),
+            got: format!("{other:?}"),
+        }),
+    }
+}
+
+/// Decode a `ValueDef::Variant(_)` Value to its name and values
+pub fn decode_variant<T>(value: Value<T>) -> Result<(String, Composite<T>), ValueDecoderError>
+where
+    T: std::fmt::Debug,
+{
+    match value.value {
+        ValueDef::Variant(Variant { name, values }) => Ok((name, values)),
+        other => Err(ValueDecoderError::UnexpectedValueType {
+            span: String::new(),
+            expected: "ValueDef::Variant(Variant { .. })".to_string(),
+            got: format!("{other:?}"),
+        }),
+    }
+}
+
+/// Convert a `Composite::Named(_)` to a record
+/// Empty composites are always unnamed, so they are accepted as an empty record
+pub fn composite_to_record<T>(
+    composite: Composite<T>,
+) -> Result<HashMap<String, Value<T>>, ValueDecoderError>
+where
+    T: std::fmt::Debug,
+{
+    match composite {
+        Composite::Named(named) => Ok(named.into_iter().collect()),
+        Composite::Unnamed(vec) if vec.is_empty() => Ok(HashMap::new()),
+        other => Err(ValueDecoderError::UnexpectedValueType {
+            span: String::new(),
+            expected: "Composite::Named(_)".to_string(),
+            got: format!("{other:?}"),
+        }),
+    }
+}
+
+/// Convert a `Composite::Unnamed(_)` with exactly `len` elements to an iterator of its elements
+pub fn composite_to_elements<T>(
+    composite: Composite<T>,
+    len: usize,
+) -> Result<std::vec::IntoIter<Value<T>>, ValueDecoderError>
+where
+    T: std::fmt::Debug,
+{
+    match composite {
+        Composite::Unnamed(vec) if vec.len() == len => Ok(vec.into_iter()),
+        Composite::Unnamed(vec) => Err(ValueDecoderError::UnexpectedVectorLength {
+            expected: len,
+            got: vec.len(),
+            span: String::new(),
+        }),
+        Composite::Named(named) if named.is_empty() && len == 0 => Ok(Vec::new().into_iter()),
+        other => Err(ValueDecoderError::UnexpectedValueType {
+            span: String::new(),
+            expected: "Composite::Unnamed(_)".to_string(),
+            got: format!("{other:?}"),
+        }),
+    }
+}
+
 /// Decode a `ValueDef::Composite(Composite::Unnamed(vec))` Value with exactly one element
 pub fn decode_singleton<T, R>(value: Value<T>) -> Result<R, ValueDecoderError>
 where
@@ -158,6 +291,17 @@ where
     }
 }
 
+/// Compact rendering of a value, byte sequences as hex strings
+pub fn format_value<T>(value: &Value<T>) -> String {
+    let mut formatted = String::new();
+    scale_value::stringify::to_writer_custom()
+        .compact()
+        .add_custom_formatter(|value, writer| format_hex(value, writer))
+        .write(value, &mut formatted)
+        .expect("writing to a String can't fail");
+    formatted
+}
+
 impl<T, R> ValueDecoder<T> for Vec<R>
 where
     R: ValueDecoder<T>,
@@ -234,18 +378,126 @@ where
 
 impl<T> ValueDecoder<T> for DateTime<Utc> {
     fn decode(value: Value<T>) -> Result<DateTime<Utc>, ValueDecoderError>
+    where
+        T: std::fmt::Debug,
+    {
+        let millis = decode_integer(value)?;
+        DateTime::from_timestamp_millis(millis).ok_or(ValueDecoderError::TimestampValueInvalid {
+            span: String::new(),
+        })
+    }
+}
+
+/// Decode any integer primitive into `R`, failing if the value doesn't fit
+/// Compact encoded integers are decoded to the same primitives
+fn decode_integer<T, R>(value: Value<T>) -> Result<R, ValueDecoderError>
+where
+    T: std::fmt::Debug,
+    R: TryFrom<u128> + TryFrom<i128>,
+{
+    let overflow = |value: String| ValueDecoderError::IntegerOverflow {
+        value,
+        target: std::any::type_name::<R>().to_string(),
+        span: String::new(),
+    };
+
+    match value.value {
+        ValueDef::Primitive(Primitive::U128(uint)) => {
+            R::try_from(uint).map_err(|_| overflow(uint.to_string()))
+        }
+        ValueDef::Primitive(Primitive::I128(int)) => {
+            R::try_from(int).map_err(|_| overflow(int.to_string()))
+        }
+        // 256 bit integers are little endian, they only fit if the high half is a zero or sign extension
+        ValueDef::Primitive(Primitive::U256(bytes)) => {
+            let (low, high) = bytes.split_at(16);
+            let uint = u128::from_le_bytes(low.try_into().unwrap());
+            if high.iter().all(|byte| *byte == 0) {
+                R::try_from(uint).ok()
+            } else {
+                None
+            }
+            .ok_or_else(|| overflow(format_256_bit_integer(bytes)))
+        }
+        ValueDef::Primitive(Primitive::I256(bytes)) => {
+            let (low, high) = bytes.split_at(16);
+            let int = i128::from_le_bytes(low.try_into().unwrap());
+            let sign_extension = if int < 0 { 0xff } else { 0 };
+            if high.iter().all(|byte| *byte == sign_extension) {
+                R::try_from(int).ok()
+            } else {
+                None
+            }
+            .ok_or_else(|| overflow(format_256_bit_integer(bytes)))
+        }
+        other => Err(ValueDecoderError::UnexpectedValueType {
+            expected: "ValueDef::Primitive(Primitive::U128(_) | Primitive::I128(_) | Primitive::U256(_) | Primitive::I256(_))".to_string(),
+            got: format!("{other:?}"),
+            span: String::new(),
+        }),
+    }
+}
+
+fn format_256_bit_integer(mut bytes: [u8; 32]) -> String {
+    bytes.reverse();
+    format!("0x{}", hex::encode(bytes))
+}
+
+macro_rules! impl_integer_decoder {
+    ($($int:ty),+) => {
+        $(
+            impl<T> ValueDecoder<T> for $int {
+                fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
+                where
+                    T: std::fmt::Debug,
+                {
+                    decode_integer(value)
+                }
+            }
+        )+
+    };
+}
+
+impl_integer_decoder!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+macro_rules! impl_primitive_decoder {
+    ($($ty:ty => $primitive:ident),+) => {
+        $(
+            impl<T> ValueDecoder<T> for $ty {
+                fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
+                where
+                    T: std::fmt::Debug,
+                {
+                    match value.value {
+                        ValueDef::Primitive(Primitive::$primitive(primitive)) => Ok(primitive),
+                        other => Err(ValueDecoderError::UnexpectedValueType {
+                            expected: concat!(
+                                "ValueDef::Primitive(Primitive::",
+                                stringify!($primitive),
+                                "(_))"
+                            )
+                            .to_string(),
+                            got: format!("{other:?}"),
+                            span: String::new(),
+                        }),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_primitive_decoder!(bool => Bool, char => Char, String => String);
+
+impl<T> ValueDecoder<T> for BitSequence {
+    fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
     where
         T: std::fmt::Debug,
     {
         match value.value {
-            ValueDef::Primitive(Primitive::U128(uint)) => DateTime::from_timestamp_millis(
-                uint as i64,
-            )
-            .ok_or(ValueDecoderError::TimestampValueInvalid {
-                span: String::new(),
-            }),
+            ValueDef::BitSequence(bits) => Ok(bits),
             other => Err(ValueDecoderError::UnexpectedValueType {
-                expected: "ValueDef::Primitive(Primitive::U128(_))".to_string(),
+                expected: "ValueDef::BitSequence(_)".to_string(),
                 got: format!("{other:?}"),
                 span: String::new(),
             }),
@@ -253,38 +505,116 @@ impl<T> ValueDecoder<T> for DateTime<Utc> {
     }
 }
 
-impl<T> ValueDecoder<T> for u8 {
+impl<T> ValueDecoder<T> for () {
     fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
     where
         T: std::fmt::Debug,
     {
-        match value.value {
-            ValueDef::Primitive(Primitive::U128(uint)) => Ok(uint as u8),
-            other => Err(ValueDecoderError::UnexpectedValueType {
-                expected: "ValueDef::Primitive(Primitive::U128(_))".to_string(),
-                got: format!("{other:?}"),
+        composite_to_elements(decode_composite(value)?, 0)?;
+        Ok(())
+    }
+}
+
+macro_rules! impl_tuple_decoder {
+    ($len:literal; $($name:ident $index:literal),+) => {
+        impl<T, $($name),+> ValueDecoder<T> for ($($name,)+)
+        where
+            $($name: ValueDecoder<T>),+
+        {
+            fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
+            where
+                T: std::fmt::Debug,
+            {
+                let mut elements = composite_to_elements(decode_composite(value)?, $len)?;
+                Ok(($(get_element($index, &mut elements)?,)+))
+            }
+        }
+    };
+}
+
+impl_tuple_decoder!(1; A 0);
+impl_tuple_decoder!(2; A 0, B 1);
+impl_tuple_decoder!(3; A 0, B 1, C 2);
+impl_tuple_decoder!(4; A 0, B 1, C 2, D 3);
+impl_tuple_decoder!(5; A 0, B 1, C 2, D 3, E 4);
+impl_tuple_decoder!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+
+impl<T, R> ValueDecoder<T> for Option<R>
+where
+    R: ValueDecoder<T>,
+{
+    fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
+    where
+        T: std::fmt::Debug,
+    {
+        let (variant_name, composite) = decode_variant(value)?;
+        match &variant_name[..] {
+            "None" => composite_to_elements(composite, 0)
+                .map(|_| None)
+                .add_error_span("None"),
+            "Some" => composite_to_elements(composite, 1)
+                .and_then(|mut elements| get_element(0, &mut elements))
+                .map(Some)
+                .add_error_span("Some"),
+            _ => Err(ValueDecoderError::UnexpectedVariantName {
+                variant_name,
                 span: String::new(),
             }),
         }
     }
 }
 
-impl<T> ValueDecoder<T> for u64 {
+impl<T, R, E> ValueDecoder<T> for Result<R, E>
+where
+    R: ValueDecoder<T>,
+    E: ValueDecoder<T>,
+{
     fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
     where
         T: std::fmt::Debug,
     {
-        match value.value {
-            ValueDef::Primitive(Primitive::U128(uint)) => Ok(uint as u64),
-            other => Err(ValueDecoderError::UnexpectedValueType {
-                expected: "ValueDef::Primitive(Primitive::U128(_))".to_string(),
-                got: format!("{other:?}"),
+        let (variant_name, composite) = decode_variant(value)?;
+        match &variant_name[..] {
+            "Ok" => composite_to_elements(composite, 1)
+                .and_then(|mut elements| get_element(0, &mut elements))
+                .map(Ok)
+                .add_error_span("Ok"),
+            "Err" => composite_to_elements(composite, 1)
+                .and_then(|mut elements| get_element(0, &mut elements))
+                .map(Err)
+                .add_error_span("Err"),
+            _ => Err(ValueDecoderError::UnexpectedVariantName {
+                variant_name,
                 span: String::new(),
             }),
         }
     }
 }
 
+/// Maps are encoded as a vector of key-value tuples
+impl<T, K, V> ValueDecoder<T> for BTreeMap<K, V>
+where
+    K: ValueDecoder<T> + Ord,
+    V: ValueDecoder<T>,
+{
+    fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
+    where
+        T: std::fmt::Debug,
+    {
+        let entries: Vec<(K, V)> = ValueDecoder::decode(value)?;
+        Ok(entries.into_iter().collect())
+    }
+}
+
+/// Decode a Value into any `serde::Deserialize` type
+/// Also usable as the decoder of `get_field_with` and `#[value_decoder(with = "deserialize")]`
+pub fn deserialize<T, R>(value: Value<T>) -> Result<R, ValueDecoderError>
+where
+    R: DeserializeOwned,
+{
+    Ok(scale_value::serde::from_value(value)?)
+}
+
 impl<T> ValueDecoder<T> for Value<T> {
     fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
     where
@@ -293,3 +623,134 @@ impl<T> ValueDecoder<T> for Value<T> {
         Ok(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use scale_value::{Composite, Value};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, ValueDecoder)]
+    struct Account {
+        #[value_decoder(rename = "free_balance")]
+        free: u128,
+        #[value_decoder(default)]
+        frozen: u128,
+        #[value_decoder(with = "decode_flag")]
+        flags: bool,
+    }
+
+    #[derive(Debug, PartialEq, ValueDecoder)]
+    #[value_decoder(transparent)]
+    struct Index(u32);
+
+    #[derive(Debug, PartialEq, ValueDecoder)]
+    enum Phase {
+        #[value_decoder(rename = "ApplyExtrinsic")]
+        Apply(Index),
+        Finalization,
+    }
+
+    fn decode_flag<T: std::fmt::Debug>(value: Value<T>) -> Result<bool, ValueDecoderError> {
+        let flags: u8 = ValueDecoder::decode(value)?;
+        Ok(flags != 0)
+    }
+
+    fn account(fields: Vec<(&str, Value<()>)>) -> Value<()> {
+        Value::named_composite(fields)
+    }
+
+    #[test]
+    fn derived_struct_reads_renamed_default_and_with_fields() {
+        let value = account(vec![
+            ("free_balance", Value::u128(10)),
+            ("flags", Value::u128(1)),
+            ("reserved", Value::u128(3)),
+        ]);
+
+        assert_eq!(
+            Account::decode(value).unwrap(),
+            Account {
+                free: 10,
+                frozen: 0,
+                flags: true,
+            }
+        );
+    }
+
+    #[test]
+    fn derived_transparent_and_enum_decode() {
+        assert_eq!(Index::decode(Value::u128(7)).unwrap(), Index(7));
+        assert_eq!(
+            Phase::decode(Value::variant(
+                "ApplyExtrinsic",
+                Composite::unnamed([Value::u128(2)]),
+            ))
+            .unwrap(),
+            Phase::Apply(Index(2))
+        );
+        assert_eq!(
+            Phase::decode(Value::variant("Finalization", Composite::unnamed([]))).unwrap(),
+            Phase::Finalization
+        );
+    }
+
+    #[test]
+    fn derived_errors_are_spanned_with_the_path_to_the_value() {
+        let missing = Account::decode(account(vec![("flags", Value::u128(0))])).unwrap_err();
+        assert!(matches!(
+            missing,
+            ValueDecoderError::RecordFieldNotFound { field_name, span }
+                if field_name == "free_balance" && span == "free_balance"
+        ));
+
+        let overflow = Phase::decode(Value::variant(
+            "ApplyExtrinsic",
+            Composite::unnamed([Value::u128(u64::MAX as u128)]),
+        ))
+        .unwrap_err();
+        assert!(matches!(
+            overflow,
+            ValueDecoderError::IntegerOverflow { span, .. } if span == "ApplyExtrinsic.0"
+        ));
+
+        let unknown =
+            Phase::decode(Value::variant("Initialization", Composite::unnamed([]))).unwrap_err();
+        assert!(matches!(
+            unknown,
+            ValueDecoderError::UnexpectedVariantName { variant_name, .. }
+                if variant_name == "Initialization"
+        ));
+    }
+
+    #[test]
+    fn serde_types_round_trip_through_values() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Transfer {
+            amount: u128,
+            memo: Option<String>,
+            hash: [u8; 4],
+        }
+
+        let transfer = Transfer {
+            amount: 1_000_000_000_000,
+            memo: Some("rent".to_string()),
+            hash: [1, 2, 3, 4],
+        };
+        let value = scale_value::serde::to_value(&transfer).unwrap();
+
+        assert_eq!(deserialize::<_, Transfer>(value).unwrap(), transfer);
+    }
+
+    #[test]
+    fn integers_are_not_truncated() {
+        assert!(matches!(
+            u32::decode(Value::u128(u32::MAX as u128 + 1)),
+            Err(ValueDecoderError::IntegerOverflow { .. })
+        ));
+        assert_eq!(
+            u64::decode(Value::u128(u32::MAX as u128 + 1)).unwrap(),
+            1 << 32
+        );
+    }
+}
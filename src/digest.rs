@@ -0,0 +1,106 @@
+use parity_scale_codec::Decode;
+use sc_consensus_babe::{BABE_ENGINE_ID, ConsensusLog, NextConfigDescriptor, PreDigest};
+use sp_runtime::{ConsensusEngineId, DigestItem};
+
+use crate::pallets::{
+    aura::digest::{AURA_ENGINE_ID, AuraConsensusLog},
+    babe::digest::BabeSlotClaim,
+};
+
+/// A digest log in human readable form
+#[derive(Debug, Clone)]
+pub struct DigestLogDescription {
+    pub kind: &'static str,
+    pub engine: Option<String>,
+    pub details: String,
+}
+
+fn engine_name(engine_id: &ConsensusEngineId) -> String {
+    String::from_utf8_lossy(engine_id).into_owned()
+}
+
+/// Decode the BABE and AURA pre-runtime, consensus and seal digests, other digests are shown as hex
+pub fn describe_digest_log(
+    log: &DigestItem,
+) -> Result<DigestLogDescription, parity_scale_codec::Error> {
+    let (kind, engine, details) = match log {
+        DigestItem::PreRuntime(engine_id, bytes) => {
+            let details = match *engine_id {
+                BABE_ENGINE_ID => {
+                    let claim = BabeSlotClaim::from(PreDigest::decode(&mut &bytes[..])?);
+                    format!(
+                        "{} claim of slot {} by authority {}",
+                        claim.claim_type, claim.slot, claim.authority_index
+                    )
+                }
+                AURA_ENGINE_ID => format!("Slot {}", u64::decode(&mut &bytes[..])?),
+                _ => format!("0x{}", hex::encode(bytes)),
+            };
+            ("PreRuntime", Some(engine_id), details)
+        }
+        DigestItem::Consensus(engine_id, bytes) => {
+            let details = match *engine_id {
+                BABE_ENGINE_ID => match ConsensusLog::decode(&mut &bytes[..])? {
+                    ConsensusLog::NextEpochData(next_epoch) => format!(
+                        "Next epoch data: {} authorities, randomness 0x{}",
+                        next_epoch.authorities.len(),
+                        hex::encode(next_epoch.randomness)
+                    ),
+                    ConsensusLog::OnDisabled(authority_index) => {
+                        format!("Authority {authority_index} disabled")
+                    }
+                    ConsensusLog::NextConfigData(NextConfigDescriptor::V1 { c, allowed_slots }) => {
+                        format!("Next config data: c = {}/{}, {allowed_slots:?}", c.0, c.1)
+                    }
+                },
+                AURA_ENGINE_ID => match AuraConsensusLog::decode(&mut &bytes[..])? {
+                    AuraConsensusLog::AuthoritiesChange(authorities) => {
+                        format!("Authorities change: {} authorities", authorities.len())
+                    }
+                    AuraConsensusLog::OnDisabled(authority_index) => {
+                        format!("Authority {authority_index} disabled")
+                    }
+                },
+                _ => format!("0x{}", hex::encode(bytes)),
+            };
+            ("Consensus", Some(engine_id), details)
+        }
+        DigestItem::Seal(engine_id, bytes) => {
+            let details = match *engine_id {
+                BABE_ENGINE_ID | AURA_ENGINE_ID => {
+                    let signature = <[u8; 64]>::decode(&mut &bytes[..])?;
+                    format!("Signature 0x{}", hex::encode(signature))
+                }
+                _ => format!("0x{}", hex::encode(bytes)),
+            };
+            ("Seal", Some(engine_id), details)
+        }
+        DigestItem::Other(bytes) => ("Other", None, format!("0x{}", hex::encode(bytes))),
+        DigestItem::RuntimeEnvironmentUpdated => ("RuntimeEnvironmentUpdated", None, String::new()),
+    };
+
+    Ok(DigestLogDescription {
+        kind,
+        engine: engine.map(engine_name),
+        details,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_scale_codec::Encode;
+
+    use super::*;
+
+    #[test]
+    fn aura_disabled_authority_index_is_u32() {
+        let mut bytes = vec![2];
+        bytes.extend(7u32.encode());
+        let log = DigestItem::Consensus(AURA_ENGINE_ID, bytes);
+
+        let description = describe_digest_log(&log).unwrap();
+        assert_eq!(description.kind, "Consensus");
+        assert_eq!(description.engine.as_deref(), Some("AURA"));
+        assert_eq!(description.details, "Authority 7 disabled");
+    }
+}
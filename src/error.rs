@@ -3,12 +3,18 @@ use tracing::subscriber::SetGlobalDefaultError;
 
 use crate::{
     decoder::{
+        constant::ConstantDecoderError,
         extrinsic::ExtrinsicDecoderError,
         metadata::MetadataError,
         storage::{StorageKeyEncoderError, StorageValueDecoderError},
         value_decoder::ValueDecoderError,
     },
-    node_rpc::{client::NodeRPCError, models::StorageKeyHex},
+    indexer::IndexerError,
+    node_rpc::{
+        client::NodeRPCError,
+        models::{BlockHashHex, BlockNumberHex, StorageKeyHex},
+    },
+    pallets::ethereum::abi::AbiError,
 };
 
 #[allow(clippy::enum_variant_names)]
@@ -29,6 +35,9 @@ pub enum Error {
     #[error(transparent)]
     StorageValueDecoderError(#[from] StorageValueDecoderError),
 
+    #[error(transparent)]
+    ConstantDecoderError(#[from] ConstantDecoderError),
+
     #[error(
         "Storage value not found in storage entry {storage_entry_name} of pallet {pallet_name}{:?}. Storage key hash {storage_entry_key_hash}",
         storage_entry_keys.as_ref().map(|ref x| format!(" with key(s) ({x})")).unwrap_or(String::new())
@@ -49,9 +58,78 @@ pub enum Error {
     #[error("Failed to parse Digest Logs: {0}")]
     ParsingDigestLogsFailed(parity_scale_codec::Error),
 
+    #[error("Failed to parse block number {0}: {1}")]
+    ParsingBlockNumberFailed(BlockNumberHex, std::num::ParseIntError),
+
+    #[error("Failed to parse hex string {0}: {1}")]
+    ParsingHexFailed(String, hex::FromHexError),
+
+    #[error("Header of block {block_hash} hashes to {computed}")]
+    HeaderHashMismatch {
+        block_hash: BlockHashHex,
+        computed: BlockHashHex,
+    },
+
+    #[error("Extrinsics of block {block_hash} have root {computed}, the header has {expected}")]
+    ExtrinsicsRootMismatch {
+        block_hash: BlockHashHex,
+        expected: String,
+        computed: String,
+    },
+
+    #[error("Invalid storage proof of key {storage_key} at block {block_hash}: {reason}")]
+    InvalidStorageProof {
+        storage_key: StorageKeyHex,
+        block_hash: BlockHashHex,
+        reason: String,
+    },
+
+    #[error("Block {0} has no runtime code")]
+    RuntimeCodeNotFound(BlockHashHex),
+
+    #[error("The runtime code has no embedded version")]
+    RuntimeVersionNotEmbedded,
+
+    #[error("Failed to execute the runtime code: {0}")]
+    ExecutingRuntimeFailed(String),
+
+    #[error("The node reports runtime {reported} at block {block_hash}, its code is {embedded}")]
+    RuntimeVersionMismatch {
+        block_hash: BlockHashHex,
+        reported: String,
+        embedded: String,
+    },
+
+    #[error("Failed to parse GRANDPA authorities: {0}")]
+    ParsingGrandpaAuthoritiesFailed(parity_scale_codec::Error),
+
+    #[error("Failed to parse GRANDPA justification: {0}")]
+    ParsingJustificationFailed(parity_scale_codec::Error),
+
+    #[error("No GRANDPA justification or finality proof found for block {0}")]
+    JustificationNotFound(u32),
+
     #[error("Couldn't find validator by authority index {0}")]
     ValidatorNotFoundForIndex(u32),
 
+    #[error("The runtime is incompatible with {incompatible_count} storage schema(s)")]
+    IncompatibleRuntime { incompatible_count: usize },
+
+    #[error("Invalid block range: {from_block} must be lower than {to_block}")]
+    InvalidBlockRange { from_block: u32, to_block: u32 },
+
     #[error(transparent)]
     MetadataError(#[from] MetadataError),
+
+    #[error(transparent)]
+    AbiError(#[from] AbiError),
+
+    #[error(transparent)]
+    IndexerError(#[from] IndexerError),
+
+    #[error("Failed to start the Ethereum JSON-RPC server: {0}")]
+    EthRpcServerFailed(std::io::Error),
+
+    #[error("Failed to start the REST API server: {0}")]
+    ApiServerFailed(std::io::Error),
 }
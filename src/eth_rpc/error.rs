@@ -0,0 +1,34 @@
+use jsonrpsee::types::{
+    ErrorObject, ErrorObjectOwned,
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+};
+use thiserror::Error;
+
+use crate::error::Error;
+
+#[derive(Debug, Error)]
+pub enum EthRpcError {
+    #[error(transparent)]
+    FetchFailed(#[from] Error),
+
+    #[error("Block range of {range} blocks exceeds the limit of {limit} blocks")]
+    BlockRangeTooLarge { range: u32, limit: u32 },
+
+    #[error("Invalid block range: {from_block} must be lower than {to_block}")]
+    InvalidBlockRange { from_block: u32, to_block: u32 },
+
+    #[error("Filtering logs by block hash is not supported, use fromBlock and toBlock")]
+    BlockHashFilterUnsupported,
+}
+
+impl From<EthRpcError> for ErrorObjectOwned {
+    fn from(error: EthRpcError) -> Self {
+        let code = match error {
+            EthRpcError::FetchFailed(_) => INTERNAL_ERROR_CODE,
+            EthRpcError::BlockRangeTooLarge { .. }
+            | EthRpcError::InvalidBlockRange { .. }
+            | EthRpcError::BlockHashFilterUnsupported => INVALID_PARAMS_CODE,
+        };
+        ErrorObject::owned(code, error.to_string(), None::<()>)
+    }
+}
@@ -0,0 +1,594 @@
+use ethereum::{
+    AccessListItem, AuthorizationListItem, Header, Log, ReceiptAny, TransactionAction,
+    TransactionV3,
+};
+use ethereum_types::{Bloom, H64, H160, H256, U64, U256};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+use crate::{
+    node_rpc::models::BlockNumberHex,
+    pallets::ethereum::transaction::{BlockTransaction, recover_sender},
+};
+
+/// Block parameter of the Ethereum JSON-RPC methods
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockTag {
+    Number(u32),
+    Earliest,
+    Latest,
+    Pending,
+    Safe,
+    Finalized,
+}
+
+impl<'de> Deserialize<'de> for BlockTag {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tag = String::deserialize(deserializer)?;
+        Ok(match &tag[..] {
+            "earliest" => BlockTag::Earliest,
+            "latest" => BlockTag::Latest,
+            "pending" => BlockTag::Pending,
+            "safe" => BlockTag::Safe,
+            "finalized" => BlockTag::Finalized,
+            _ => {
+                BlockTag::Number(u32::try_from(&BlockNumberHex(tag.clone())).map_err(|err| {
+                    D::Error::custom(format!("invalid block number {tag}: {err}"))
+                })?)
+            }
+        })
+    }
+}
+
+/// Filter parameter of a single value or a list of values
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T: PartialEq> OneOrMany<T> {
+    fn contains(&self, value: &T) -> bool {
+        match self {
+            OneOrMany::One(one) => one == value,
+            OneOrMany::Many(many) => many.contains(value),
+        }
+    }
+}
+
+/// Parameter of `eth_getLogs`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogFilter {
+    pub from_block: Option<BlockTag>,
+    pub to_block: Option<BlockTag>,
+    pub block_hash: Option<H256>,
+    pub address: Option<OneOrMany<H160>>,
+    pub topics: Option<Vec<Option<OneOrMany<H256>>>>,
+}
+
+impl LogFilter {
+    /// A log matches if its address is in `address` and every topic position matches, `null`
+    /// positions match any topic
+    pub fn matches(&self, log: &EthLog) -> bool {
+        let address_matches = self
+            .address
+            .as_ref()
+            .is_none_or(|address| address.contains(&log.address));
+
+        let topics_match = self
+            .topics
+            .iter()
+            .flatten()
+            .enumerate()
+            .all(|(i, topic)| match topic {
+                Some(topic) => log
+                    .topics
+                    .get(i)
+                    .is_some_and(|log_topic| topic.contains(log_topic)),
+                None => true,
+            });
+
+        address_matches && topics_match
+    }
+}
+
+/// An Ethereum block decoded from the Substrate storage, with its transactions matched to their
+/// receipts and statuses
+#[derive(Debug, Clone)]
+pub struct EthereumBlock {
+    pub hash: H256,
+    pub header: Header,
+    pub transactions: Vec<BlockTransaction>,
+    /// Base fee the transactions were charged, `None` without a `BaseFee` pallet
+    pub base_fee_per_gas: Option<U256>,
+}
+
+impl EthereumBlock {
+    pub fn number(&self) -> U64 {
+        U64::from(self.header.number.low_u64())
+    }
+
+    pub fn position(&self, transaction_hash: &H256) -> Option<usize> {
+        self.transactions
+            .iter()
+            .position(|transaction| transaction.transaction.hash() == *transaction_hash)
+    }
+
+    /// Logs of every transaction, with their transaction index and block-wide log index
+    pub fn logs(&self) -> impl Iterator<Item = EthLog> + '_ {
+        self.transactions
+            .iter()
+            .enumerate()
+            .flat_map(|(transaction_index, transaction)| {
+                transaction
+                    .logs()
+                    .iter()
+                    .map(move |log| (transaction_index, transaction, log))
+            })
+            .enumerate()
+            .map(|(log_index, (transaction_index, transaction, log))| {
+                EthLog::new(self, transaction_index, transaction, log_index, log)
+            })
+    }
+}
+
+fn serialize_hex<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum EthBlockTransactions {
+    Hashes(Vec<H256>),
+    Full(Vec<EthTransaction>),
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthBlock {
+    pub hash: H256,
+    pub parent_hash: H256,
+    pub sha3_uncles: H256,
+    pub miner: H160,
+    pub state_root: H256,
+    pub transactions_root: H256,
+    pub receipts_root: H256,
+    pub logs_bloom: Bloom,
+    pub difficulty: U256,
+    pub number: U64,
+    pub gas_limit: U256,
+    pub gas_used: U256,
+    pub timestamp: U64,
+    #[serde(serialize_with = "serialize_hex")]
+    pub extra_data: Vec<u8>,
+    pub mix_hash: H256,
+    pub nonce: H64,
+    pub uncles: Vec<H256>,
+    pub transactions: EthBlockTransactions,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U256>,
+}
+
+impl EthBlock {
+    /// `full` includes the transaction objects instead of their hashes
+    pub fn new(block: &EthereumBlock, full: bool) -> Self {
+        let header = &block.header;
+        let transactions = if full {
+            EthBlockTransactions::Full(
+                (0..block.transactions.len())
+                    .map(|index| EthTransaction::new(block, index))
+                    .collect(),
+            )
+        } else {
+            EthBlockTransactions::Hashes(
+                block
+                    .transactions
+                    .iter()
+                    .map(|transaction| transaction.transaction.hash())
+                    .collect(),
+            )
+        };
+
+        Self {
+            hash: block.hash,
+            parent_hash: header.parent_hash,
+            sha3_uncles: header.ommers_hash,
+            miner: header.beneficiary,
+            state_root: header.state_root,
+            transactions_root: header.transactions_root,
+            receipts_root: header.receipts_root,
+            logs_bloom: header.logs_bloom,
+            difficulty: header.difficulty,
+            number: block.number(),
+            gas_limit: header.gas_limit,
+            gas_used: header.gas_used,
+            // Frontier stores the timestamp in milliseconds
+            timestamp: U64::from(header.timestamp / 1000),
+            extra_data: header.extra_data.clone(),
+            mix_hash: header.mix_hash,
+            nonce: header.nonce,
+            uncles: Vec::new(),
+            transactions,
+            base_fee_per_gas: block.base_fee_per_gas,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthAccessListItem {
+    pub address: H160,
+    pub storage_keys: Vec<H256>,
+}
+
+impl From<&AccessListItem> for EthAccessListItem {
+    fn from(item: &AccessListItem) -> Self {
+        Self {
+            address: item.address,
+            storage_keys: item.storage_keys.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthAuthorizationListItem {
+    pub chain_id: U64,
+    pub address: H160,
+    pub nonce: U256,
+    pub y_parity: U64,
+    pub r: U256,
+    pub s: U256,
+}
+
+impl From<&AuthorizationListItem> for EthAuthorizationListItem {
+    fn from(item: &AuthorizationListItem) -> Self {
+        Self {
+            chain_id: U64::from(item.chain_id),
+            address: item.address,
+            nonce: item.nonce,
+            y_parity: U64::from(u64::from(item.signature.odd_y_parity)),
+            r: U256::from_big_endian(item.signature.r.as_bytes()),
+            s: U256::from_big_endian(item.signature.s.as_bytes()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthTransaction {
+    pub hash: H256,
+    pub nonce: U256,
+    pub block_hash: H256,
+    pub block_number: U64,
+    pub transaction_index: U64,
+    pub from: Option<H160>,
+    pub to: Option<H160>,
+    pub value: U256,
+    pub gas: U256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<U256>,
+    #[serde(serialize_with = "serialize_hex")]
+    pub input: Vec<u8>,
+    #[serde(rename = "type")]
+    pub transaction_type: U64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<U64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_list: Option<Vec<EthAccessListItem>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization_list: Option<Vec<EthAuthorizationListItem>>,
+    pub v: U64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y_parity: Option<U64>,
+    pub r: U256,
+    pub s: U256,
+}
+
+fn to_address(action: TransactionAction) -> Option<H160> {
+    match action {
+        TransactionAction::Call(address) => Some(address),
+        TransactionAction::Create => None,
+    }
+}
+
+fn to_u256(value: &H256) -> U256 {
+    U256::from_big_endian(value.as_bytes())
+}
+
+/// EIP-2718 transaction type number
+fn transaction_type_number(transaction: &TransactionV3) -> U64 {
+    U64::from(match transaction {
+        TransactionV3::Legacy(_) => 0,
+        TransactionV3::EIP2930(_) => 1,
+        TransactionV3::EIP1559(_) => 2,
+        TransactionV3::EIP7702(_) => 4,
+    })
+}
+
+/// Sender from the transaction status, recovered from the signature for blocks without statuses
+fn sender(transaction: &BlockTransaction) -> Option<H160> {
+    transaction
+        .status
+        .as_ref()
+        .map(|status| status.from)
+        .or_else(|| recover_sender(&transaction.transaction).ok())
+}
+
+impl EthTransaction {
+    pub fn new(block: &EthereumBlock, index: usize) -> Self {
+        let block_transaction = &block.transactions[index];
+        let transaction = &block_transaction.transaction;
+
+        let mut eth_transaction = Self {
+            hash: transaction.hash(),
+            nonce: U256::zero(),
+            block_hash: block.hash,
+            block_number: block.number(),
+            transaction_index: U64::from(index),
+            from: sender(block_transaction),
+            to: None,
+            value: U256::zero(),
+            gas: U256::zero(),
+            gas_price: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            input: Vec::new(),
+            transaction_type: transaction_type_number(transaction),
+            chain_id: None,
+            access_list: None,
+            authorization_list: None,
+            v: U64::zero(),
+            y_parity: None,
+            r: U256::zero(),
+            s: U256::zero(),
+        };
+
+        match transaction {
+            TransactionV3::Legacy(transaction) => {
+                eth_transaction.nonce = transaction.nonce;
+                eth_transaction.to = to_address(transaction.action);
+                eth_transaction.value = transaction.value;
+                eth_transaction.gas = transaction.gas_limit;
+                eth_transaction.gas_price = Some(transaction.gas_price);
+                eth_transaction.input = transaction.input.clone();
+                eth_transaction.chain_id = transaction.signature.chain_id().map(U64::from);
+                eth_transaction.v = U64::from(transaction.signature.v());
+                eth_transaction.r = to_u256(transaction.signature.r());
+                eth_transaction.s = to_u256(transaction.signature.s());
+            }
+            TransactionV3::EIP2930(transaction) => {
+                eth_transaction.nonce = transaction.nonce;
+                eth_transaction.to = to_address(transaction.action);
+                eth_transaction.value = transaction.value;
+                eth_transaction.gas = transaction.gas_limit;
+                eth_transaction.gas_price = Some(transaction.gas_price);
+                eth_transaction.input = transaction.input.clone();
+                eth_transaction.chain_id = Some(U64::from(transaction.chain_id));
+                eth_transaction.access_list =
+                    Some(transaction.access_list.iter().map(Into::into).collect());
+                let y_parity = U64::from(u64::from(transaction.signature.odd_y_parity()));
+                eth_transaction.v = y_parity;
+                eth_transaction.y_parity = Some(y_parity);
+                eth_transaction.r = to_u256(transaction.signature.r());
+                eth_transaction.s = to_u256(transaction.signature.s());
+            }
+            TransactionV3::EIP1559(transaction) => {
+                eth_transaction.nonce = transaction.nonce;
+                eth_transaction.to = to_address(transaction.action);
+                eth_transaction.value = transaction.value;
+                eth_transaction.gas = transaction.gas_limit;
+                eth_transaction.max_fee_per_gas = Some(transaction.max_fee_per_gas);
+                eth_transaction.max_priority_fee_per_gas =
+                    Some(transaction.max_priority_fee_per_gas);
+                eth_transaction.gas_price = block.base_fee_per_gas.map(|base_fee| {
+                    dynamic_gas_price(
+                        base_fee,
+                        transaction.max_fee_per_gas,
+                        transaction.max_priority_fee_per_gas,
+                    )
+                });
+                eth_transaction.input = transaction.input.clone();
+                eth_transaction.chain_id = Some(U64::from(transaction.chain_id));
+                eth_transaction.access_list =
+                    Some(transaction.access_list.iter().map(Into::into).collect());
+                let y_parity = U64::from(u64::from(transaction.signature.odd_y_parity()));
+                eth_transaction.v = y_parity;
+                eth_transaction.y_parity = Some(y_parity);
+                eth_transaction.r = to_u256(transaction.signature.r());
+                eth_transaction.s = to_u256(transaction.signature.s());
+            }
+            TransactionV3::EIP7702(transaction) => {
+                eth_transaction.nonce = transaction.nonce;
+                eth_transaction.to = to_address(transaction.destination);
+                eth_transaction.value = transaction.value;
+                eth_transaction.gas = transaction.gas_limit;
+                eth_transaction.max_fee_per_gas = Some(transaction.max_fee_per_gas);
+                eth_transaction.max_priority_fee_per_gas =
+                    Some(transaction.max_priority_fee_per_gas);
+                eth_transaction.gas_price = block.base_fee_per_gas.map(|base_fee| {
+                    dynamic_gas_price(
+                        base_fee,
+                        transaction.max_fee_per_gas,
+                        transaction.max_priority_fee_per_gas,
+                    )
+                });
+                eth_transaction.input = transaction.data.clone();
+                eth_transaction.chain_id = Some(U64::from(transaction.chain_id));
+                eth_transaction.access_list =
+                    Some(transaction.access_list.iter().map(Into::into).collect());
+                eth_transaction.authorization_list = Some(
+                    transaction
+                        .authorization_list
+                        .iter()
+                        .map(Into::into)
+                        .collect(),
+                );
+                let y_parity = U64::from(u64::from(transaction.signature.odd_y_parity()));
+                eth_transaction.v = y_parity;
+                eth_transaction.y_parity = Some(y_parity);
+                eth_transaction.r = to_u256(transaction.signature.r());
+                eth_transaction.s = to_u256(transaction.signature.s());
+            }
+        }
+
+        eth_transaction
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthLog {
+    pub address: H160,
+    pub topics: Vec<H256>,
+    #[serde(serialize_with = "serialize_hex")]
+    pub data: Vec<u8>,
+    pub block_hash: H256,
+    pub block_number: U64,
+    pub transaction_hash: H256,
+    pub transaction_index: U64,
+    pub log_index: U64,
+    pub removed: bool,
+}
+
+impl EthLog {
+    fn new(
+        block: &EthereumBlock,
+        transaction_index: usize,
+        transaction: &BlockTransaction,
+        log_index: usize,
+        log: &Log,
+    ) -> Self {
+        Self {
+            address: log.address,
+            topics: log.topics.clone(),
+            data: log.data.clone(),
+            block_hash: block.hash,
+            block_number: block.number(),
+            transaction_hash: transaction.transaction.hash(),
+            transaction_index: U64::from(transaction_index),
+            log_index: U64::from(log_index),
+            removed: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthReceipt {
+    pub transaction_hash: H256,
+    pub transaction_index: U64,
+    pub block_hash: H256,
+    pub block_number: U64,
+    pub from: Option<H160>,
+    pub to: Option<H160>,
+    pub cumulative_gas_used: U256,
+    pub gas_used: Option<U256>,
+    pub contract_address: Option<H160>,
+    pub logs: Vec<EthLog>,
+    pub logs_bloom: Bloom,
+    #[serde(rename = "type")]
+    pub transaction_type: U64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<U64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root: Option<H256>,
+    /// Dynamic fees are only known when the base fee of the block is
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effective_gas_price: Option<U256>,
+}
+
+/// Gas price paid by a dynamic fee transaction, the base fee plus its tip capped by its max fee
+fn dynamic_gas_price(
+    base_fee_per_gas: U256,
+    max_fee_per_gas: U256,
+    max_priority_fee_per_gas: U256,
+) -> U256 {
+    max_fee_per_gas.min(base_fee_per_gas.saturating_add(max_priority_fee_per_gas))
+}
+
+impl EthReceipt {
+    /// `None` if the block has no receipt for the transaction
+    pub fn new(block: &EthereumBlock, index: usize) -> Option<Self> {
+        let block_transaction = &block.transactions[index];
+        let transaction = &block_transaction.transaction;
+        let hash = transaction.hash();
+
+        let (cumulative_gas_used, logs_bloom, status, root) =
+            match block_transaction.receipt.as_ref()? {
+                ReceiptAny::Frontier(receipt) => (
+                    receipt.used_gas,
+                    receipt.logs_bloom,
+                    None,
+                    Some(receipt.state_root),
+                ),
+                ReceiptAny::EIP658(receipt)
+                | ReceiptAny::EIP2930(receipt)
+                | ReceiptAny::EIP1559(receipt)
+                | ReceiptAny::EIP7702(receipt) => (
+                    receipt.used_gas,
+                    receipt.logs_bloom,
+                    Some(U64::from(receipt.status_code)),
+                    None,
+                ),
+            };
+
+        let (to, effective_gas_price) = match transaction {
+            TransactionV3::Legacy(transaction) => {
+                (to_address(transaction.action), Some(transaction.gas_price))
+            }
+            TransactionV3::EIP2930(transaction) => {
+                (to_address(transaction.action), Some(transaction.gas_price))
+            }
+            TransactionV3::EIP1559(transaction) => (
+                to_address(transaction.action),
+                block.base_fee_per_gas.map(|base_fee| {
+                    dynamic_gas_price(
+                        base_fee,
+                        transaction.max_fee_per_gas,
+                        transaction.max_priority_fee_per_gas,
+                    )
+                }),
+            ),
+            TransactionV3::EIP7702(transaction) => (
+                to_address(transaction.destination),
+                block.base_fee_per_gas.map(|base_fee| {
+                    dynamic_gas_price(
+                        base_fee,
+                        transaction.max_fee_per_gas,
+                        transaction.max_priority_fee_per_gas,
+                    )
+                }),
+            ),
+        };
+
+        Some(Self {
+            transaction_hash: hash,
+            transaction_index: U64::from(index),
+            block_hash: block.hash,
+            block_number: block.number(),
+            from: sender(block_transaction),
+            to,
+            cumulative_gas_used,
+            gas_used: block_transaction.gas_used,
+            contract_address: block_transaction
+                .status
+                .as_ref()
+                .and_then(|status| status.contract_address),
+            logs: block
+                .logs()
+                .filter(|log| log.transaction_index == U64::from(index))
+                .collect(),
+            logs_bloom,
+            transaction_type: transaction_type_number(transaction),
+            status,
+            root,
+            effective_gas_price,
+        })
+    }
+}
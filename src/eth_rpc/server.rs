@@ -0,0 +1,404 @@
+use std::{collections::HashMap, net::SocketAddr, num::NonZeroUsize, sync::Arc};
+
+use ethereum_types::{H256, U256};
+use frame_metadata::RuntimeMetadata;
+use jsonrpsee::{
+    RpcModule,
+    server::Server,
+    types::{ErrorObjectOwned, Params},
+};
+use lru::LruCache;
+use parking_lot::Mutex;
+use sqlx::AnyPool;
+use tracing::{debug, info, warn};
+
+use crate::{
+    decoder::metadata::AnyRuntimeMetadata,
+    error::Error,
+    eth_rpc::{
+        error::EthRpcError,
+        models::{
+            BlockTag, EthBlock, EthLog, EthReceipt, EthTransaction, EthereumBlock, LogFilter,
+        },
+    },
+    fetch::fetch_metadata,
+    indexer,
+    node_rpc::{
+        client::NodeRPC,
+        models::{BlockHashHex, BlockNumberHex},
+    },
+    pallets::{self, ethereum::transaction::match_transactions},
+};
+
+/// Maximum number of blocks scanned by a single `eth_getLogs` request
+const MAX_LOG_BLOCK_RANGE: u32 = 1024;
+
+/// Number of transaction hashes remembered with the block number of the block they were served in
+const TRANSACTION_CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(65536).unwrap();
+
+/// Serves Ethereum blocks decoded from the Substrate storage of the `Ethereum` pallet
+///
+/// Frontier numbers Ethereum blocks with the Substrate block number. The transaction hash to block
+/// mapping only lives in the Frontier node's database, so transactions are found among the recently
+/// served blocks, in the `ethereum_transactions` table of the indexer database when one is given,
+/// or by scanning back `lookback` blocks from the best block.
+pub struct EthRpcContext {
+    rpc: NodeRPC,
+    lookback: u32,
+    indexer_pool: Option<AnyPool>,
+    metadata_by_spec_version: Mutex<HashMap<u64, Arc<RuntimeMetadata>>>,
+    block_number_by_transaction: Mutex<LruCache<H256, u32>>,
+}
+
+impl EthRpcContext {
+    pub fn new(rpc: NodeRPC, lookback: u32, indexer_pool: Option<AnyPool>) -> Self {
+        Self {
+            rpc,
+            lookback,
+            indexer_pool,
+            metadata_by_spec_version: Mutex::new(HashMap::new()),
+            block_number_by_transaction: Mutex::new(LruCache::new(TRANSACTION_CACHE_SIZE)),
+        }
+    }
+
+    async fn best_block_number(&self) -> Result<u32, Error> {
+        let header = self.rpc.chain_get_best_header().await?;
+        u32::try_from(&header.number)
+            .map_err(|err| Error::ParsingBlockNumberFailed(header.number.clone(), err))
+    }
+
+    async fn finalized_block_number(&self) -> Result<u32, Error> {
+        let finalized_hash = self.rpc.chain_get_finalized_head().await?;
+        let header = self.rpc.chain_get_header(finalized_hash).await?;
+        u32::try_from(&header.number)
+            .map_err(|err| Error::ParsingBlockNumberFailed(header.number.clone(), err))
+    }
+
+    /// `None` for block numbers above the best block
+    async fn resolve_block_tag(&self, tag: BlockTag) -> Result<Option<u32>, Error> {
+        let best_block_number = self.best_block_number().await?;
+        Ok(match tag {
+            BlockTag::Number(block_number) if block_number > best_block_number => None,
+            BlockTag::Number(block_number) => Some(block_number),
+            BlockTag::Earliest => Some(0),
+            BlockTag::Latest | BlockTag::Pending => Some(best_block_number),
+            BlockTag::Safe | BlockTag::Finalized => Some(self.finalized_block_number().await?),
+        })
+    }
+
+    async fn metadata(
+        &self,
+        spec_version: u64,
+        block_hash: &BlockHashHex,
+    ) -> Result<Arc<RuntimeMetadata>, Error> {
+        if let Some(metadata) = self.metadata_by_spec_version.lock().get(&spec_version) {
+            return Ok(metadata.clone());
+        }
+
+        let metadata = Arc::new(fetch_metadata(&self.rpc, block_hash).await?);
+        self.metadata_by_spec_version
+            .lock()
+            .insert(spec_version, metadata.clone());
+        Ok(metadata)
+    }
+
+    /// Base fee charged to the transactions of a block, set in the state of its parent block
+    async fn fetch_base_fee_per_gas(&self, block_hash: &BlockHashHex) -> Result<U256, Error> {
+        let header = self.rpc.chain_get_header(block_hash.clone()).await?;
+        let parent_hash = header.parent_hash;
+        let runtime_version = self.rpc.state_get_runtime_version(&parent_hash).await?;
+        let metadata = self
+            .metadata(runtime_version.spec_version, &parent_hash)
+            .await?;
+
+        pallets::base_fee::fetch_base_fee_per_gas(
+            &self.rpc,
+            &parent_hash,
+            AnyRuntimeMetadata(&metadata),
+            &runtime_version,
+        )
+        .await
+    }
+
+    /// Block number of a transaction in the indexer database
+    async fn indexed_block_number(&self, transaction_hash: &H256) -> Option<u32> {
+        let pool = self.indexer_pool.as_ref()?;
+        let block_number: Result<Option<i64>, sqlx::Error> =
+            sqlx::query_scalar("SELECT block_number FROM ethereum_transactions WHERE hash = $1")
+                .bind(format!("{transaction_hash:?}"))
+                .fetch_optional(pool)
+                .await;
+
+        match block_number {
+            Ok(block_number) => block_number.map(|block_number| block_number as u32),
+            Err(error) => {
+                warn!(%error, "transaction lookup in the indexer database failed");
+                None
+            }
+        }
+    }
+
+    /// `None` if the runtime of the block has no `Ethereum` pallet
+    async fn fetch_block(&self, block_number: u32) -> Result<Option<EthereumBlock>, Error> {
+        let block_hash = self
+            .rpc
+            .chain_get_block_hash(&BlockNumberHex::from(block_number))
+            .await?;
+        let runtime_version = self.rpc.state_get_runtime_version(&block_hash).await?;
+        let metadata = self
+            .metadata(runtime_version.spec_version, &block_hash)
+            .await?;
+        let metadata = AnyRuntimeMetadata(&metadata);
+
+        if !metadata
+            .pallet_names()?
+            .contains(pallets::ethereum::fetch::PALLET_NAME)
+        {
+            return Ok(None);
+        }
+
+        // Transactions of the genesis block paid no base fee
+        let base_fee_per_gas = if block_number > 0
+            && metadata
+                .pallet_names()?
+                .contains(pallets::base_fee::fetch::PALLET_NAME)
+        {
+            match self.fetch_base_fee_per_gas(&block_hash).await {
+                Ok(base_fee_per_gas) => Some(base_fee_per_gas),
+                Err(error) => {
+                    warn!(%error, block_number, "base fee unavailable");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let block =
+            pallets::ethereum::fetch_block(&self.rpc, &block_hash, metadata, &runtime_version)
+                .await?;
+        let receipts =
+            pallets::ethereum::fetch_receipts(&self.rpc, &block_hash, metadata, &runtime_version)
+                .await?;
+        let statuses = pallets::ethereum::fetch_transaction_statuses(
+            &self.rpc,
+            &block_hash,
+            metadata,
+            &runtime_version,
+        )
+        .await?;
+
+        let block = EthereumBlock {
+            hash: block.header.hash(),
+            header: block.header,
+            transactions: match_transactions(block.transactions, receipts, statuses),
+            base_fee_per_gas,
+        };
+
+        let mut block_number_by_transaction = self.block_number_by_transaction.lock();
+        for transaction in &block.transactions {
+            block_number_by_transaction.put(transaction.transaction.hash(), block_number);
+        }
+
+        Ok(Some(block))
+    }
+
+    /// Find the block of a transaction and its index in the block
+    async fn find_transaction(
+        &self,
+        transaction_hash: &H256,
+    ) -> Result<Option<(EthereumBlock, usize)>, Error> {
+        let cached_block_number = self
+            .block_number_by_transaction
+            .lock()
+            .get(transaction_hash)
+            .copied();
+        let known_block_number = match cached_block_number {
+            Some(block_number) => Some(block_number),
+            None => self.indexed_block_number(transaction_hash).await,
+        };
+
+        if let Some(block_number) = known_block_number
+            && let Some(block) = self.fetch_block(block_number).await?
+            && let Some(index) = block.position(transaction_hash)
+        {
+            return Ok(Some((block, index)));
+        }
+
+        let best_block_number = self.best_block_number().await?;
+        let oldest_block_number = best_block_number.saturating_sub(self.lookback);
+        debug!(
+            ?transaction_hash,
+            best_block_number, oldest_block_number, "scanning for transaction"
+        );
+
+        for block_number in (oldest_block_number..=best_block_number).rev() {
+            if let Some(block) = self.fetch_block(block_number).await?
+                && let Some(index) = block.position(transaction_hash)
+            {
+                return Ok(Some((block, index)));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+async fn get_block_by_number(
+    params: Params<'static>,
+    context: Arc<EthRpcContext>,
+) -> Result<Option<EthBlock>, ErrorObjectOwned> {
+    let mut params = params.sequence();
+    let tag: BlockTag = params.next()?;
+    let full: bool = params.optional_next()?.unwrap_or_default();
+
+    let Some(block_number) = context
+        .resolve_block_tag(tag)
+        .await
+        .map_err(EthRpcError::from)?
+    else {
+        return Ok(None);
+    };
+
+    let block = context
+        .fetch_block(block_number)
+        .await
+        .map_err(EthRpcError::from)?;
+    Ok(block.map(|block| EthBlock::new(&block, full)))
+}
+
+async fn get_transaction_by_hash(
+    params: Params<'static>,
+    context: Arc<EthRpcContext>,
+) -> Result<Option<EthTransaction>, ErrorObjectOwned> {
+    let (transaction_hash,): (H256,) = params.parse()?;
+
+    let transaction = context
+        .find_transaction(&transaction_hash)
+        .await
+        .map_err(EthRpcError::from)?;
+    Ok(transaction.map(|(block, index)| EthTransaction::new(&block, index)))
+}
+
+async fn get_transaction_receipt(
+    params: Params<'static>,
+    context: Arc<EthRpcContext>,
+) -> Result<Option<EthReceipt>, ErrorObjectOwned> {
+    let (transaction_hash,): (H256,) = params.parse()?;
+
+    let transaction = context
+        .find_transaction(&transaction_hash)
+        .await
+        .map_err(EthRpcError::from)?;
+    Ok(transaction.and_then(|(block, index)| EthReceipt::new(&block, index)))
+}
+
+async fn get_logs(
+    params: Params<'static>,
+    context: Arc<EthRpcContext>,
+) -> Result<Vec<EthLog>, ErrorObjectOwned> {
+    let (filter,): (LogFilter,) = params.parse()?;
+
+    if filter.block_hash.is_some() {
+        Err(EthRpcError::BlockHashFilterUnsupported)?;
+    }
+
+    let best_block_number = context
+        .best_block_number()
+        .await
+        .map_err(EthRpcError::from)?;
+    let from_block = match context
+        .resolve_block_tag(filter.from_block.unwrap_or(BlockTag::Latest))
+        .await
+        .map_err(EthRpcError::from)?
+    {
+        Some(from_block) => from_block,
+        None => return Ok(Vec::new()),
+    };
+    let to_block = context
+        .resolve_block_tag(filter.to_block.unwrap_or(BlockTag::Latest))
+        .await
+        .map_err(EthRpcError::from)?
+        .unwrap_or(best_block_number);
+
+    if from_block > to_block {
+        Err(EthRpcError::InvalidBlockRange {
+            from_block,
+            to_block,
+        })?;
+    }
+    let range = to_block - from_block + 1;
+    if range > MAX_LOG_BLOCK_RANGE {
+        Err(EthRpcError::BlockRangeTooLarge {
+            range,
+            limit: MAX_LOG_BLOCK_RANGE,
+        })?;
+    }
+
+    let mut logs = Vec::new();
+    for block_number in from_block..=to_block {
+        let Some(block) = context
+            .fetch_block(block_number)
+            .await
+            .map_err(EthRpcError::from)?
+        else {
+            continue;
+        };
+
+        logs.extend(block.logs().filter(|log| filter.matches(log)));
+    }
+
+    Ok(logs)
+}
+
+fn rpc_module(context: EthRpcContext) -> RpcModule<EthRpcContext> {
+    let mut module = RpcModule::new(context);
+
+    module
+        .register_async_method("eth_getBlockByNumber", |params, context, _| {
+            get_block_by_number(params, context)
+        })
+        .expect("eth_getBlockByNumber is registered once");
+    module
+        .register_async_method("eth_getTransactionByHash", |params, context, _| {
+            get_transaction_by_hash(params, context)
+        })
+        .expect("eth_getTransactionByHash is registered once");
+    module
+        .register_async_method("eth_getTransactionReceipt", |params, context, _| {
+            get_transaction_receipt(params, context)
+        })
+        .expect("eth_getTransactionReceipt is registered once");
+    module
+        .register_async_method("eth_getLogs", |params, context, _| {
+            get_logs(params, context)
+        })
+        .expect("eth_getLogs is registered once");
+
+    module
+}
+
+/// Serve the Ethereum JSON-RPC read API until the server stops
+pub async fn serve(
+    rpc: NodeRPC,
+    listen_address: SocketAddr,
+    lookback: u32,
+    database_url: Option<&str>,
+) -> Result<(), Error> {
+    let indexer_pool = match database_url {
+        Some(database_url) => Some(indexer::connect_pool(database_url).await?),
+        None => None,
+    };
+
+    let server = Server::builder()
+        .build(listen_address)
+        .await
+        .map_err(Error::EthRpcServerFailed)?;
+    let local_address = server.local_addr().map_err(Error::EthRpcServerFailed)?;
+
+    let handle = server.start(rpc_module(EthRpcContext::new(rpc, lookback, indexer_pool)));
+    info!("Ethereum JSON-RPC server listening on {local_address}");
+
+    handle.stopped().await;
+    Ok(())
+}
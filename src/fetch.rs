@@ -1,10 +1,19 @@
+use derive_more::Display;
 use frame_decode::storage::IntoStorageKeys;
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use parity_scale_codec::Decode;
+use scale_decode::IntoVisitor;
 use scale_info_legacy::LookupName;
+use ss58::Ss58AddressFormat;
 
 use crate::{
     decoder::{
+        constant::{AnyConstantValue, decode_constant_any},
         metadata::AnyRuntimeMetadata,
-        storage::{AnyStorageValue, decode_storage_value_any, encode_storage_key_any},
+        storage::{
+            AnyStorageValue, decode_storage_value_any, decode_storage_value_as,
+            encode_storage_key_any,
+        },
         value_decoder::{ValueDecoder, WithErrorSpan},
     },
     error::Error,
@@ -14,6 +23,49 @@ use crate::{
     },
 };
 
+/// Finality of a block on the canonical chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum BlockStatus {
+    Finalized,
+    Best,
+}
+
+/// Compare the block number against the last finalized block
+pub async fn fetch_block_status(rpc: &NodeRPC, block_number: u32) -> Result<BlockStatus, Error> {
+    let finalized_hash = rpc.chain_get_finalized_head().await?;
+    let finalized_header = rpc.chain_get_header(finalized_hash).await?;
+    let finalized_number = u32::try_from(&finalized_header.number)
+        .map_err(|err| Error::ParsingBlockNumberFailed(finalized_header.number.clone(), err))?;
+
+    Ok(if block_number <= finalized_number {
+        BlockStatus::Finalized
+    } else {
+        BlockStatus::Best
+    })
+}
+
+/// Fetch and decode the runtime metadata of a block, built from the verified runtime code when the
+/// node is untrusted
+pub async fn fetch_metadata(
+    rpc: &NodeRPC,
+    block_hash: &BlockHashHex,
+) -> Result<RuntimeMetadata, Error> {
+    let metadata_bytes = match rpc.storage_verifier() {
+        Some(storage_verifier) => storage_verifier
+            .runtime(rpc, block_hash)
+            .await?
+            .metadata_bytes
+            .clone(),
+        None => rpc.state_get_metadata(block_hash).await?.0,
+    };
+
+    let RuntimeMetadataPrefixed(_, metadata) =
+        RuntimeMetadataPrefixed::decode(&mut &metadata_bytes[..])
+            .map_err(Error::ParsingRuntimeMetadataFailed)?;
+
+    Ok(metadata)
+}
+
 pub async fn fetch<T, K>(
     pallet_name: &str,
     storage_entry_name: &str,
@@ -27,9 +79,133 @@ where
     T: ValueDecoder<LookupName> + ValueDecoder<u32>,
     K: IntoStorageKeys + std::fmt::Debug,
 {
-    let storage_keys_str = format!("{storage_keys:?}");
     let full_name_str =
-        format!("pallet stoge: {pallet_name}:{storage_entry_name}:{storage_keys_str}");
+        format!("pallet stoge: {pallet_name}:{storage_entry_name}:{storage_keys:?}");
+    let storage_bytes = fetch_storage_bytes(
+        pallet_name,
+        storage_entry_name,
+        storage_keys,
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await?;
+
+    let value = decode_storage_value_any(
+        storage_bytes,
+        pallet_name,
+        storage_entry_name,
+        metadata,
+        runtime_version.spec_version,
+    )?;
+
+    Ok(match value {
+        AnyStorageValue::Legacy(value) => {
+            ValueDecoder::decode(*value).add_error_span(&full_name_str)?
+        }
+        AnyStorageValue::Modern(value) => {
+            ValueDecoder::decode(value).add_error_span(&full_name_str)?
+        }
+    })
+}
+
+/// Fetch a storage value, `None` if the storage entry has no value for the keys
+/// Used for `ValueQuery` storage entries, where a missing value is the default value
+pub async fn fetch_optional<T, K>(
+    pallet_name: &str,
+    storage_entry_name: &str,
+    storage_keys: K,
+    rpc: &NodeRPC,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<Option<T>, Error>
+where
+    T: ValueDecoder<LookupName> + ValueDecoder<u32>,
+    K: IntoStorageKeys + std::fmt::Debug,
+{
+    match fetch(
+        pallet_name,
+        storage_entry_name,
+        storage_keys,
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await
+    {
+        Ok(value) => Ok(Some(value)),
+        Err(Error::StorageValueNotFound { .. }) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Fetch a storage value and decode it directly from the SCALE bytes with `scale-decode`
+/// Useful for types deriving `DecodeAsType`, without writing a `ValueDecoder` impl
+pub async fn fetch_as<T, K>(
+    pallet_name: &str,
+    storage_entry_name: &str,
+    storage_keys: K,
+    rpc: &NodeRPC,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<T, Error>
+where
+    T: IntoVisitor,
+    K: IntoStorageKeys + std::fmt::Debug,
+{
+    let storage_bytes = fetch_storage_bytes(
+        pallet_name,
+        storage_entry_name,
+        storage_keys,
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await?;
+
+    Ok(decode_storage_value_as(
+        storage_bytes,
+        pallet_name,
+        storage_entry_name,
+        metadata,
+        runtime_version.spec_version,
+    )?)
+}
+
+/// Read a raw storage value, from a verified read proof when the node is untrusted
+pub async fn fetch_storage_value(
+    rpc: &NodeRPC,
+    storage_key: &StorageKeyHex,
+    block_hash: &BlockHashHex,
+) -> Result<Option<StorageValueBytes>, Error> {
+    match rpc.storage_verifier() {
+        Some(storage_verifier) => {
+            storage_verifier
+                .read_storage(rpc, storage_key, block_hash)
+                .await
+        }
+        None => Ok(rpc.state_get_storage(storage_key, block_hash).await?),
+    }
+}
+
+async fn fetch_storage_bytes<K>(
+    pallet_name: &str,
+    storage_entry_name: &str,
+    storage_keys: K,
+    rpc: &NodeRPC,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<Vec<u8>, Error>
+where
+    K: IntoStorageKeys + std::fmt::Debug,
+{
+    let storage_keys_str = format!("{storage_keys:?}");
     let key = encode_storage_key_any(
         pallet_name,
         storage_entry_name,
@@ -40,8 +216,7 @@ where
 
     let key_hex = StorageKeyHex::from(key.0);
 
-    let StorageValueBytes(storage_bytes) = rpc
-        .state_get_storage(&key_hex, block_hash)
+    let StorageValueBytes(storage_bytes) = fetch_storage_value(rpc, &key_hex, block_hash)
         .await?
         .ok_or(Error::StorageValueNotFound {
             pallet_name: pallet_name.to_string(),
@@ -50,20 +225,45 @@ where
             storage_entry_key_hash: key_hex,
         })?;
 
-    let value = decode_storage_value_any(
-        storage_bytes,
+    Ok(storage_bytes)
+}
+
+/// Decode a pallet constant from the metadata
+pub fn constant<T>(
+    pallet_name: &str,
+    constant_name: &str,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<T, Error>
+where
+    T: ValueDecoder<LookupName> + ValueDecoder<u32>,
+{
+    let full_name_str = format!("pallet constant: {pallet_name}:{constant_name}");
+
+    let value = decode_constant_any(
         pallet_name,
-        storage_entry_name,
+        constant_name,
         metadata,
         runtime_version.spec_version,
     )?;
 
     Ok(match value {
-        AnyStorageValue::Legacy(value) => {
+        AnyConstantValue::Legacy(value) => {
             ValueDecoder::decode(*value).add_error_span(&full_name_str)?
         }
-        AnyStorageValue::Modern(value) => {
+        AnyConstantValue::Modern(value) => {
             ValueDecoder::decode(value).add_error_span(&full_name_str)?
         }
     })
 }
+
+/// SS58 address format of the chain, from the `system_properties` of the node
+pub async fn ss58_address_format(rpc: &NodeRPC) -> Result<Ss58AddressFormat, Error> {
+    Ok(match rpc.system_properties().await?.ss58_format {
+        0 => Ss58AddressFormat::Polkadot,
+        2 => Ss58AddressFormat::Kusama,
+        42 => Ss58AddressFormat::Substrate,
+        137 => Ss58AddressFormat::Vara,
+        other => Ss58AddressFormat::Custom(other),
+    })
+}
@@ -0,0 +1,391 @@
+use ethereum::{TransactionAction, TransactionV3};
+use ethereum_types::H160;
+use parity_scale_codec::Decode;
+use scale_value::{Value, ValueDef, Variant};
+use sp_runtime::{
+    DigestItem,
+    traits::{BlakeTwo256, Hash},
+};
+use ss58::Ss58AddressFormat;
+use tracing::{debug, warn};
+
+use crate::{
+    author::find_block_author,
+    decoder::{
+        extrinsic::decode_extrinsic_any,
+        metadata::AnyRuntimeMetadata,
+        value_decoder::{composite_to_record, format_value, get_field},
+    },
+    error::Error,
+    fetch,
+    node_rpc::{
+        client::NodeRPC,
+        models::{BlockHashHex, BlockHeader, ExtrinsicBytes, LogBytes, RuntimeVersion},
+    },
+    pallets::{
+        self,
+        ethereum::{
+            address::checksum_address,
+            transaction::{BlockTransaction, match_transactions, recover_sender},
+        },
+        session::decoder::decode_public_key,
+        system::decoder::{Event, EventRecord, Phase},
+    },
+    verify,
+};
+
+#[derive(Debug, Clone)]
+pub struct BlockRow {
+    pub number: u32,
+    pub hash: BlockHashHex,
+    pub parent_hash: BlockHashHex,
+    pub state_root: String,
+    pub extrinsics_root: String,
+    pub spec_version: u64,
+    /// RFC 3339 timestamp
+    pub timestamp: String,
+    pub author: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExtrinsicRow {
+    /// `{block}-{index}`, as in the extrinsics table
+    pub id: String,
+    pub index: u32,
+    pub hash: String,
+    pub pallet: String,
+    pub call: String,
+    pub signer: Option<String>,
+    /// `None` if neither `System.ExtrinsicSuccess` nor `System.ExtrinsicFailed` was emitted
+    pub success: Option<bool>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EventRow {
+    /// `{block}-{index}`, as in the events table
+    pub id: String,
+    pub index: u32,
+    pub extrinsic_id: Option<String>,
+    pub pallet: String,
+    pub name: String,
+    pub params: String,
+}
+
+/// A `Balances.Transfer` event
+#[derive(Debug, Clone)]
+pub struct TransferRow {
+    pub event_id: String,
+    pub extrinsic_id: Option<String>,
+    pub sender: String,
+    pub receiver: String,
+    /// Decimal string, `u128` amounts don't fit the integer columns
+    pub amount: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct EthereumTransactionRow {
+    pub hash: String,
+    pub index: u32,
+    pub sender: Option<String>,
+    /// Called address or the address of the created contract
+    pub receiver: Option<String>,
+    pub value: String,
+    pub success: Option<bool>,
+}
+
+/// A block decoded into the rows of the indexer tables
+#[derive(Debug, Clone)]
+pub struct IndexedBlock {
+    /// Whether the block was finalized when it was decoded, best blocks may still be retracted
+    pub finalized: bool,
+    pub block: BlockRow,
+    pub extrinsics: Vec<ExtrinsicRow>,
+    pub events: Vec<EventRow>,
+    pub transfers: Vec<TransferRow>,
+    pub ethereum_transactions: Vec<EthereumTransactionRow>,
+}
+
+/// Fetch and decode a block
+///
+/// Only failing to fetch the block, its runtime, timestamp or events fails the block. The author is
+/// stored as NULL and undecodable extrinsics or Ethereum transactions are left out, with a warning.
+pub async fn decode_block(
+    rpc: &NodeRPC,
+    block_number: u32,
+    block_hash: BlockHashHex,
+    finalized: bool,
+    address_format: Ss58AddressFormat,
+) -> Result<IndexedBlock, Error> {
+    let metadata = fetch::fetch_metadata(rpc, &block_hash).await?;
+    let metadata = AnyRuntimeMetadata(&metadata);
+    let pallets = metadata.pallet_names()?;
+
+    let signed_block = rpc.chain_get_block(&block_hash).await?;
+    let runtime_version = rpc.state_get_runtime_version(&block_hash).await?;
+    if rpc.storage_verifier().is_some() {
+        verify::verify_block(&signed_block.block, &block_hash, &runtime_version)?;
+    }
+    let header = &signed_block.block.header;
+
+    let timestamp =
+        pallets::timestamp::fetch_timestamp(rpc, &block_hash, metadata, &runtime_version).await?;
+
+    let author = match find_author(rpc, header, &block_hash, metadata, &runtime_version).await {
+        Ok(author) => author.map(|account_id| ss58::encode(&account_id, address_format)),
+        Err(error) => {
+            warn!(%error, block_number, "block author unavailable");
+            None
+        }
+    };
+
+    let event_records =
+        pallets::system::fetch_events(rpc, &block_hash, metadata, &runtime_version).await?;
+
+    let extrinsics = signed_block
+        .block
+        .extrinsics
+        .iter()
+        .enumerate()
+        .filter_map(|(i, ExtrinsicBytes(ext))| {
+            let extrinsic = match decode_extrinsic_any(ext, metadata, runtime_version.spec_version)
+            {
+                Ok(extrinsic) => extrinsic,
+                Err(error) => {
+                    warn!(%error, extrinsic_id = format!("{block_number}-{i}"), "extrinsic skipped");
+                    return None;
+                }
+            };
+            let signer = match extrinsic.signer(ext, metadata) {
+                Ok(signer) => signer.map(|address| format_address(address, address_format)),
+                Err(error) => {
+                    debug!(%error);
+                    None
+                }
+            };
+
+            Some(ExtrinsicRow {
+                id: format!("{block_number}-{i}"),
+                index: i as u32,
+                hash: format!("0x{}", hex::encode(BlakeTwo256::hash(ext))),
+                pallet: extrinsic.pallet_name().to_string(),
+                call: extrinsic.call_name().to_string(),
+                signer,
+                success: extrinsic_success(&event_records, i as u32),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut events = Vec::new();
+    let mut transfers = Vec::new();
+    for (i, record) in event_records.iter().enumerate() {
+        let event_id = format!("{block_number}-{i}");
+        let extrinsic_id = match record.phase {
+            Phase::ApplyExtrinsic(ext_idx) => Some(format!("{block_number}-{ext_idx}")),
+            Phase::Initialization | Phase::Finalization => None,
+        };
+
+        if record.event.name == "Balances" && record.event.action == "Transfer" {
+            match decode_transfer(&record.event, address_format) {
+                Ok((sender, receiver, amount)) => transfers.push(TransferRow {
+                    event_id: event_id.clone(),
+                    extrinsic_id: extrinsic_id.clone(),
+                    sender,
+                    receiver,
+                    amount,
+                }),
+                Err(error) => warn!(%error, event_id),
+            }
+        }
+
+        events.push(EventRow {
+            id: event_id,
+            index: i as u32,
+            extrinsic_id,
+            pallet: record.event.name.clone(),
+            name: record.event.action.clone(),
+            params: record.event.params.clone(),
+        });
+    }
+
+    let ethereum_transactions = if pallets.contains("Ethereum") {
+        decode_ethereum_transactions(rpc, block_number, &block_hash, metadata, &runtime_version)
+            .await
+    } else {
+        Vec::new()
+    };
+
+    Ok(IndexedBlock {
+        finalized,
+        block: BlockRow {
+            number: block_number,
+            hash: block_hash,
+            parent_hash: header.parent_hash.clone(),
+            state_root: header.state_root.clone(),
+            extrinsics_root: header.extrinsics_root.clone(),
+            spec_version: runtime_version.spec_version,
+            timestamp: timestamp.to_rfc3339(),
+            author,
+        },
+        extrinsics,
+        events,
+        transfers,
+        ethereum_transactions,
+    })
+}
+
+/// Account id of the block author, `None` if the consensus digests don't identify it
+async fn find_author(
+    rpc: &NodeRPC,
+    header: &BlockHeader,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<Option<[u8; 32]>, Error> {
+    let digest_logs = header
+        .digest
+        .logs
+        .iter()
+        .map(|LogBytes(bytes)| {
+            DigestItem::decode(&mut &bytes[..]).map_err(Error::ParsingDigestLogsFailed)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let block_author = find_block_author(
+        rpc,
+        &digest_logs,
+        block_hash,
+        &header.parent_hash,
+        metadata,
+        runtime_version,
+    )
+    .await?;
+    Ok(block_author.map(|block_author| block_author.account_id))
+}
+
+/// Transactions of the Ethereum block, without a status when the receipts or statuses can't be
+/// fetched and empty when the block itself can't be
+async fn decode_ethereum_transactions(
+    rpc: &NodeRPC,
+    block_number: u32,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Vec<EthereumTransactionRow> {
+    let ethereum_block =
+        match pallets::ethereum::fetch_block(rpc, block_hash, metadata, runtime_version).await {
+            Ok(ethereum_block) => ethereum_block,
+            Err(error) => {
+                warn!(%error, block_number, "Ethereum block unavailable");
+                return Vec::new();
+            }
+        };
+    let receipts = pallets::ethereum::fetch_receipts(rpc, block_hash, metadata, runtime_version)
+        .await
+        .unwrap_or_else(|error| {
+            warn!(%error, block_number, "Ethereum receipts unavailable");
+            Vec::new()
+        });
+    let transaction_statuses =
+        pallets::ethereum::fetch_transaction_statuses(rpc, block_hash, metadata, runtime_version)
+            .await
+            .unwrap_or_else(|error| {
+                warn!(%error, block_number, "Ethereum transaction statuses unavailable");
+                Vec::new()
+            });
+
+    match_transactions(ethereum_block.transactions, receipts, transaction_statuses)
+        .iter()
+        .enumerate()
+        .map(|(i, block_transaction)| ethereum_transaction_row(i as u32, block_transaction))
+        .collect()
+}
+
+/// Outcome of an extrinsic from its `System.ExtrinsicSuccess` or `System.ExtrinsicFailed` event
+fn extrinsic_success(event_records: &[EventRecord], extrinsic_index: u32) -> Option<bool> {
+    event_records
+        .iter()
+        .filter(|record| {
+            matches!(record.phase, Phase::ApplyExtrinsic(ext_idx) if ext_idx == extrinsic_index)
+                && record.event.name == "System"
+        })
+        .find_map(|record| match record.event.action.as_str() {
+            "ExtrinsicSuccess" => Some(true),
+            "ExtrinsicFailed" => Some(false),
+            _ => None,
+        })
+}
+
+/// SS58 encode an `AccountId32` or a `MultiAddress::Id`, checksum the `AccountId20` of Frontier
+/// chains like their Ethereum addresses, other addresses are kept as values
+fn format_address<T>(address: Value<T>, address_format: Ss58AddressFormat) -> String
+where
+    T: std::fmt::Debug + Clone,
+{
+    let account_id = match &address.value {
+        ValueDef::Variant(Variant { name, values }) if name == "Id" && values.len() == 1 => {
+            values.values().next().unwrap().clone()
+        }
+        _ => address.clone(),
+    };
+
+    if let Ok(account_id) = decode_public_key(account_id.clone()) {
+        ss58::encode(&account_id, address_format)
+    } else if let Ok(account_id) = decode_public_key(account_id) {
+        checksum_address(&H160(account_id))
+    } else {
+        format_value(&address)
+    }
+}
+
+/// `Balances.Transfer { from, to, amount }`
+fn decode_transfer(
+    event: &Event,
+    address_format: Ss58AddressFormat,
+) -> Result<(String, String, String), Error> {
+    let mut fields = composite_to_record(event.values.clone())?;
+    let sender: Value<()> = get_field("from", &mut fields)?;
+    let receiver: Value<()> = get_field("to", &mut fields)?;
+    let amount: u128 = get_field("amount", &mut fields)?;
+
+    Ok((
+        format_address(sender, address_format),
+        format_address(receiver, address_format),
+        amount.to_string(),
+    ))
+}
+
+fn ethereum_transaction_row(
+    index: u32,
+    block_transaction: &BlockTransaction,
+) -> EthereumTransactionRow {
+    let transaction = &block_transaction.transaction;
+    let sender = match recover_sender(transaction) {
+        Ok(sender) => Some(checksum_address(&sender)),
+        Err(error) => {
+            warn!(%error);
+            None
+        }
+    };
+    let (action, value) = match transaction {
+        TransactionV3::Legacy(transaction) => (transaction.action, transaction.value),
+        TransactionV3::EIP2930(transaction) => (transaction.action, transaction.value),
+        TransactionV3::EIP1559(transaction) => (transaction.action, transaction.value),
+        TransactionV3::EIP7702(transaction) => (transaction.destination, transaction.value),
+    };
+    let receiver = match action {
+        TransactionAction::Call(address) => Some(address),
+        TransactionAction::Create => block_transaction
+            .status
+            .as_ref()
+            .and_then(|status| status.contract_address),
+    };
+
+    EthereumTransactionRow {
+        hash: format!("{:?}", transaction.hash()),
+        index,
+        sender,
+        receiver: receiver.map(|receiver| checksum_address(&receiver)),
+        value: value.to_string(),
+        success: block_transaction.succeeded(),
+    }
+}
@@ -0,0 +1,273 @@
+use std::time::Duration;
+
+use derive_more::Display;
+use tracing::{Instrument, Level, info, span, warn};
+
+use crate::{
+    error::Error,
+    fetch,
+    indexer::{
+        block::decode_block,
+        sink::{Cursor, IndexerSink},
+    },
+    node_rpc::{
+        client::NodeRPC,
+        models::{BlockHashHex, BlockHeader, BlockNumberHex},
+    },
+};
+
+/// Head of the chain the indexer follows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, clap::ValueEnum)]
+pub enum FollowMode {
+    /// Only finalized blocks, which are never retracted
+    #[display("finalized")]
+    Finalized,
+    /// Best blocks for low latency, retracted blocks are rolled back on reorgs
+    #[display("best")]
+    Best,
+}
+
+fn block_number(header: &BlockHeader) -> Result<u32, Error> {
+    u32::try_from(&header.number)
+        .map_err(|err| Error::ParsingBlockNumberFailed(header.number.clone(), err))
+}
+
+/// Whether an indexed block is still on the followed chain, which may have become shorter than it
+async fn is_canonical(
+    rpc: &NodeRPC,
+    block_number: u32,
+    block_hash: &BlockHashHex,
+) -> Result<bool, Error> {
+    let canonical_hash = rpc
+        .chain_get_block_hash_optional(&BlockNumberHex::from(block_number))
+        .await?;
+    Ok(canonical_hash.as_ref() == Some(block_hash))
+}
+
+/// Index the blocks following the cursor, or from `start_block` on an empty database, up to the
+/// followed head, then keep polling it
+///
+/// Every indexed block must be the child of the previously indexed one. When the parent hash of the
+/// next block doesn't match, or the last indexed block left the canonical chain, which may have
+/// become shorter than it, the blocks above the common ancestor are retracted and the new branch is
+/// indexed.
+pub async fn follow(
+    rpc: &NodeRPC,
+    sink: &IndexerSink,
+    follow_mode: FollowMode,
+    start_block: u32,
+    poll_interval: Duration,
+) -> Result<(), Error> {
+    let address_format = fetch::ss58_address_format(rpc).await?;
+
+    let mut cursor = sink.cursor().await?;
+    match &cursor {
+        Some(cursor) => info!(
+            block_number = cursor.block_number,
+            %cursor.block_hash,
+            %follow_mode,
+            "resuming after the last indexed block"
+        ),
+        None => info!(start_block, %follow_mode, "indexing an empty database"),
+    }
+
+    loop {
+        let finalized_hash = rpc.chain_get_finalized_head().await?;
+        let finalized_number = block_number(&rpc.chain_get_header(finalized_hash).await?)?;
+        let head_number = match follow_mode {
+            FollowMode::Finalized => finalized_number,
+            FollowMode::Best => block_number(&rpc.chain_get_best_header().await?)?,
+        };
+
+        // A reorg may replace the last indexed block without growing the chain, the blocks below it
+        // are linked by their parent hashes and are canonical if it is
+        if let Some(indexed) = &cursor
+            && !is_canonical(rpc, indexed.block_number, &indexed.block_hash).await?
+        {
+            cursor = retract(rpc, sink, indexed).await?;
+        }
+
+        let mut next_block = cursor
+            .as_ref()
+            .map_or(start_block, |cursor| cursor.block_number + 1);
+
+        while next_block <= head_number {
+            // The best chain got shorter since the head was read, the next poll checks the cursor
+            let Some(block_hash) = rpc
+                .chain_get_block_hash_optional(&BlockNumberHex::from(next_block))
+                .await?
+            else {
+                break;
+            };
+            let header = rpc.chain_get_header(block_hash.clone()).await?;
+
+            if let Some(indexed) = &cursor
+                && header.parent_hash != indexed.block_hash
+            {
+                cursor = retract(rpc, sink, indexed).await?;
+                next_block = cursor
+                    .as_ref()
+                    .map_or(start_block, |cursor| cursor.block_number + 1);
+                continue;
+            }
+
+            let indexed_block = decode_block(
+                rpc,
+                next_block,
+                block_hash.clone(),
+                next_block <= finalized_number,
+                address_format,
+            )
+            .instrument(span!(Level::INFO, "index block", block_number = next_block))
+            .await?;
+            sink.write_block(&indexed_block).await?;
+            info!(
+                block_number = next_block,
+                finalized = indexed_block.finalized,
+                extrinsics = indexed_block.extrinsics.len(),
+                events = indexed_block.events.len(),
+                "indexed block"
+            );
+
+            cursor = Some(Cursor {
+                block_number: next_block,
+                block_hash,
+            });
+            next_block += 1;
+        }
+
+        if follow_mode == FollowMode::Best {
+            sink.mark_finalized(finalized_number).await?;
+        }
+
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+/// Roll back the indexed blocks which left the canonical chain, returning the common ancestor
+///
+/// Finalized blocks are never retracted, so the search ends at the last finalized block at the
+/// latest.
+async fn retract(
+    rpc: &NodeRPC,
+    sink: &IndexerSink,
+    indexed: &Cursor,
+) -> Result<Option<Cursor>, Error> {
+    let mut block_number = indexed.block_number;
+    let common_ancestor = loop {
+        let Some(indexed_hash) = sink.block_hash(block_number).await? else {
+            break None;
+        };
+        if is_canonical(rpc, block_number, &indexed_hash).await? {
+            break Some(Cursor {
+                block_number,
+                block_hash: indexed_hash,
+            });
+        }
+
+        match block_number.checked_sub(1) {
+            Some(parent_number) => block_number = parent_number,
+            None => break None,
+        }
+    };
+
+    warn!(
+        retracted_from = indexed.block_number,
+        common_ancestor = ?common_ancestor.as_ref().map(|cursor| cursor.block_number),
+        "reorg, rolling back the retracted blocks"
+    );
+    sink.retract(common_ancestor.as_ref()).await?;
+
+    Ok(common_ancestor)
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpsee::{RpcModule, server::Server};
+
+    use super::*;
+    use crate::indexer::block::{BlockRow, IndexedBlock};
+
+    fn hash(branch: u8, block_number: u32) -> BlockHashHex {
+        let mut hash = [branch; 32];
+        hash[..4].copy_from_slice(&block_number.to_be_bytes());
+        BlockHashHex::from(hash)
+    }
+
+    fn indexed_block(block_number: u32, block_hash: BlockHashHex) -> IndexedBlock {
+        IndexedBlock {
+            finalized: false,
+            block: BlockRow {
+                number: block_number,
+                hash: block_hash,
+                parent_hash: BlockHashHex::from([0; 32]),
+                state_root: String::new(),
+                extrinsics_root: String::new(),
+                spec_version: 1,
+                timestamp: String::new(),
+                author: None,
+            },
+            extrinsics: vec![],
+            events: vec![],
+            transfers: vec![],
+            ethereum_transactions: vec![],
+        }
+    }
+
+    /// Node answering `chain_getBlockHash` from the hashes of its best chain, null above its head
+    async fn node(best_chain: Vec<BlockHashHex>) -> NodeRPC {
+        let mut module = RpcModule::new(best_chain);
+        module
+            .register_method("chain_getBlockHash", |params, best_chain, _| {
+                let block_number: BlockNumberHex = params.one().unwrap();
+                let block_number = u32::try_from(&block_number).unwrap();
+                best_chain.get(block_number as usize).cloned()
+            })
+            .unwrap();
+
+        let server = Server::builder().build("127.0.0.1:0").await.unwrap();
+        let address = server.local_addr().unwrap();
+        let handle = server.start(module);
+        tokio::spawn(handle.stopped());
+
+        NodeRPC::new(&format!("ws://{address}")).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn reorg_to_a_shorter_branch_retracts_the_blocks_above_its_head() {
+        let database_path =
+            std::env::temp_dir().join(format!("submarine-follower-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&database_path);
+        let sink = IndexerSink::connect(&format!("sqlite://{}?mode=rwc", database_path.display()))
+            .await
+            .unwrap();
+
+        // Blocks 0 to 3 of branch 1 are indexed, the best chain switched to blocks 0 to 1 of branch 2
+        for block_number in 0..=3 {
+            let block_hash = if block_number == 0 {
+                hash(0, 0)
+            } else {
+                hash(1, block_number)
+            };
+            sink.write_block(&indexed_block(block_number, block_hash))
+                .await
+                .unwrap();
+        }
+        let rpc = node(vec![hash(0, 0), hash(2, 1)]).await;
+
+        let indexed = sink.cursor().await.unwrap().unwrap();
+        assert!(
+            !is_canonical(&rpc, indexed.block_number, &indexed.block_hash)
+                .await
+                .unwrap()
+        );
+
+        let common_ancestor = retract(&rpc, &sink, &indexed).await.unwrap().unwrap();
+        assert_eq!(common_ancestor.block_number, 0);
+        assert_eq!(common_ancestor.block_hash, hash(0, 0));
+        assert_eq!(sink.cursor().await.unwrap().unwrap().block_number, 0);
+        assert_eq!(sink.block_hash(1).await.unwrap(), None);
+
+        let _ = std::fs::remove_file(&database_path);
+    }
+}
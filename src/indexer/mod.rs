@@ -0,0 +1,7 @@
+pub mod block;
+pub mod follower;
+pub mod schema;
+pub mod sink;
+
+pub use follower::{FollowMode, follow};
+pub use sink::{IndexerError, IndexerSink, connect_pool};
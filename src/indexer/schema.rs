@@ -0,0 +1,136 @@
+use sqlx::AnyPool;
+use tracing::info;
+
+use crate::indexer::sink::IndexerError;
+
+/// Schema migrations in order, the schema version is the number of migrations applied
+///
+/// The statements must run unchanged on both SQLite and Postgres.
+const MIGRATIONS: &[&[&str]] = &[
+    &[
+        "CREATE TABLE blocks (
+            number BIGINT PRIMARY KEY,
+            hash TEXT NOT NULL,
+            parent_hash TEXT NOT NULL,
+            state_root TEXT NOT NULL,
+            extrinsics_root TEXT NOT NULL,
+            spec_version BIGINT NOT NULL,
+            timestamp TEXT NOT NULL,
+            author TEXT
+        )",
+        "CREATE INDEX blocks_hash ON blocks (hash)",
+        "CREATE TABLE extrinsics (
+            id TEXT PRIMARY KEY,
+            block_number BIGINT NOT NULL,
+            extrinsic_index BIGINT NOT NULL,
+            hash TEXT NOT NULL,
+            pallet TEXT NOT NULL,
+            call TEXT NOT NULL,
+            signer TEXT,
+            success BOOLEAN
+        )",
+        "CREATE INDEX extrinsics_block_number ON extrinsics (block_number)",
+        "CREATE INDEX extrinsics_hash ON extrinsics (hash)",
+        "CREATE INDEX extrinsics_signer ON extrinsics (signer)",
+        "CREATE TABLE events (
+            id TEXT PRIMARY KEY,
+            block_number BIGINT NOT NULL,
+            event_index BIGINT NOT NULL,
+            extrinsic_id TEXT,
+            pallet TEXT NOT NULL,
+            name TEXT NOT NULL,
+            params TEXT NOT NULL
+        )",
+        "CREATE INDEX events_block_number ON events (block_number)",
+        "CREATE INDEX events_pallet_name ON events (pallet, name)",
+        "CREATE TABLE transfers (
+            event_id TEXT PRIMARY KEY,
+            block_number BIGINT NOT NULL,
+            extrinsic_id TEXT,
+            sender TEXT NOT NULL,
+            receiver TEXT NOT NULL,
+            amount TEXT NOT NULL
+        )",
+        "CREATE INDEX transfers_block_number ON transfers (block_number)",
+        "CREATE INDEX transfers_sender ON transfers (sender)",
+        "CREATE INDEX transfers_receiver ON transfers (receiver)",
+        "CREATE TABLE ethereum_transactions (
+            hash TEXT PRIMARY KEY,
+            block_number BIGINT NOT NULL,
+            transaction_index BIGINT NOT NULL,
+            sender TEXT,
+            receiver TEXT,
+            value TEXT NOT NULL,
+            success BOOLEAN
+        )",
+        "CREATE INDEX ethereum_transactions_block_number ON ethereum_transactions (block_number)",
+        "CREATE INDEX ethereum_transactions_sender ON ethereum_transactions (sender)",
+        "CREATE INDEX ethereum_transactions_receiver ON ethereum_transactions (receiver)",
+        "CREATE TABLE indexer_cursor (
+            id BIGINT PRIMARY KEY,
+            block_number BIGINT NOT NULL,
+            block_hash TEXT NOT NULL
+        )",
+    ],
+    // Only finalized blocks were indexed before best blocks could be followed
+    &[
+        "ALTER TABLE blocks ADD COLUMN finalized BOOLEAN NOT NULL DEFAULT TRUE",
+        "ALTER TABLE extrinsics ADD COLUMN finalized BOOLEAN NOT NULL DEFAULT TRUE",
+        "ALTER TABLE events ADD COLUMN finalized BOOLEAN NOT NULL DEFAULT TRUE",
+        "ALTER TABLE transfers ADD COLUMN finalized BOOLEAN NOT NULL DEFAULT TRUE",
+        "ALTER TABLE ethereum_transactions ADD COLUMN finalized BOOLEAN NOT NULL DEFAULT TRUE",
+    ],
+];
+
+async fn schema_version(pool: &AnyPool) -> Result<usize, IndexerError> {
+    let version: Option<i64> =
+        sqlx::query_scalar("SELECT version FROM schema_version ORDER BY version DESC LIMIT 1")
+            .fetch_optional(pool)
+            .await?;
+    Ok(version.unwrap_or(0) as usize)
+}
+
+/// Check that the database was migrated to the latest version, without migrating it
+pub async fn check_version(pool: &AnyPool) -> Result<(), IndexerError> {
+    let version = schema_version(pool).await?;
+    if version != MIGRATIONS.len() {
+        Err(IndexerError::UnsupportedSchemaVersion {
+            version,
+            latest: MIGRATIONS.len(),
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Bring the database schema up to the latest version, each migration in its own transaction
+pub async fn migrate(pool: &AnyPool) -> Result<(), IndexerError> {
+    sqlx::query("CREATE TABLE IF NOT EXISTS schema_version (version BIGINT NOT NULL)")
+        .execute(pool)
+        .await?;
+
+    let version = schema_version(pool).await?;
+
+    if version > MIGRATIONS.len() {
+        Err(IndexerError::UnsupportedSchemaVersion {
+            version,
+            latest: MIGRATIONS.len(),
+        })?;
+    }
+
+    for (i, statements) in MIGRATIONS.iter().enumerate().skip(version) {
+        let mut transaction = pool.begin().await?;
+        for statement in *statements {
+            sqlx::query(statement).execute(&mut *transaction).await?;
+        }
+        sqlx::query("INSERT INTO schema_version (version) VALUES ($1)")
+            .bind(i as i64 + 1)
+            .execute(&mut *transaction)
+            .await?;
+        transaction.commit().await?;
+
+        info!(version = i + 1, "migrated the indexer schema");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,299 @@
+use sqlx::{Any, AnyPool, Transaction, any::AnyPoolOptions};
+use thiserror::Error;
+
+use crate::{
+    indexer::{block::IndexedBlock, schema},
+    node_rpc::models::BlockHashHex,
+};
+
+#[derive(Debug, Error)]
+pub enum IndexerError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("The database has schema version {version}, this version reads version {latest}")]
+    UnsupportedSchemaVersion { version: usize, latest: usize },
+}
+
+/// Tables of the indexed records and their block number column
+const TABLES: [(&str, &str); 5] = [
+    ("blocks", "number"),
+    ("extrinsics", "block_number"),
+    ("events", "block_number"),
+    ("transfers", "block_number"),
+    ("ethereum_transactions", "block_number"),
+];
+
+/// Last block written to the database, indexing resumes after it
+#[derive(Debug, Clone)]
+pub struct Cursor {
+    pub block_number: u32,
+    pub block_hash: BlockHashHex,
+}
+
+/// Writes indexed blocks to SQLite or Postgres, picked by the scheme of the database URL
+#[derive(Debug, Clone)]
+pub struct IndexerSink {
+    pool: AnyPool,
+}
+
+/// Connect to the database without migrating it, for the readers of the indexed records
+pub async fn connect_pool(database_url: &str) -> Result<AnyPool, IndexerError> {
+    sqlx::any::install_default_drivers();
+    Ok(AnyPoolOptions::new().connect(database_url).await?)
+}
+
+impl IndexerSink {
+    /// Connect to the database and migrate its schema to the latest version
+    pub async fn connect(database_url: &str) -> Result<Self, IndexerError> {
+        let pool = connect_pool(database_url).await?;
+        schema::migrate(&pool).await?;
+
+        Ok(Self { pool })
+    }
+
+    pub async fn cursor(&self) -> Result<Option<Cursor>, IndexerError> {
+        let cursor: Option<(i64, String)> =
+            sqlx::query_as("SELECT block_number, block_hash FROM indexer_cursor WHERE id = 0")
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(cursor.map(|(block_number, block_hash)| Cursor {
+            block_number: block_number as u32,
+            block_hash: BlockHashHex(block_hash),
+        }))
+    }
+
+    /// Hash of the indexed block at a height
+    pub async fn block_hash(
+        &self,
+        block_number: u32,
+    ) -> Result<Option<BlockHashHex>, IndexerError> {
+        let block_hash: Option<String> =
+            sqlx::query_scalar("SELECT hash FROM blocks WHERE number = $1")
+                .bind(block_number as i64)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(block_hash.map(BlockHashHex))
+    }
+
+    /// Delete the records of the blocks retracted by a reorg, everything above the common ancestor,
+    /// and move the cursor back to it
+    pub async fn retract(&self, common_ancestor: Option<&Cursor>) -> Result<(), IndexerError> {
+        let mut transaction = self.pool.begin().await?;
+
+        // Without a common ancestor every indexed block was retracted
+        let above = common_ancestor.map_or(-1, |cursor| cursor.block_number as i64);
+        for (table, block_number_column) in TABLES {
+            sqlx::query(&format!(
+                "DELETE FROM {table} WHERE {block_number_column} > $1"
+            ))
+            .bind(above)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        match common_ancestor {
+            Some(cursor) => {
+                sqlx::query(
+                    "UPDATE indexer_cursor SET block_number = $1, block_hash = $2 WHERE id = 0",
+                )
+                .bind(cursor.block_number as i64)
+                .bind(&cursor.block_hash.0)
+                .execute(&mut *transaction)
+                .await?;
+            }
+            None => {
+                sqlx::query("DELETE FROM indexer_cursor")
+                    .execute(&mut *transaction)
+                    .await?;
+            }
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Tag the records of the blocks up to the finalized block as finalized
+    pub async fn mark_finalized(&self, finalized_number: u32) -> Result<(), IndexerError> {
+        let mut transaction = self.pool.begin().await?;
+
+        for (table, block_number_column) in TABLES {
+            sqlx::query(&format!(
+                "UPDATE {table} SET finalized = TRUE
+                WHERE {block_number_column} <= $1 AND finalized = FALSE"
+            ))
+            .bind(finalized_number as i64)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Upsert the rows of a block and move the cursor to it in a single transaction, so writing
+    /// the same block twice leaves the database unchanged
+    pub async fn write_block(&self, indexed_block: &IndexedBlock) -> Result<(), IndexerError> {
+        let mut transaction = self.pool.begin().await?;
+
+        write_rows(&mut transaction, indexed_block).await?;
+
+        let block = &indexed_block.block;
+        sqlx::query(
+            "INSERT INTO indexer_cursor (id, block_number, block_hash) VALUES (0, $1, $2)
+            ON CONFLICT (id) DO UPDATE SET
+                block_number = excluded.block_number,
+                block_hash = excluded.block_hash",
+        )
+        .bind(block.number as i64)
+        .bind(&block.hash.0)
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+        Ok(())
+    }
+}
+
+async fn write_rows(
+    transaction: &mut Transaction<'_, Any>,
+    indexed_block: &IndexedBlock,
+) -> Result<(), IndexerError> {
+    let block = &indexed_block.block;
+    let block_number = block.number as i64;
+
+    sqlx::query(
+        "INSERT INTO blocks (
+            number, hash, parent_hash, state_root, extrinsics_root, spec_version, timestamp, author,
+            finalized
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        ON CONFLICT (number) DO UPDATE SET
+            hash = excluded.hash,
+            parent_hash = excluded.parent_hash,
+            state_root = excluded.state_root,
+            extrinsics_root = excluded.extrinsics_root,
+            spec_version = excluded.spec_version,
+            timestamp = excluded.timestamp,
+            author = excluded.author,
+            finalized = excluded.finalized",
+    )
+    .bind(block_number)
+    .bind(&block.hash.0)
+    .bind(&block.parent_hash.0)
+    .bind(&block.state_root)
+    .bind(&block.extrinsics_root)
+    .bind(block.spec_version as i64)
+    .bind(&block.timestamp)
+    .bind(&block.author)
+    .bind(indexed_block.finalized)
+    .execute(&mut **transaction)
+    .await?;
+
+    for extrinsic in &indexed_block.extrinsics {
+        sqlx::query(
+            "INSERT INTO extrinsics (
+                id, block_number, extrinsic_index, hash, pallet, call, signer, success, finalized
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (id) DO UPDATE SET
+                block_number = excluded.block_number,
+                extrinsic_index = excluded.extrinsic_index,
+                hash = excluded.hash,
+                pallet = excluded.pallet,
+                call = excluded.call,
+                signer = excluded.signer,
+                success = excluded.success,
+                finalized = excluded.finalized",
+        )
+        .bind(&extrinsic.id)
+        .bind(block_number)
+        .bind(extrinsic.index as i64)
+        .bind(&extrinsic.hash)
+        .bind(&extrinsic.pallet)
+        .bind(&extrinsic.call)
+        .bind(&extrinsic.signer)
+        .bind(extrinsic.success)
+        .bind(indexed_block.finalized)
+        .execute(&mut **transaction)
+        .await?;
+    }
+
+    for event in &indexed_block.events {
+        sqlx::query(
+            "INSERT INTO events (
+                id, block_number, event_index, extrinsic_id, pallet, name, params, finalized
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (id) DO UPDATE SET
+                block_number = excluded.block_number,
+                event_index = excluded.event_index,
+                extrinsic_id = excluded.extrinsic_id,
+                pallet = excluded.pallet,
+                name = excluded.name,
+                params = excluded.params,
+                finalized = excluded.finalized",
+        )
+        .bind(&event.id)
+        .bind(block_number)
+        .bind(event.index as i64)
+        .bind(&event.extrinsic_id)
+        .bind(&event.pallet)
+        .bind(&event.name)
+        .bind(&event.params)
+        .bind(indexed_block.finalized)
+        .execute(&mut **transaction)
+        .await?;
+    }
+
+    for transfer in &indexed_block.transfers {
+        sqlx::query(
+            "INSERT INTO transfers (
+                event_id, block_number, extrinsic_id, sender, receiver, amount, finalized
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (event_id) DO UPDATE SET
+                block_number = excluded.block_number,
+                extrinsic_id = excluded.extrinsic_id,
+                sender = excluded.sender,
+                receiver = excluded.receiver,
+                amount = excluded.amount,
+                finalized = excluded.finalized",
+        )
+        .bind(&transfer.event_id)
+        .bind(block_number)
+        .bind(&transfer.extrinsic_id)
+        .bind(&transfer.sender)
+        .bind(&transfer.receiver)
+        .bind(&transfer.amount)
+        .bind(indexed_block.finalized)
+        .execute(&mut **transaction)
+        .await?;
+    }
+
+    for ethereum_transaction in &indexed_block.ethereum_transactions {
+        sqlx::query(
+            "INSERT INTO ethereum_transactions (
+                hash, block_number, transaction_index, sender, receiver, value, success, finalized
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (hash) DO UPDATE SET
+                block_number = excluded.block_number,
+                transaction_index = excluded.transaction_index,
+                sender = excluded.sender,
+                receiver = excluded.receiver,
+                value = excluded.value,
+                success = excluded.success,
+                finalized = excluded.finalized",
+        )
+        .bind(&ethereum_transaction.hash)
+        .bind(block_number)
+        .bind(ethereum_transaction.index as i64)
+        .bind(&ethereum_transaction.sender)
+        .bind(&ethereum_transaction.receiver)
+        .bind(&ethereum_transaction.value)
+        .bind(ethereum_transaction.success)
+        .bind(indexed_block.finalized)
+        .execute(&mut **transaction)
+        .await?;
+    }
+
+    Ok(())
+}
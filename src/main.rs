@@ -1,29 +1,77 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::SocketAddr,
+    path::PathBuf,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
 use clap::Parser;
-use frame_metadata::RuntimeMetadataPrefixed;
-use parity_scale_codec::Decode;
+use ethereum::{TransactionAction, TransactionV3};
+use ethereum_types::{H160, H256};
+use parity_scale_codec::{Decode, Encode};
 use prettytable::{Table, row, table};
 use sp_runtime::DigestItem;
-use ss58::Ss58AddressFormat;
 use tracing::{Instrument, Level, debug, info, span, warn};
 
 use crate::{
-    decoder::{extrinsic::decode_extrinsic_any, metadata::AnyRuntimeMetadata},
+    author::find_block_author,
+    call_tree::CallTree,
+    decoder::{
+        constant::{AnyConstantValue, decode_pallet_constant_any},
+        extrinsic::decode_extrinsic_any,
+        metadata::AnyRuntimeMetadata,
+        value_decoder::format_value,
+    },
     error::Error,
+    fetch::BlockStatus,
     node_rpc::{
         client::NodeRPC,
         models::{
-            BlockHashHex, BlockNumberHex, ChainMetadataBytes, ExtrinsicBytes, LogBytes,
-            RuntimeVersion,
+            BlockHashHex, BlockNumberHex, ExtrinsicBytes, FinalityProofBytes, Justification,
+            LogBytes, RuntimeVersion,
         },
     },
-    pallets::{ethereum, session, system::decoder::Phase},
+    pallets::{
+        babe::{self, digest::BabeSlotClaim},
+        collator_selection,
+        ethereum::abi::{AbiError, AbiRegistry, DecodedAbiItem, parse_abi_registration},
+        ethereum::address::checksum_address,
+        ethereum::transaction::{
+            BlockTransaction, match_transactions, recover_sender, transaction_type,
+        },
+        evm,
+        grandpa::{
+            self,
+            decoder::GrandpaAuthority,
+            justification::{
+                FinalityProof, GRANDPA_ENGINE_ID, GrandpaJustification, PrecommitStatus,
+            },
+        },
+        para_inclusion,
+        para_inherent::{self, decoder::ParaInherentData},
+        parachain_system::{self, decoder::ValidationDataInherent},
+        schema::{Compatibility, CompatibilityReport, Requirement, check_compatibility},
+        system::decoder::{EventRecord, Phase},
+    },
+    xcm::{XcmEvent, XcmProgram, decoder::XcmError},
 };
 
+mod api;
+mod author;
+mod call_tree;
 mod decoder;
+mod digest;
 mod error;
+mod eth_rpc;
 mod fetch;
+mod indexer;
 mod node_rpc;
 mod pallets;
+mod parachain;
+mod runtime_upgrade;
+mod verify;
+mod xcm;
 
 /// Infinity Query command line interface
 #[derive(clap::Parser, Debug)]
@@ -35,8 +83,110 @@ struct Args {
     #[arg(long, short, default_value = "10000")]
     block_number: u32,
 
-    #[arg(long, short, default_value = "ws://37.27.51.25:9944")]
+    #[arg(long, short, global = true, default_value = "ws://37.27.51.25:9944")]
     node_rpc_url: String,
+
+    /// Relay chain node used to find the relay chain block including a parablock
+    #[arg(long, global = true)]
+    relay_rpc_url: Option<String>,
+
+    /// Verify header hashes, extrinsics roots and storage proofs instead of trusting the node, the
+    /// runtime version and metadata are read from the verified runtime code
+    #[arg(long, global = true)]
+    verify: bool,
+
+    /// Contract ABI JSON used to decode EVM transactions and logs, as `<contract address>=<path>`
+    #[arg(long, global = true, value_parser = parse_abi_registration)]
+    abi: Vec<(H160, PathBuf)>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Diff the runtime metadata of two blocks
+    DiffRuntime {
+        #[arg(long)]
+        from_block: u32,
+
+        #[arg(long)]
+        to_block: u32,
+    },
+
+    /// Find the runtime upgrades in a block range
+    RuntimeUpgrades {
+        #[arg(long)]
+        from_block: u32,
+
+        #[arg(long)]
+        to_block: u32,
+    },
+
+    /// Check the runtime at a block against the storage schemas of the pallet decoders
+    Check,
+
+    /// Decode the pallet constants at a block
+    Constants {
+        /// Only show the constants of this pallet
+        #[arg(long)]
+        pallet: Option<String>,
+    },
+
+    /// Show the balance, nonce, code and storage slots of an EVM account at a block
+    EvmAccount {
+        address: H160,
+
+        /// Contract storage slot to read, can be repeated
+        #[arg(long = "slot")]
+        slots: Vec<H256>,
+    },
+
+    /// Verify the GRANDPA justification finalizing a block against the authority set
+    Finality,
+
+    /// Serve an Ethereum JSON-RPC read API decoded from the Substrate storage
+    EthRpc {
+        #[arg(long, default_value = "127.0.0.1:8545")]
+        listen_address: SocketAddr,
+
+        /// Number of blocks scanned back from the best block to find a transaction by hash
+        #[arg(long, default_value = "256")]
+        lookback: u32,
+
+        /// Indexer database to look transactions up by hash before scanning blocks
+        #[arg(long)]
+        database_url: Option<String>,
+    },
+
+    /// Index blocks, extrinsics, events, transfers and Ethereum transactions into a database
+    Index {
+        /// `sqlite://<path>?mode=rwc` or `postgres://<user>:<password>@<host>/<database>`
+        #[arg(long)]
+        database_url: String,
+
+        /// First block to index when the database has no cursor yet
+        #[arg(long, default_value = "0")]
+        start_block: u32,
+
+        /// Follow the finalized head, or the best head rolling back retracted blocks on reorgs
+        #[arg(long, value_enum, default_value_t = indexer::FollowMode::Finalized)]
+        follow: indexer::FollowMode,
+
+        /// Seconds between polls of the finalized head once all finalized blocks are indexed
+        #[arg(long, default_value = "6")]
+        poll_interval: u64,
+    },
+
+    /// Serve a REST API over the indexer database, decoding missing blocks from the node
+    Serve {
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        listen_address: SocketAddr,
+
+        /// Indexer database to serve from, blocks are only decoded from the node without it
+        #[arg(long)]
+        database_url: Option<String>,
+    },
 }
 
 #[tokio::main]
@@ -53,9 +203,27 @@ async fn main() -> Result<(), Error> {
         .finish();
     tracing::subscriber::set_global_default(collector)?;
 
+    let abi_registry = AbiRegistry::load(&args.abi)?;
+
     info!("Starting chain follower.");
 
-    let rpc = NodeRPC::new(&args.node_rpc_url).await?;
+    let mut rpc = NodeRPC::new(&args.node_rpc_url).await?;
+    if args.verify {
+        info!("Verifying block headers, extrinsics, storage proofs and runtime code.");
+        rpc = rpc.with_storage_verification();
+    }
+
+    let relay_rpc = match &args.relay_rpc_url {
+        Some(relay_rpc_url) => {
+            let relay_rpc = NodeRPC::new(relay_rpc_url).await?;
+            Some(if args.verify {
+                relay_rpc.with_storage_verification()
+            } else {
+                relay_rpc
+            })
+        }
+        None => None,
+    };
 
     let system_chain = rpc.system_chain().await?;
     info!("System chain: {system_chain}");
@@ -72,29 +240,145 @@ async fn main() -> Result<(), Error> {
         sync_state.starting_block, sync_state.current_block, sync_state.highest_block
     );
 
-    if let Err(error) = fetch_block(&rpc, args.block_number)
-        .instrument(span!(
-            Level::INFO,
-            "fetch block",
-            block_number = args.block_number
-        ))
-        .await
-    {
+    let result = match args.command {
+        None => {
+            fetch_block(&rpc, relay_rpc.as_ref(), args.block_number, &abi_registry)
+                .instrument(span!(
+                    Level::INFO,
+                    "fetch block",
+                    block_number = args.block_number
+                ))
+                .await
+        }
+        Some(Command::DiffRuntime {
+            from_block,
+            to_block,
+        }) => {
+            diff_runtime(&rpc, from_block, to_block)
+                .instrument(span!(Level::INFO, "diff runtime", from_block, to_block))
+                .await
+        }
+        Some(Command::RuntimeUpgrades {
+            from_block,
+            to_block,
+        }) => {
+            runtime_upgrades(&rpc, from_block, to_block)
+                .instrument(span!(Level::INFO, "runtime upgrades", from_block, to_block))
+                .await
+        }
+        Some(Command::Check) => {
+            check_runtime(&rpc, args.block_number)
+                .instrument(span!(
+                    Level::INFO,
+                    "check runtime",
+                    block_number = args.block_number
+                ))
+                .await
+        }
+        Some(Command::Constants { pallet }) => {
+            print_constants(&rpc, args.block_number, pallet.as_deref())
+                .instrument(span!(
+                    Level::INFO,
+                    "constants",
+                    block_number = args.block_number
+                ))
+                .await
+        }
+        Some(Command::EvmAccount { address, slots }) => {
+            print_evm_account(&rpc, args.block_number, &address, &slots)
+                .instrument(span!(
+                    Level::INFO,
+                    "evm account",
+                    block_number = args.block_number,
+                    ?address
+                ))
+                .await
+        }
+        Some(Command::Finality) => {
+            print_finality(&rpc, args.block_number)
+                .instrument(span!(
+                    Level::INFO,
+                    "finality",
+                    block_number = args.block_number
+                ))
+                .await
+        }
+        Some(Command::EthRpc {
+            listen_address,
+            lookback,
+            database_url,
+        }) => {
+            eth_rpc::server::serve(rpc, listen_address, lookback, database_url.as_deref())
+                .instrument(span!(Level::INFO, "eth rpc", %listen_address))
+                .await
+        }
+        Some(Command::Index {
+            database_url,
+            start_block,
+            follow,
+            poll_interval,
+        }) => {
+            index(&rpc, &database_url, follow, start_block, poll_interval)
+                .instrument(span!(Level::INFO, "index", %follow))
+                .await
+        }
+        Some(Command::Serve {
+            listen_address,
+            database_url,
+        }) => {
+            serve(rpc, database_url.as_deref(), listen_address)
+                .instrument(span!(Level::INFO, "serve", %listen_address))
+                .await
+        }
+    };
+
+    if let Err(error) = result {
         warn!(%error);
     }
 
     Ok(())
 }
 
-async fn fetch_block(rpc: &NodeRPC, block_number: u32) -> Result<(), Error> {
+async fn index(
+    rpc: &NodeRPC,
+    database_url: &str,
+    follow_mode: indexer::FollowMode,
+    start_block: u32,
+    poll_interval: u64,
+) -> Result<(), Error> {
+    let sink = indexer::IndexerSink::connect(database_url).await?;
+    indexer::follow(
+        rpc,
+        &sink,
+        follow_mode,
+        start_block,
+        Duration::from_secs(poll_interval),
+    )
+    .await
+}
+
+async fn serve(
+    rpc: NodeRPC,
+    database_url: Option<&str>,
+    listen_address: SocketAddr,
+) -> Result<(), Error> {
+    let store = match database_url {
+        Some(database_url) => Some(api::store::ApiStore::connect(database_url).await?),
+        None => None,
+    };
+    api::server::serve(rpc, store, listen_address).await
+}
+
+async fn fetch_block(
+    rpc: &NodeRPC,
+    relay_rpc: Option<&NodeRPC>,
+    block_number: u32,
+    abi_registry: &AbiRegistry,
+) -> Result<(), Error> {
     let queried_block_number = BlockNumberHex::from(block_number);
     let block_hash = rpc.chain_get_block_hash(&queried_block_number).await?;
 
-    let ChainMetadataBytes(metadata_bytes) = rpc.state_get_metadata(&block_hash).await?;
-
-    let RuntimeMetadataPrefixed(_, metadata) =
-        RuntimeMetadataPrefixed::decode(&mut &metadata_bytes[..])
-            .map_err(Error::ParsingRuntimeMetadataFailed)?;
+    let metadata = fetch::fetch_metadata(rpc, &block_hash).await?;
 
     debug!(?metadata);
 
@@ -102,15 +386,33 @@ async fn fetch_block(rpc: &NodeRPC, block_number: u32) -> Result<(), Error> {
 
     info!("Metadata version: {}", metadata.version());
 
+    let compatibility_report = check_compatibility(pallets::STORAGE_SCHEMAS, metadata)?;
+    debug!(?compatibility_report);
+
+    if !compatibility_report.is_compatible() {
+        print_compatibility_report(&compatibility_report);
+        Err(Error::IncompatibleRuntime {
+            incompatible_count: compatibility_report.incompatible_count(),
+        })?;
+    }
+
     let pallets = metadata.pallet_names()?;
 
     info!(?pallets);
 
-    let mut signed_block = rpc.chain_get_block(&block_hash).await?;
+    let signed_block = rpc.chain_get_block(&block_hash).await?;
     debug!(?signed_block);
 
     let runtime_version = rpc.state_get_runtime_version(&block_hash).await?;
 
+    let verified = rpc.storage_verifier().is_some();
+    if let Some(storage_verifier) = rpc.storage_verifier() {
+        storage_verifier
+            .verify_runtime_version(rpc, &block_hash, &runtime_version)
+            .await?;
+        verify::verify_block(&signed_block.block, &block_hash, &runtime_version)?;
+    }
+
     let digest_logs = signed_block
         .block
         .header
@@ -125,16 +427,71 @@ async fn fetch_block(rpc: &NodeRPC, block_number: u32) -> Result<(), Error> {
     let timestamp =
         pallets::timestamp::fetch_timestamp(rpc, &block_hash, metadata, &runtime_version).await?;
 
-    let block_validator =
-        find_validator(rpc, &digest_logs, &block_hash, metadata, &runtime_version).await?;
+    let babe_slot_claim =
+        babe::digest::find_slot_claim(&digest_logs).map_err(Error::ParsingDigestLogsFailed)?;
+    debug!(?babe_slot_claim);
 
-    let block_collator =
-        find_collator(rpc, &digest_logs, &block_hash, metadata, &runtime_version).await?;
+    let block_author = find_block_author(
+        rpc,
+        &digest_logs,
+        &block_hash,
+        &signed_block.block.header.parent_hash,
+        metadata,
+        &runtime_version,
+    )
+    .await
+    .unwrap_or_else(|error| {
+        warn!(%error, "block author unavailable");
+        None
+    });
+    debug!(?block_author);
+
+    let block_author = match block_author {
+        Some(block_author) => {
+            let address_format = fetch::ss58_address_format(rpc).await?;
+            let address = ss58::encode(&block_author.account_id, address_format);
+
+            let collator_role = if babe_slot_claim.is_none()
+                && pallets.contains(collator_selection::fetch::PALLET_NAME)
+            {
+                collator_selection::fetch_collator_role(
+                    rpc,
+                    &block_author.account_id,
+                    &block_hash,
+                    metadata,
+                    &runtime_version,
+                )
+                .await
+                .unwrap_or_else(|error| {
+                    warn!(%error);
+                    None
+                })
+            } else {
+                None
+            };
+
+            Some(match collator_role {
+                Some(collator_role) => {
+                    format!("{address} ({}, {collator_role})", block_author.source)
+                }
+                None => format!("{address} ({})", block_author.source),
+            })
+        }
+        None => None,
+    };
+    // BABE blocks are authored by relay chain validators, AURA blocks by parachain collators
+    let (block_validator, block_collator) = if babe_slot_claim.is_some() {
+        (block_author, None)
+    } else {
+        (None, block_author)
+    };
+
+    let block_status = fetch::fetch_block_status(rpc, block_number).await?;
 
     let mut block_table = table![
         ["Timestamp", timestamp],
         ["Block Time"],
-        ["Status"],
+        ["Status", block_status],
         ["Hash", block_hash],
         ["Parent Hash", signed_block.block.header.parent_hash],
         ["State Root", signed_block.block.header.state_root],
@@ -142,213 +499,1335 @@ async fn fetch_block(rpc: &NodeRPC, block_number: u32) -> Result<(), Error> {
         ["Validator", block_validator.unwrap_or(String::from("-"))],
         ["Collator", block_collator.unwrap_or(String::from("-"))],
         ["Ref Time"],
-        ["Spec Version", runtime_version.spec_version]
+        ["Spec Version", runtime_version.spec_version],
+        [
+            "Verified",
+            if verified {
+                "Header hash, extrinsics root, storage proofs, runtime version and metadata"
+            } else {
+                "No, trusting the node"
+            }
+        ]
     ];
 
+    if let Some(babe_slot_claim) = &babe_slot_claim {
+        add_babe_rows(
+            &mut block_table,
+            rpc,
+            babe_slot_claim,
+            &block_hash,
+            metadata,
+            &runtime_version,
+        )
+        .await;
+    }
+
+    // Decoded once for the parachain rows, the candidates and the XCM messages
+    let validation_data_inherent = if pallets.contains(parachain_system::decoder::PALLET_NAME) {
+        parachain_system::find_validation_data(
+            &signed_block.block.extrinsics,
+            metadata,
+            runtime_version.spec_version,
+        )
+        .inspect_err(|error| warn!(%error, "parachain validation data unavailable"))
+    } else {
+        Ok(None)
+    };
+    debug!(?validation_data_inherent);
+    let para_inherent = if pallets.contains(para_inherent::decoder::PALLET_NAME) {
+        para_inherent::find_para_inherent(
+            &signed_block.block.extrinsics,
+            metadata,
+            runtime_version.spec_version,
+        )
+        .unwrap_or_else(|error| {
+            warn!(%error, "para inherent unavailable");
+            None
+        })
+    } else {
+        None
+    };
+    debug!(?para_inherent);
+
+    if pallets.contains(parachain_system::decoder::PALLET_NAME) {
+        add_parachain_rows(
+            &mut block_table,
+            rpc,
+            relay_rpc,
+            validation_data_inherent.as_ref().map(Option::as_ref),
+            &block_hash,
+            metadata,
+            &runtime_version,
+        )
+        .await;
+    }
+
     block_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
     println!("Block");
     block_table.printstd();
 
+    print_digest_logs(&digest_logs);
+
+    let events =
+        pallets::system::fetch_events(rpc, &block_hash, metadata, &runtime_version).await?;
+
     let mut extrinsics_table = Table::new();
     extrinsics_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
 
-    signed_block
-        .block
-        .extrinsics
-        .iter_mut()
-        .enumerate()
-        .for_each(|(i, ExtrinsicBytes(ext))| {
-            match decode_extrinsic_any(ext, metadata, runtime_version.spec_version) {
-                Ok(extrinsic) => {
-                    let extrinsic_id = format!("{block_number}-{i}");
-                    let action = format!("{} ({})", extrinsic.pallet_name(), extrinsic.call_name());
-                    extrinsics_table.add_row(row![extrinsic_id, "hash", "time", "result", action]);
-                    debug!(?extrinsic);
+    // Nested call of the extrinsic which emitted the event, by event index
+    let mut event_calls = HashMap::new();
+
+    for (i, ExtrinsicBytes(ext)) in signed_block.block.extrinsics.iter().enumerate() {
+        match decode_extrinsic_any(ext, metadata, runtime_version.spec_version) {
+            Ok(extrinsic) => {
+                let extrinsic_id = format!("{block_number}-{i}");
+                let call_tree = match extrinsic.call_args(ext, metadata) {
+                    Ok(args) => CallTree::new(extrinsic.pallet_name(), extrinsic.call_name(), args),
+                    Err(error) => {
+                        debug!(%error);
+                        CallTree::new::<u32>(extrinsic.pallet_name(), extrinsic.call_name(), vec![])
+                    }
+                };
+
+                let extrinsic_events = events
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, record)| match record.phase {
+                        Phase::ApplyExtrinsic(ext_idx) => ext_idx as usize == i,
+                        _ => false,
+                    })
+                    .collect::<Vec<_>>();
+                let paths = call_tree.attribute_events(
+                    &extrinsic_events
+                        .iter()
+                        .map(|(_, record)| &record.event)
+                        .collect::<Vec<_>>(),
+                );
+
+                let mut event_ids_by_path = HashMap::<_, Vec<_>>::new();
+                for ((event_idx, _), path) in extrinsic_events.into_iter().zip(paths) {
+                    if !path.is_empty() {
+                        event_ids_by_path
+                            .entry(path.clone())
+                            .or_default()
+                            .push(format!("{block_number}-{event_idx}"));
+                        event_calls.insert(event_idx, path);
+                    }
                 }
-                Err(error) => warn!("{error}"),
-            };
-        });
+
+                let action = call_tree.format(&|path| match event_ids_by_path.get(path) {
+                    Some(event_ids) => format!(" [{}]", event_ids.join(", ")),
+                    None => String::new(),
+                });
+                extrinsics_table.add_row(row![extrinsic_id, "hash", "time", "result", action]);
+                debug!(?extrinsic, ?call_tree);
+            }
+            Err(error) => warn!("{error}"),
+        };
+    }
 
     println!("Extrinsics");
     extrinsics_table.printstd();
 
-    fetch_events(rpc, block_number, &block_hash, metadata, &runtime_version).await?;
+    print_events(block_number, &events, &event_calls, abi_registry);
 
-    if pallets.contains("Ethereum") {
-        let ethereum_block =
-            pallets::ethereum::fetch_block(rpc, &block_hash, metadata, &runtime_version)
-                .instrument(span!(Level::INFO, "Fetch Ethereum block", ?block_hash))
-                .await?;
-        debug!(?ethereum_block);
+    if pallets.contains(para_inclusion::decoder::PALLET_NAME) {
+        print_parachain_candidates(para_inherent.as_ref(), &events);
+    }
 
-        let ethereum_block_hash = pallets::ethereum::fetch_block_hash(
+    if xcm::event::XCM_PALLETS
+        .iter()
+        .any(|pallet| pallets.contains(*pallet))
+    {
+        print_xcm(
+            block_number,
+            &signed_block.block.extrinsics,
+            &events,
+            validation_data_inherent
+                .as_ref()
+                .ok()
+                .and_then(Option::as_ref),
+            para_inherent.as_ref(),
+            metadata,
+            &runtime_version,
+        );
+    }
+
+    // The Ethereum storage schemas are optional, a failure only leaves the Ethereum block out
+    if pallets.contains("Ethereum")
+        && let Err(error) = print_ethereum_block(
             rpc,
             block_number,
             &block_hash,
+            block_status,
             metadata,
             &runtime_version,
+            abi_registry,
         )
-        .await?;
-
-        let mut block_table = table![
-            ["Status"],
-            ["Hash", format!("0x{}", hex::encode(ethereum_block_hash))],
-            [
-                "Parent Hash",
-                format!("0x{}", hex::encode(ethereum_block.header.parent_hash))
-            ],
-            [
-                "State Root",
-                format!("0x{}", hex::encode(ethereum_block.header.state_root))
-            ],
-            ["Mined By"]
-        ];
-
-        block_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
-        println!("Ethereum Block");
-        block_table.printstd();
+        .await
+    {
+        warn!(%error, "Ethereum block unavailable");
     }
 
     Ok(())
 }
 
-async fn find_validator(
+async fn print_ethereum_block(
     rpc: &NodeRPC,
-    digest_logs: &[DigestItem],
+    block_number: u32,
     block_hash: &BlockHashHex,
+    block_status: BlockStatus,
     metadata: AnyRuntimeMetadata<'_>,
     runtime_version: &RuntimeVersion,
-) -> Result<Option<String>, Error> {
-    let babe_pre_digest_log = digest_logs
-        .iter()
-        .find_map(|log| match log {
-            DigestItem::PreRuntime(consensus_engine_id, bytes) => {
-                if &consensus_engine_id[..] == "BABE".as_bytes() {
-                    Some(sc_consensus_babe::PreDigest::decode(&mut &bytes[..]))
-                } else {
-                    None
-                }
+    abi_registry: &AbiRegistry,
+) -> Result<(), Error> {
+    let ethereum_block = pallets::ethereum::fetch_block(rpc, block_hash, metadata, runtime_version)
+        .instrument(span!(Level::INFO, "Fetch Ethereum block", ?block_hash))
+        .await?;
+    debug!(?ethereum_block);
+
+    let ethereum_block_hash = pallets::ethereum::fetch_block_hash(
+        rpc,
+        block_number,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await?;
+
+    // The transactions are still shown without their receipts and statuses, with "-" results
+    let receipts =
+        match pallets::ethereum::fetch_receipts(rpc, block_hash, metadata, runtime_version).await {
+            Ok(receipts) => Some(receipts),
+            Err(error) => {
+                warn!(%error, "Ethereum receipts unavailable");
+                None
             }
-            _ => None,
-        })
-        .transpose()
-        .map_err(Error::ParsingDigestLogsFailed)?;
+        };
+    let transaction_statuses = match pallets::ethereum::fetch_transaction_statuses(
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await
+    {
+        Ok(transaction_statuses) => Some(transaction_statuses),
+        Err(error) => {
+            warn!(%error, "Ethereum transaction statuses unavailable");
+            None
+        }
+    };
+    debug!(?receipts, ?transaction_statuses);
 
-    debug!(?babe_pre_digest_log);
+    let format_count =
+        |items: Option<usize>| items.map_or(String::from("Unavailable"), |count| count.to_string());
+    let mut block_table = table![
+        ["Status", block_status],
+        ["Hash", format!("0x{}", hex::encode(ethereum_block_hash))],
+        [
+            "Parent Hash",
+            format!("0x{}", hex::encode(ethereum_block.header.parent_hash))
+        ],
+        [
+            "State Root",
+            format!("0x{}", hex::encode(ethereum_block.header.state_root))
+        ],
+        [
+            "Mined By",
+            checksum_address(&ethereum_block.header.beneficiary)
+        ],
+        ["Receipts", format_count(receipts.as_ref().map(Vec::len))],
+        [
+            "Transaction Statuses",
+            format_count(transaction_statuses.as_ref().map(Vec::len))
+        ]
+    ];
 
-    Ok(match babe_pre_digest_log {
-        Some(babe_pre_digest_log) => {
-            let authority_index = babe_pre_digest_log.authority_index();
+    block_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    println!("Ethereum Block");
+    block_table.printstd();
 
-            let validators =
-                session::fetch_validators(rpc, block_hash, metadata, runtime_version).await?;
+    let transactions = match_transactions(
+        ethereum_block.transactions,
+        receipts.unwrap_or_default(),
+        transaction_statuses.unwrap_or_default(),
+    );
 
-            let block_validator_pubkey = validators
-                .get(authority_index as usize)
-                .ok_or(Error::ValidatorNotFoundForIndex(authority_index))?
-                .0;
+    print_ethereum_transactions(&transactions, abi_registry);
+    print_ethereum_logs(&transactions, abi_registry);
 
-            let address_format = match rpc.system_properties().await?.ss58_format {
-                0 => Ss58AddressFormat::Polkadot,
-                2 => Ss58AddressFormat::Kusama,
-                42 => Ss58AddressFormat::Substrate,
-                137 => Ss58AddressFormat::Vara,
-                other => Ss58AddressFormat::Custom(other),
-            };
+    Ok(())
+}
 
-            Some(ss58::encode(&block_validator_pubkey, address_format))
+/// Format a decoded ABI item, `None` if no registered ABI matches
+fn format_decoded_abi_item(decoded: Result<Option<DecodedAbiItem>, AbiError>) -> Option<String> {
+    match decoded {
+        Ok(decoded) => decoded.map(|decoded| decoded.to_string()),
+        Err(error) => {
+            warn!(%error);
+            None
         }
-        None => None,
-    })
+    }
 }
 
-/// Unit type wrapper that represents a slot.
-#[derive(Debug, parity_scale_codec::Encode, parity_scale_codec::Decode, Clone, Copy)]
-#[repr(transparent)]
-pub struct Slot(pub u64);
+fn print_ethereum_transactions(transactions: &[BlockTransaction], abi_registry: &AbiRegistry) {
+    let mut transactions_table = Table::new();
+    transactions_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    transactions_table.set_titles(row![
+        "Index", "Hash", "Type", "From", "To", "Value", "Call", "Result", "Gas Used"
+    ]);
 
-async fn find_collator(
-    rpc: &NodeRPC,
-    digest_logs: &[DigestItem],
-    block_hash: &BlockHashHex,
-    metadata: AnyRuntimeMetadata<'_>,
-    runtime_version: &RuntimeVersion,
-) -> Result<Option<String>, Error> {
-    let aura_pre_digest_log = digest_logs
+    transactions
         .iter()
-        .find_map(|log| match log {
-            DigestItem::PreRuntime(consensus_engine_id, bytes) => {
-                if &consensus_engine_id[..] == "AURA".as_bytes() {
-                    Some(Slot::decode(&mut &bytes[..]))
-                } else {
-                    None
+        .enumerate()
+        .for_each(|(i, block_transaction)| {
+            let transaction = &block_transaction.transaction;
+            let sender = match recover_sender(transaction) {
+                Ok(sender) => checksum_address(&sender),
+                Err(error) => {
+                    warn!(%error);
+                    String::from("-")
                 }
-            }
-            _ => None,
-        })
-        .transpose()
-        .map_err(Error::ParsingDigestLogsFailed)?;
+            };
+            let (action, value, input) = match transaction {
+                TransactionV3::Legacy(transaction) => {
+                    (transaction.action, transaction.value, &transaction.input)
+                }
+                TransactionV3::EIP2930(transaction) => {
+                    (transaction.action, transaction.value, &transaction.input)
+                }
+                TransactionV3::EIP1559(transaction) => {
+                    (transaction.action, transaction.value, &transaction.input)
+                }
+                TransactionV3::EIP7702(transaction) => (
+                    transaction.destination,
+                    transaction.value,
+                    &transaction.data,
+                ),
+            };
+            let call = match action {
+                TransactionAction::Call(address) => {
+                    format_decoded_abi_item(abi_registry.decode_input(&address, input))
+                }
+                TransactionAction::Create => None,
+            };
+            let contract_address = block_transaction
+                .status
+                .as_ref()
+                .and_then(|status| status.contract_address);
+            let receiver = match (action, contract_address) {
+                (TransactionAction::Call(address), _) => checksum_address(&address),
+                (TransactionAction::Create, Some(contract_address)) => {
+                    format!(
+                        "Contract creation ({})",
+                        checksum_address(&contract_address)
+                    )
+                }
+                (TransactionAction::Create, None) => String::from("Contract creation"),
+            };
+            let result = match block_transaction.succeeded() {
+                Some(true) => "Success",
+                Some(false) => "Failed",
+                None => "-",
+            };
 
-    debug!(?aura_pre_digest_log);
+            transactions_table.add_row(row![
+                i,
+                format!("{:?}", transaction.hash()),
+                transaction_type(transaction),
+                sender,
+                receiver,
+                value,
+                call.unwrap_or(String::from("-")),
+                result,
+                block_transaction
+                    .gas_used
+                    .map(|gas_used| gas_used.to_string())
+                    .unwrap_or(String::from("-"))
+            ]);
+        });
 
-    Ok(match aura_pre_digest_log {
-        Some(slot) => {
-            let validators =
-                session::fetch_validators(rpc, block_hash, metadata, runtime_version).await?;
+    println!("Ethereum Transactions");
+    transactions_table.printstd();
+}
 
-            let authority_index = slot.0 % validators.len() as u64;
+fn print_ethereum_logs(transactions: &[BlockTransaction], abi_registry: &AbiRegistry) {
+    let mut logs_table = Table::new();
+    logs_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    logs_table.set_titles(row![
+        "Transaction",
+        "Index",
+        "Address",
+        "Event",
+        "Topics",
+        "Data"
+    ]);
 
-            let block_validator_pubkey = validators
-                .get(authority_index as usize)
-                .ok_or(Error::ValidatorNotFoundForIndex(authority_index as u32))?
-                .0;
+    transactions
+        .iter()
+        .enumerate()
+        .for_each(|(transaction_index, transaction)| {
+            transaction
+                .logs()
+                .iter()
+                .enumerate()
+                .for_each(|(log_index, log)| {
+                    let topics = log
+                        .topics
+                        .iter()
+                        .map(|topic| format!("{topic:?}"))
+                        .collect::<Vec<_>>();
+                    logs_table.add_row(row![
+                        transaction_index,
+                        log_index,
+                        checksum_address(&log.address),
+                        format_decoded_abi_item(abi_registry.decode_log(log))
+                            .unwrap_or(String::from("-")),
+                        topics.join("\n"),
+                        format!("0x{}", hex::encode(&log.data))
+                    ]);
+                });
+        });
 
-            let address_format = match rpc.system_properties().await?.ss58_format {
-                0 => Ss58AddressFormat::Polkadot,
-                2 => Ss58AddressFormat::Kusama,
-                42 => Ss58AddressFormat::Substrate,
-                137 => Ss58AddressFormat::Vara,
-                other => Ss58AddressFormat::Custom(other),
-            };
+    println!("Ethereum Logs");
+    logs_table.printstd();
+}
+
+/// Slot, claim and epoch details of a BABE block
+///
+/// Rows which can't be fetched are shown as "-".
+async fn add_babe_rows(
+    block_table: &mut Table,
+    rpc: &NodeRPC,
+    babe_slot_claim: &BabeSlotClaim,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) {
+    let slot_time = babe::fetch::slot_duration(metadata, runtime_version)
+        .inspect_err(|error| warn!(%error))
+        .ok()
+        .and_then(|slot_duration| babe_slot_claim.slot.checked_mul(slot_duration))
+        .and_then(|millis| i64::try_from(millis).ok())
+        .and_then(DateTime::<Utc>::from_timestamp_millis)
+        .map(|slot_time| slot_time.to_string())
+        .unwrap_or(String::from("-"));
 
-            Some(ss58::encode(&block_validator_pubkey, address_format))
+    let epoch = match (
+        babe::fetch_epoch_index(rpc, block_hash, metadata, runtime_version).await,
+        babe::fetch::epoch_duration(metadata, runtime_version),
+    ) {
+        (Ok(epoch_index), Ok(epoch_duration)) => format!("{epoch_index} ({epoch_duration} slots)"),
+        (Err(error), _) | (_, Err(error)) => {
+            warn!(%error);
+            String::from("-")
         }
-        None => None,
-    })
+    };
+    let authorities =
+        match babe::fetch_authorities(rpc, block_hash, metadata, runtime_version).await {
+            Ok(authorities) => authorities.len().to_string(),
+            Err(error) => {
+                warn!(%error);
+                String::from("-")
+            }
+        };
+    let next_epoch_config =
+        match babe::fetch_next_epoch_config(rpc, block_hash, metadata, runtime_version).await {
+            Ok(next_epoch_config) => next_epoch_config
+                .map(|next_epoch_config| next_epoch_config.to_string())
+                .unwrap_or(String::from("Unchanged")),
+            Err(error) => {
+                warn!(%error);
+                String::from("-")
+            }
+        };
+
+    block_table.add_row(row!["Slot", babe_slot_claim.slot]);
+    block_table.add_row(row!["Slot Time", slot_time]);
+    block_table.add_row(row!["Claim", babe_slot_claim.claim_type]);
+    block_table.add_row(row![
+        "VRF Output",
+        babe_slot_claim
+            .vrf_output
+            .as_ref()
+            .map(|vrf_output| format!("0x{}", hex::encode(vrf_output)))
+            .unwrap_or(String::from("-"))
+    ]);
+    block_table.add_row(row!["Epoch", epoch]);
+    block_table.add_row(row!["Epoch Authorities", authorities]);
+    block_table.add_row(row!["Next Epoch Config", next_epoch_config]);
 }
 
-async fn fetch_events(
+/// Relay parent, inbound messages and relay chain inclusion of a Cumulus parablock
+///
+/// Rows which can't be decoded or fetched are shown as "-", `validation_data_inherent` is an error
+/// when the inherent couldn't be decoded.
+async fn add_parachain_rows(
+    block_table: &mut Table,
     rpc: &NodeRPC,
-    block_number: u32,
+    relay_rpc: Option<&NodeRPC>,
+    validation_data_inherent: Result<Option<&ValidationDataInherent>, &Error>,
     block_hash: &BlockHashHex,
     metadata: AnyRuntimeMetadata<'_>,
     runtime_version: &RuntimeVersion,
-) -> Result<(), Error> {
+) {
+    let validation_data_inherent = match validation_data_inherent {
+        Ok(Some(validation_data_inherent)) => Some(validation_data_inherent),
+        Ok(None) => return,
+        Err(_) => None,
+    };
+    let validation_data = validation_data_inherent
+        .map(|validation_data_inherent| &validation_data_inherent.validation_data);
+
+    let para_id = match pallets::parachain_info::fetch_parachain_id(
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await
+    {
+        Ok(para_id) => Some(para_id),
+        Err(error) => {
+            warn!(%error, "parachain id unavailable");
+            None
+        }
+    };
+
+    let downward_messages = validation_data_inherent.map(|validation_data_inherent| {
+        let downward_messages = &validation_data_inherent.downward_messages;
+        let downward_messages_size: usize = downward_messages
+            .iter()
+            .map(|message| message.msg.len())
+            .sum();
+        format!(
+            "{} ({downward_messages_size} bytes)",
+            downward_messages.len()
+        )
+    });
+
+    let horizontal_messages = validation_data_inherent.map(|validation_data_inherent| {
+        let mut horizontal_message_counts = BTreeMap::new();
+        for (sender, _) in &validation_data_inherent.horizontal_messages {
+            *horizontal_message_counts.entry(*sender).or_insert(0) += 1;
+        }
+        if horizontal_message_counts.is_empty() {
+            String::from("0")
+        } else {
+            let senders = horizontal_message_counts
+                .iter()
+                .map(|(sender, count)| format!("{count} from {sender}"))
+                .collect::<Vec<_>>();
+            format!(
+                "{} ({})",
+                validation_data_inherent.horizontal_messages.len(),
+                senders.join(", ")
+            )
+        }
+    });
+
+    let inclusion_block = match (relay_rpc, para_id, validation_data) {
+        (Some(relay_rpc), Some(para_id), Some(validation_data)) => {
+            match parachain::find_inclusion_block(
+                relay_rpc,
+                para_id,
+                block_hash,
+                validation_data.relay_parent_number,
+            )
+            .await
+            {
+                Ok(Some(inclusion_block)) => {
+                    format!("{} ({})", inclusion_block.number, inclusion_block.hash)
+                }
+                Ok(None) => String::from("Not found"),
+                Err(error) => {
+                    warn!(%error, "relay chain inclusion unavailable");
+                    String::from("-")
+                }
+            }
+        }
+        _ => String::from("-"),
+    };
+
+    let unavailable = || String::from("-");
+    block_table.add_row(row![
+        "Para ID",
+        para_id.map_or_else(unavailable, |para_id| para_id.to_string())
+    ]);
+    block_table.add_row(row![
+        "Relay Parent",
+        validation_data.map_or_else(unavailable, |validation_data| validation_data
+            .relay_parent_number
+            .to_string())
+    ]);
+    block_table.add_row(row![
+        "Relay Storage Root",
+        validation_data.map_or_else(unavailable, |validation_data| format!(
+            "{:?}",
+            validation_data.relay_parent_storage_root
+        ))
+    ]);
+    block_table.add_row(row![
+        "Downward Messages",
+        downward_messages.unwrap_or_else(unavailable)
+    ]);
+    block_table.add_row(row![
+        "Horizontal Messages",
+        horizontal_messages.unwrap_or_else(unavailable)
+    ]);
+    block_table.add_row(row!["Included In", inclusion_block]);
+}
+
+fn print_digest_logs(digest_logs: &[DigestItem]) {
+    let mut digest_table = Table::new();
+    digest_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    digest_table.set_titles(row!["Index", "Kind", "Engine", "Details"]);
+
+    digest_logs
+        .iter()
+        .enumerate()
+        .for_each(|(i, log)| match digest::describe_digest_log(log) {
+            Ok(description) => {
+                digest_table.add_row(row![
+                    i,
+                    description.kind,
+                    description.engine.unwrap_or(String::from("-")),
+                    description.details
+                ]);
+            }
+            Err(error) => {
+                warn!(%error);
+                digest_table.add_row(row![
+                    i,
+                    "-",
+                    "-",
+                    format!("0x{}", hex::encode(log.encode()))
+                ]);
+            }
+        });
+
+    println!("Digest");
+    digest_table.printstd();
+}
+
+/// Events of a block, `event_calls` holds the nested call of the extrinsic emitting an event
+fn print_events(
+    block_number: u32,
+    events: &[EventRecord],
+    event_calls: &HashMap<usize, Vec<usize>>,
+    abi_registry: &AbiRegistry,
+) {
     let mut events_table = Table::new();
-    let events = pallets::system::fetch_events(rpc, block_hash, metadata, runtime_version).await?;
     events_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
 
     events.iter().enumerate().for_each(|(i, event)| {
         let event_id = format!("{block_number}-{i}");
         let (ty, ext_id) = match event.phase {
-            Phase::ApplyExtrinsic(ext_idx) => {
-                ("Extrinsic", Some(format!("{block_number}-{ext_idx}")))
-            }
+            Phase::ApplyExtrinsic(ext_idx) => match event_calls.get(&i) {
+                Some(path) => (
+                    "Extrinsic",
+                    Some(format!(
+                        "{block_number}-{ext_idx} (call {})",
+                        path.iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(".")
+                    )),
+                ),
+                None => ("Extrinsic", Some(format!("{block_number}-{ext_idx}"))),
+            },
             Phase::Initialization => ("Initialization", None),
             Phase::Finalization => ("Finalization", None),
         };
         let action = format!("{} ({})", event.event.name, event.event.action);
+        let details = match evm::decoder::decode_log_event(&event.event) {
+            Some(Ok(log)) => format!(
+                "{}: {}",
+                checksum_address(&log.address),
+                format_decoded_abi_item(abi_registry.decode_log(&log))
+                    .unwrap_or(format!("0x{}", hex::encode(&log.data)))
+            ),
+            Some(Err(error)) => {
+                warn!(%error);
+                String::new()
+            }
+            None => String::new(),
+        };
         events_table.add_row(row![
             event_id,
             ext_id.unwrap_or(String::from("-")),
             action,
-            ty
+            ty,
+            details
         ]);
         debug!(?event);
     });
 
     println!("Events");
     events_table.printstd();
+}
+
+/// Candidates of the `ParaInherent.enter` inherent and the parachains backed or included by a relay
+/// chain block
+///
+/// The candidate events are still shown when the inherent can't be decoded.
+fn print_parachain_candidates(para_inherent: Option<&ParaInherentData>, events: &[EventRecord]) {
+    if let Some(para_inherent) = para_inherent {
+        let backed_para_ids = para_inherent
+            .backed_candidates
+            .iter()
+            .map(|backed_candidate| backed_candidate.candidate.descriptor.para_id.to_string())
+            .collect::<Vec<_>>();
+
+        let mut inherent_table = table![
+            [
+                "Backed Candidates",
+                format!(
+                    "{} ({})",
+                    backed_para_ids.len(),
+                    if backed_para_ids.is_empty() {
+                        String::from("-")
+                    } else {
+                        backed_para_ids.join(", ")
+                    }
+                )
+            ],
+            ["Bitfields", para_inherent.bitfield_count],
+            ["Disputes", para_inherent.dispute_count]
+        ];
+        inherent_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+        println!("Para Inherent");
+        inherent_table.printstd();
+    }
+
+    let mut candidates_table = Table::new();
+    candidates_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    candidates_table.set_titles(row![
+        "Para ID",
+        "Status",
+        "Head Data Hash",
+        "Core Index",
+        "Group Index",
+        "Relay Parent"
+    ]);
+
+    for record in events {
+        match para_inclusion::decoder::decode_candidate_event(&record.event) {
+            Some(Ok(candidate)) => {
+                let descriptor = &candidate.receipt.descriptor;
+                candidates_table.add_row(row![
+                    descriptor.para_id,
+                    candidate.status,
+                    format!("{:?}", descriptor.para_head),
+                    candidate.core_index,
+                    candidate.group_index,
+                    format!("{:?}", descriptor.relay_parent)
+                ]);
+            }
+            Some(Err(error)) => warn!(%error),
+            None => {}
+        }
+    }
+
+    println!("Parachain Candidates");
+    candidates_table.printstd();
+}
+
+/// Render an argument of an `XcmPallet` / `PolkadotXcm` call, `message` as an instruction list
+fn format_xcm_call_arg(name: &str, value: &scale_value::Value<u32>) -> String {
+    if name == "message"
+        && let Ok(program) = XcmProgram::from_versioned(value)
+    {
+        format!(
+            "{name} ({}):\n  {}",
+            program.version.unwrap_or_default(),
+            program.instructions.join("\n  ")
+        )
+    } else {
+        format!("{name}: {}", format_value(value))
+    }
+}
+
+/// XCM calls, inbound messages of the parachain inherents and XCM events of a block
+///
+/// Inbound messages are linked to the `MessageQueue.Processed` events executing them by message ID.
+/// Upward messages of backed candidates are only enqueued once the candidate is included in a later
+/// relay chain block, their outcome is shown as pending.
+fn print_xcm(
+    block_number: u32,
+    extrinsics: &[ExtrinsicBytes],
+    events: &[EventRecord],
+    validation_data_inherent: Option<&ValidationDataInherent>,
+    para_inherent: Option<&ParaInherentData>,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) {
+    let xcm_events = events
+        .iter()
+        .enumerate()
+        .filter_map(|(i, record)| match xcm::decode_xcm_event(&record.event) {
+            Some(Ok(xcm_event)) => Some((i, record, xcm_event)),
+            Some(Err(error)) => {
+                warn!(%error);
+                None
+            }
+            None => None,
+        })
+        .collect::<Vec<_>>();
+    debug!(?xcm_events);
+
+    let outcomes = xcm_events
+        .iter()
+        .filter_map(|(_, _, xcm_event)| match xcm_event {
+            XcmEvent::Processed { id, success, .. } => Some((
+                *id,
+                String::from(if *success { "Success" } else { "Failed" }),
+            )),
+            XcmEvent::ProcessingFailed { id, error, .. } => Some((*id, format!("Failed: {error}"))),
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut calls_table = Table::new();
+    calls_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    calls_table.set_titles(row!["Extrinsic ID", "Call", "Arguments"]);
+
+    for (i, ExtrinsicBytes(ext)) in extrinsics.iter().enumerate() {
+        let extrinsic = match decode_extrinsic_any(ext, metadata, runtime_version.spec_version) {
+            Ok(extrinsic) => extrinsic,
+            Err(error) => {
+                warn!(%error);
+                continue;
+            }
+        };
+        if !xcm::event::XCM_PALLETS.contains(&extrinsic.pallet_name()) {
+            continue;
+        }
+
+        let arguments = match extrinsic.call_args(ext, metadata) {
+            Ok(args) => args
+                .iter()
+                .map(|(name, value)| format_xcm_call_arg(name, value))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Err(error) => {
+                warn!(%error);
+                String::new()
+            }
+        };
+        calls_table.add_row(row![
+            format!("{block_number}-{i}"),
+            format!("{} ({})", extrinsic.pallet_name(), extrinsic.call_name()),
+            arguments
+        ]);
+    }
+
+    println!("XCM Calls");
+    calls_table.printstd();
+
+    let mut messages_table = Table::new();
+    messages_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    messages_table.set_titles(row![
+        "Kind",
+        "Message ID",
+        "Version",
+        "Instructions",
+        "Outcome"
+    ]);
+
+    let mut add_message =
+        |kind: String, encoded: &[u8], program: Result<XcmProgram, XcmError>, pending: bool| {
+            match program {
+                Ok(program) => {
+                    let message_id = program.message_id(encoded);
+                    let outcome = if pending {
+                        "Pending inclusion"
+                    } else {
+                        outcomes.get(&message_id).map(String::as_str).unwrap_or("-")
+                    };
+                    messages_table.add_row(row![
+                        kind,
+                        format!("0x{}", hex::encode(message_id)),
+                        program.version.unwrap_or_default(),
+                        program.instructions.join("\n"),
+                        outcome
+                    ]);
+                }
+                Err(error) => warn!(%error, kind),
+            }
+        };
+
+    if let Some(validation_data_inherent) = validation_data_inherent {
+        for message in &validation_data_inherent.downward_messages {
+            add_message(
+                String::from("Downward"),
+                &message.msg,
+                xcm::decode_versioned_xcm(&message.msg, metadata),
+                false,
+            );
+        }
+        for (sender, message) in &validation_data_inherent.horizontal_messages {
+            match xcm::decode_hrmp_message(&message.data, metadata) {
+                Ok(programs) => programs.into_iter().for_each(|(encoded, program)| {
+                    add_message(
+                        format!("Horizontal from {sender}"),
+                        encoded,
+                        Ok(program),
+                        false,
+                    )
+                }),
+                Err(error) => warn!(%error),
+            }
+        }
+    }
+
+    if let Some(para_inherent) = para_inherent {
+        for backed_candidate in &para_inherent.backed_candidates {
+            let candidate = &backed_candidate.candidate;
+            for message in &candidate.commitments.upward_messages.0 {
+                add_message(
+                    format!("Upward from {} (backed)", candidate.descriptor.para_id),
+                    message,
+                    xcm::decode_versioned_xcm(message, metadata),
+                    true,
+                );
+            }
+        }
+    }
+
+    println!("XCM Messages");
+    messages_table.printstd();
+
+    let mut events_table = Table::new();
+    events_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    events_table.set_titles(row!["Event ID", "Event", "Message ID", "Details"]);
+
+    for (i, record, xcm_event) in &xcm_events {
+        let message_id = xcm_event
+            .message_id()
+            .map(|message_id| format!("0x{}", hex::encode(message_id)))
+            .unwrap_or(String::from("-"));
+        let details = match xcm_event {
+            XcmEvent::Sent {
+                destination,
+                program,
+                ..
+            } => format!("To {destination}:\n{}", program.instructions.join("\n")),
+            XcmEvent::Attempted { outcome } => outcome.clone(),
+            XcmEvent::XcmpMessageSent { .. } => String::new(),
+            XcmEvent::Processed {
+                origin, success, ..
+            } => format!(
+                "From {origin}: {}",
+                if *success { "Success" } else { "Failed" }
+            ),
+            XcmEvent::ProcessingFailed { origin, error, .. } => {
+                format!("From {origin}: Failed: {error}")
+            }
+        };
+        events_table.add_row(row![
+            format!("{block_number}-{i}"),
+            format!("{} ({})", record.event.name, record.event.action),
+            message_id,
+            details
+        ]);
+    }
+
+    println!("XCM Events");
+    events_table.printstd();
+}
+
+async fn diff_runtime(rpc: &NodeRPC, from_block: u32, to_block: u32) -> Result<(), Error> {
+    let from_block_hash = rpc
+        .chain_get_block_hash(&BlockNumberHex::from(from_block))
+        .await?;
+    let to_block_hash = rpc
+        .chain_get_block_hash(&BlockNumberHex::from(to_block))
+        .await?;
+
+    let from_runtime_version = rpc.state_get_runtime_version(&from_block_hash).await?;
+    let to_runtime_version = rpc.state_get_runtime_version(&to_block_hash).await?;
+
+    let from_metadata = fetch::fetch_metadata(rpc, &from_block_hash).await?;
+    let to_metadata = fetch::fetch_metadata(rpc, &to_block_hash).await?;
+
+    let diff = runtime_upgrade::diff_metadata(
+        AnyRuntimeMetadata(&from_metadata),
+        AnyRuntimeMetadata(&to_metadata),
+    )?;
+
+    let mut runtime_table = table![
+        ["", "From", "To"],
+        ["Block", from_block, to_block],
+        ["Hash", from_block_hash, to_block_hash],
+        [
+            "Spec Version",
+            from_runtime_version.spec_version,
+            to_runtime_version.spec_version
+        ],
+        [
+            "Metadata Version",
+            from_metadata.version(),
+            to_metadata.version()
+        ]
+    ];
+    runtime_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    println!("Runtime");
+    runtime_table.printstd();
+
+    if diff.is_empty() {
+        println!("No metadata changes");
+        return Ok(());
+    }
+
+    let mut pallets_table = Table::new();
+    pallets_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    diff.pallets_added.iter().for_each(|pallet_name| {
+        pallets_table.add_row(row![pallet_name, "added"]);
+    });
+    diff.pallets_removed.iter().for_each(|pallet_name| {
+        pallets_table.add_row(row![pallet_name, "removed"]);
+    });
+
+    println!("Pallets");
+    pallets_table.printstd();
+
+    let mut changes_table = Table::new();
+    changes_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    diff.item_changes.iter().for_each(|change| {
+        let status = match (&change.old, &change.new) {
+            (None, Some(_)) => "added",
+            (Some(_), None) => "removed",
+            _ => "changed",
+        };
+        changes_table.add_row(row![
+            change.pallet_name,
+            change.kind,
+            change.item_name,
+            status,
+            change.old.as_deref().unwrap_or("-"),
+            change.new.as_deref().unwrap_or("-")
+        ]);
+    });
+
+    println!("Changes");
+    changes_table.printstd();
+
+    Ok(())
+}
+
+async fn runtime_upgrades(rpc: &NodeRPC, from_block: u32, to_block: u32) -> Result<(), Error> {
+    let upgrades = runtime_upgrade::find_runtime_upgrades(rpc, from_block, to_block).await?;
+
+    let mut upgrades_table = Table::new();
+    upgrades_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+    upgrades.iter().for_each(|upgrade| {
+        let (event_id, ext_id) = match &upgrade.code_updated_event {
+            Some((i, event_record)) => {
+                let event_id = format!("{}-{i}", upgrade.block_number);
+                let ext_id = match event_record.phase {
+                    Phase::ApplyExtrinsic(ext_idx) => format!("{}-{ext_idx}", upgrade.block_number),
+                    _ => String::from("-"),
+                };
+                (event_id, ext_id)
+            }
+            None => (String::from("-"), String::from("-")),
+        };
+        upgrades_table.add_row(row![
+            upgrade.block_number,
+            upgrade.block_hash,
+            upgrade.old_spec_version,
+            upgrade.new_spec_version,
+            event_id,
+            ext_id
+        ]);
+        debug!(?upgrade);
+    });
+
+    println!("Runtime Upgrades");
+    upgrades_table.printstd();
+
+    Ok(())
+}
+
+async fn print_constants(
+    rpc: &NodeRPC,
+    block_number: u32,
+    pallet_name: Option<&str>,
+) -> Result<(), Error> {
+    let block_hash = rpc
+        .chain_get_block_hash(&BlockNumberHex::from(block_number))
+        .await?;
+    let runtime_version = rpc.state_get_runtime_version(&block_hash).await?;
+    let metadata = fetch::fetch_metadata(rpc, &block_hash).await?;
+    let metadata = AnyRuntimeMetadata(&metadata);
+    let type_registry = metadata.type_registry();
+
+    let pallets = match pallet_name {
+        Some(pallet_name) => vec![metadata.pallet_metadata(pallet_name)?],
+        None => metadata.pallets()?,
+    };
+
+    let mut constants_table = Table::new();
+    constants_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+    for pallet in pallets {
+        let pallet_name = pallet.name()?;
+
+        for constant in pallet.constants()? {
+            let value = match decode_pallet_constant_any(
+                pallet_name,
+                &constant,
+                metadata,
+                runtime_version.spec_version,
+            ) {
+                Ok(AnyConstantValue::Legacy(value)) => value.to_string(),
+                Ok(AnyConstantValue::Modern(value)) => value.to_string(),
+                Err(error) => {
+                    warn!(%error);
+                    format!("0x{}", hex::encode(constant.value()?))
+                }
+            };
+
+            constants_table.add_row(row![
+                pallet_name,
+                constant.name()?,
+                constant.type_as_str(type_registry)?,
+                value
+            ]);
+        }
+    }
+
+    println!("Constants");
+    constants_table.printstd();
+
+    Ok(())
+}
+
+async fn print_evm_account(
+    rpc: &NodeRPC,
+    block_number: u32,
+    address: &H160,
+    slots: &[H256],
+) -> Result<(), Error> {
+    let block_hash = rpc
+        .chain_get_block_hash(&BlockNumberHex::from(block_number))
+        .await?;
+    let runtime_version = rpc.state_get_runtime_version(&block_hash).await?;
+    let metadata = fetch::fetch_metadata(rpc, &block_hash).await?;
+    let metadata = AnyRuntimeMetadata(&metadata);
+
+    let account = evm::fetch_account(rpc, address, &block_hash, metadata, &runtime_version).await?;
+    let code =
+        evm::fetch_account_code(rpc, address, &block_hash, metadata, &runtime_version).await?;
+
+    let mut account_table = table![
+        ["Address", checksum_address(address)],
+        ["Free Balance", account.data.free],
+        ["Reserved Balance", account.data.reserved],
+        ["Frozen Balance", account.data.frozen],
+        ["Nonce", account.nonce],
+        ["Code Size", code.len()],
+        ["Code Hash", format!("{:?}", evm::code_hash(&code))]
+    ];
+    account_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    println!("EVM Account");
+    account_table.printstd();
+
+    if !slots.is_empty() {
+        let mut storage_table = Table::new();
+        storage_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+        storage_table.set_titles(row!["Slot", "Value"]);
+
+        for slot in slots {
+            let value = evm::fetch_account_storage(
+                rpc,
+                address,
+                slot,
+                &block_hash,
+                metadata,
+                &runtime_version,
+            )
+            .await?;
+            storage_table.add_row(row![format!("{slot:?}"), format!("{value:?}")]);
+        }
+
+        println!("EVM Storage");
+        storage_table.printstd();
+    }
 
     Ok(())
 }
+
+/// Verify the GRANDPA justification stored with the block, or the finality proof of the node when
+/// the block has none
+async fn print_finality(rpc: &NodeRPC, block_number: u32) -> Result<(), Error> {
+    let block_hash = rpc
+        .chain_get_block_hash(&BlockNumberHex::from(block_number))
+        .await?;
+    let signed_block = rpc.chain_get_block(&block_hash).await?;
+
+    let block_justification = signed_block
+        .justifications
+        .into_iter()
+        .flatten()
+        .find(|Justification(engine_id, _)| *engine_id == GRANDPA_ENGINE_ID)
+        .map(|Justification(_, justification)| justification);
+
+    let (source, justification, finalizes_block) = match block_justification {
+        Some(justification) => ("chain_getBlock", justification, true),
+        None => {
+            let FinalityProofBytes(proof_bytes) =
+                rpc.grandpa_prove_finality(block_number)
+                    .await?
+                    .ok_or(Error::JustificationNotFound(block_number))?;
+            let finality_proof = FinalityProof::decode(&mut &proof_bytes[..])
+                .map_err(Error::ParsingJustificationFailed)?;
+            let finalizes_block = finality_proof.proves(&block_hash);
+            (
+                "grandpa_proveFinality",
+                finality_proof.justification,
+                finalizes_block,
+            )
+        }
+    };
+
+    let justification = GrandpaJustification::decode(&mut &justification[..])
+        .map_err(Error::ParsingJustificationFailed)?;
+    debug!(?justification);
+
+    // A block enacting a new authority set is still finalized by the previous set, so the set is
+    // read from the parent state of the justified block
+    let target_hash = BlockHashHex::from(justification.commit.target_hash);
+    let target_header = rpc.chain_get_header(target_hash.clone()).await?;
+    if rpc.storage_verifier().is_some() {
+        verify::verify_header(&target_header, &target_hash)?;
+    }
+    let set_block_hash = target_header.parent_hash;
+    let runtime_version = rpc.state_get_runtime_version(&set_block_hash).await?;
+    let metadata = fetch::fetch_metadata(rpc, &set_block_hash).await?;
+    let metadata = AnyRuntimeMetadata(&metadata);
+
+    let set_id =
+        grandpa::fetch_current_set_id(rpc, &set_block_hash, metadata, &runtime_version).await?;
+    let authorities =
+        grandpa::fetch_authorities(rpc, &set_block_hash, metadata, &runtime_version).await?;
+    let verification = justification.verify(set_id, &authorities);
+    debug!(?verification);
+
+    let mut justification_table = table![
+        ["Source", source],
+        ["Round", justification.round],
+        ["Set ID", set_id],
+        ["Target Number", justification.commit.target_number],
+        ["Target Hash", target_hash],
+        [
+            "Finalizes Block",
+            if finalizes_block { "Yes" } else { "No" }
+        ],
+        [
+            "Signed Weight",
+            format!(
+                "{} / {} (threshold {})",
+                verification.signed_weight,
+                verification.total_weight,
+                verification.threshold()
+            )
+        ],
+        [
+            "Verified",
+            if verification.is_valid() { "Yes" } else { "No" }
+        ]
+    ];
+    justification_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    println!("GRANDPA Justification");
+    justification_table.printstd();
+
+    let address_format = fetch::ss58_address_format(rpc).await?;
+    let mut authorities_table = Table::new();
+    authorities_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    authorities_table.set_titles(row!["Authority", "Weight", "Precommit", "Precommit Target"]);
+
+    for GrandpaAuthority(public_key, weight) in &authorities {
+        let (status, target_number) = match verification.precommit_of(public_key) {
+            Some(precommit) => (
+                precommit.status.to_string(),
+                precommit.target_number.to_string(),
+            ),
+            None => (String::from("missing"), String::from("-")),
+        };
+        authorities_table.add_row(row![
+            ss58::encode(public_key, address_format),
+            weight,
+            status,
+            target_number
+        ]);
+    }
+    for precommit in verification
+        .precommits
+        .iter()
+        .filter(|precommit| precommit.status == PrecommitStatus::UnknownAuthority)
+    {
+        authorities_table.add_row(row![
+            ss58::encode(&precommit.authority, address_format),
+            "-",
+            precommit.status,
+            precommit.target_number
+        ]);
+    }
+
+    println!("GRANDPA Authorities");
+    authorities_table.printstd();
+
+    Ok(())
+}
+
+async fn check_runtime(rpc: &NodeRPC, block_number: u32) -> Result<(), Error> {
+    let block_hash = rpc
+        .chain_get_block_hash(&BlockNumberHex::from(block_number))
+        .await?;
+    let metadata = fetch::fetch_metadata(rpc, &block_hash).await?;
+
+    let compatibility_report =
+        check_compatibility(pallets::STORAGE_SCHEMAS, AnyRuntimeMetadata(&metadata))?;
+    print_compatibility_report(&compatibility_report);
+
+    if !compatibility_report.is_compatible() {
+        Err(Error::IncompatibleRuntime {
+            incompatible_count: compatibility_report.incompatible_count(),
+        })?;
+    }
+
+    Ok(())
+}
+
+fn print_compatibility_report(compatibility_report: &CompatibilityReport) {
+    let mut compatibility_table = Table::new();
+    compatibility_table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+    compatibility_report.entries.iter().for_each(|entry| {
+        let (status, details) = match (&entry.compatibility, entry.requirement) {
+            (Compatibility::Compatible { storage_types }, _) => {
+                ("compatible", storage_types.clone())
+            }
+            (Compatibility::PalletNotPresent, _) => ("pallet not present", String::from("-")),
+            (Compatibility::Incompatible(error), Requirement::Required) => {
+                ("incompatible", error.to_string())
+            }
+            (Compatibility::Incompatible(error), Requirement::Optional) => {
+                ("optional, skipped", error.to_string())
+            }
+        };
+        compatibility_table.add_row(row![
+            entry.pallet_name,
+            entry.storage_entry_name,
+            status,
+            details
+        ]);
+    });
+
+    println!("Compatibility");
+    compatibility_table.printstd();
+}
@@ -4,16 +4,20 @@ use jsonrpsee::{
     ws_client::{WsClient, WsClientBuilder},
 };
 
-use crate::node_rpc::models::{SyncState, SystemProperties};
+use crate::{
+    node_rpc::models::{ReadProof, SyncState, SystemProperties},
+    verify::StorageVerifier,
+};
 
 pub use super::error::NodeRPCError;
 use super::models::{
-    BlockHashHex, BlockHeader, BlockNumberHex, ChainMetadataBytes, RuntimeVersion, SignedBlock,
-    StorageKeyHex, StorageValueBytes,
+    BlockHashHex, BlockHeader, BlockNumberHex, ChainMetadataBytes, FinalityProofBytes,
+    RuntimeVersion, SignedBlock, StorageKeyHex, StorageValueBytes,
 };
 
 pub struct NodeRPC {
     client: WsClient,
+    storage_verifier: Option<StorageVerifier>,
 }
 
 #[allow(dead_code)]
@@ -24,7 +28,20 @@ impl NodeRPC {
             .await
             .map_err(NodeRPCError::FailedConnection)?;
 
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            storage_verifier: None,
+        })
+    }
+
+    /// Read storage values from proofs checked against the block state root
+    pub fn with_storage_verification(mut self) -> Self {
+        self.storage_verifier = Some(StorageVerifier::default());
+        self
+    }
+
+    pub fn storage_verifier(&self) -> Option<&StorageVerifier> {
+        self.storage_verifier.as_ref()
     }
 
     async fn request<P, R>(&self, method: &str, params: P) -> Result<R, NodeRPCError>
@@ -69,6 +86,20 @@ impl NodeRPC {
             .await
     }
 
+    /// Header of a block, `None` if the node doesn't know the hash
+    pub async fn chain_get_header_optional(
+        &self,
+        header_hash: BlockHashHex,
+    ) -> Result<Option<BlockHeader>, NodeRPCError> {
+        self.request("chain_getHeader", rpc_params![header_hash])
+            .await
+    }
+
+    /// Get the header of the best block
+    pub async fn chain_get_best_header(&self) -> Result<BlockHeader, NodeRPCError> {
+        self.request("chain_getHeader", rpc_params![]).await
+    }
+
     pub async fn chain_get_finalized_head(&self) -> Result<BlockHashHex, NodeRPCError> {
         self.request("chain_getFinalizedHead", rpc_params![]).await
     }
@@ -81,6 +112,15 @@ impl NodeRPC {
             .await
     }
 
+    /// Hash of the best chain block at a height, `None` above the best block
+    pub async fn chain_get_block_hash_optional(
+        &self,
+        block_number: &BlockNumberHex,
+    ) -> Result<Option<BlockHashHex>, NodeRPCError> {
+        self.request("chain_getBlockHash", rpc_params![block_number])
+            .await
+    }
+
     pub async fn chain_get_block(
         &self,
         block_hash: &BlockHashHex,
@@ -89,6 +129,16 @@ impl NodeRPC {
             .await
     }
 
+    /// Get a GRANDPA finality proof of the block, whose justification may target a descendant
+    /// block. `None` if the block isn't finalized yet
+    pub async fn grandpa_prove_finality(
+        &self,
+        block_number: u32,
+    ) -> Result<Option<FinalityProofBytes>, NodeRPCError> {
+        self.request("grandpa_proveFinality", rpc_params![block_number])
+            .await
+    }
+
     pub async fn state_get_metadata(
         &self,
         block_hash: &BlockHashHex,
@@ -127,4 +177,13 @@ impl NodeRPC {
         self.request("state_getStorage", rpc_params![storage_key, block_hash])
             .await
     }
+
+    pub async fn state_get_read_proof(
+        &self,
+        storage_keys: &[StorageKeyHex],
+        block_hash: &BlockHashHex,
+    ) -> Result<ReadProof, NodeRPCError> {
+        self.request("state_getReadProof", rpc_params![storage_keys, block_hash])
+            .await
+    }
 }
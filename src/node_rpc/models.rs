@@ -6,9 +6,25 @@ use serde::de::Error;
 use serde::{Deserialize, Deserializer, Serialize};
 
 /// Block hash as hexadecimal string
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Display)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
 pub struct BlockHashHex(pub String);
 
+impl From<[u8; 32]> for BlockHashHex {
+    fn from(value: [u8; 32]) -> Self {
+        Self(format!("0x{}", hex::encode(value)))
+    }
+}
+
+impl BlockHashHex {
+    /// The parent hash of the genesis block
+    pub fn is_zero(&self) -> bool {
+        self.0
+            .trim_start_matches("0x")
+            .bytes()
+            .all(|byte| byte == b'0')
+    }
+}
+
 /// Block number as hexadecimal string
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Display)]
 pub struct BlockNumberHex(pub String);
@@ -19,6 +35,14 @@ impl From<u32> for BlockNumberHex {
     }
 }
 
+impl TryFrom<&BlockNumberHex> for u32 {
+    type Error = std::num::ParseIntError;
+
+    fn try_from(value: &BlockNumberHex) -> Result<Self, Self::Error> {
+        u32::from_str_radix(value.0.trim_start_matches("0x"), 16)
+    }
+}
+
 /// Block number as hexadecimal string
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Display)]
 pub struct StorageKeyHex(pub String);
@@ -51,8 +75,13 @@ pub struct LogBytes(#[serde(deserialize_with = "deserialize_hex")] pub Vec<u8>);
 #[derive(Debug, Clone, Deserialize)]
 pub struct SignedBlock {
     pub block: Block,
+    pub justifications: Option<Vec<Justification>>,
 }
 
+/// Consensus engine id and encoded justification, both serialized as byte arrays
+#[derive(Debug, Clone, Deserialize)]
+pub struct Justification(pub [u8; 4], pub Vec<u8>);
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Block {
     pub header: BlockHeader,
@@ -74,6 +103,9 @@ pub struct RuntimeVersion {
     pub impl_name: String,
     pub apis: Vec<JsonValue>,
     pub spec_version: u64,
+    /// Named `stateVersion` before runtimes used it for the extrinsics root version
+    pub system_version: Option<u8>,
+    pub state_version: Option<u8>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -92,6 +124,22 @@ pub struct SystemProperties {
     pub token_symbol: String,
 }
 
+/// Trie nodes proving storage values against the state root of `at`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReadProof {
+    pub at: BlockHashHex,
+    pub proof: Vec<TrieNodeBytes>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(transparent)]
+pub struct TrieNodeBytes(#[serde(deserialize_with = "deserialize_hex")] pub Vec<u8>);
+
+/// SCALE encoded GRANDPA finality proof
+#[derive(Debug, Clone, Deserialize)]
+#[serde(transparent)]
+pub struct FinalityProofBytes(#[serde(deserialize_with = "deserialize_hex")] pub Vec<u8>);
+
 /// Chain Metadata as a bytestring
 #[derive(Debug, Clone, Deserialize)]
 #[serde(transparent)]
@@ -0,0 +1,6 @@
+use crate::{decoder::value_decoder::ValueDecoder, pallets::session::decoder::decode_public_key};
+
+/// AURA authority sr25519 or ed25519 public key
+#[derive(Debug, Clone, Copy, ValueDecoder)]
+#[value_decoder(transparent)]
+pub struct AuraAuthority(#[value_decoder(with = "decode_public_key")] pub [u8; 32]);
@@ -0,0 +1,29 @@
+use parity_scale_codec::Decode;
+use sp_runtime::{ConsensusEngineId, DigestItem};
+
+pub const AURA_ENGINE_ID: ConsensusEngineId = *b"AURA";
+
+/// Session key type of AURA authorities
+pub const AURA_KEY_TYPE_ID: [u8; 4] = *b"aura";
+
+/// `sp_consensus_aura::ConsensusLog`, AURA authorities are sr25519 or ed25519 public keys
+#[derive(Debug, Decode)]
+pub enum AuraConsensusLog {
+    #[codec(index = 1)]
+    AuthoritiesChange(Vec<[u8; 32]>),
+    #[codec(index = 2)]
+    OnDisabled(u32),
+}
+
+/// Find and decode the slot of the AURA pre-runtime digest
+pub fn find_slot(digest_logs: &[DigestItem]) -> Result<Option<u64>, parity_scale_codec::Error> {
+    digest_logs
+        .iter()
+        .find_map(|log| match log {
+            DigestItem::PreRuntime(engine_id, bytes) if *engine_id == AURA_ENGINE_ID => {
+                Some(u64::decode(&mut &bytes[..]))
+            }
+            _ => None,
+        })
+        .transpose()
+}
@@ -0,0 +1,85 @@
+use crate::{
+    decoder::metadata::AnyRuntimeMetadata,
+    error::Error,
+    fetch::fetch,
+    node_rpc::{
+        client::NodeRPC,
+        models::{BlockHashHex, RuntimeVersion},
+    },
+    pallets::{
+        aura::decoder::AuraAuthority,
+        schema::{StorageSchema, StorageTypes},
+    },
+};
+
+pub const PALLET_NAME: &str = "Aura";
+
+pub const AUTHORITIES: StorageSchema<()> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "Authorities",
+    shapes: &[
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "Vec<T::AuthorityId>",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "BoundedVec<T::AuthorityId, T::MaxAuthorities>",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "Vec<sp_consensus_aura::sr25519::app_sr25519::Public>",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "frame_support::storage::bounded_vec::BoundedVec<sp_consensus_aura::sr25519::app_sr25519::Public>",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "bounded_collections::bounded_vec::BoundedVec<sp_consensus_aura::sr25519::app_sr25519::Public>",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "bounded_collections::bounded_vec::BoundedVec<sp_consensus_aura::ed25519::app_ed25519::Public>",
+            },
+        ),
+    ],
+};
+
+/// Authorities taking turns to author the AURA slots
+pub async fn fetch_authorities(
+    rpc: &NodeRPC,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<Vec<AuraAuthority>, Error> {
+    AUTHORITIES.resolve(metadata)?;
+
+    fetch(
+        PALLET_NAME,
+        AUTHORITIES.storage_entry_name,
+        (),
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await
+}
@@ -0,0 +1,5 @@
+pub mod decoder;
+pub mod digest;
+pub mod fetch;
+
+pub use fetch::fetch_authorities;
@@ -0,0 +1,32 @@
+use derive_more::Display;
+
+use crate::{decoder::value_decoder::ValueDecoder, pallets::session::decoder::decode_public_key};
+
+/// BABE authority sr25519 public key and its weight
+#[allow(dead_code)]
+#[derive(Debug, Clone, ValueDecoder)]
+pub struct BabeAuthority(
+    #[value_decoder(with = "decode_public_key")] pub [u8; 32],
+    pub u64,
+);
+
+#[derive(Debug, Clone, Copy, Display, ValueDecoder)]
+pub enum AllowedSlots {
+    #[display("primary")]
+    #[value_decoder(rename = "PrimarySlots")]
+    Primary,
+    #[display("primary and secondary plain")]
+    #[value_decoder(rename = "PrimaryAndSecondaryPlainSlots")]
+    PrimaryAndSecondaryPlain,
+    #[display("primary and secondary VRF")]
+    #[value_decoder(rename = "PrimaryAndSecondaryVRFSlots")]
+    PrimaryAndSecondaryVRF,
+}
+
+/// `sp_consensus_babe::BabeEpochConfiguration`
+#[derive(Debug, Clone, Display, ValueDecoder)]
+#[display("c = {}/{}, {allowed_slots} slots", c.0, c.1)]
+pub struct BabeEpochConfiguration {
+    pub c: (u64, u64),
+    pub allowed_slots: AllowedSlots,
+}
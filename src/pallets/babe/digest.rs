@@ -0,0 +1,62 @@
+use derive_more::Display;
+use parity_scale_codec::{Decode, Encode};
+use sc_consensus_babe::{BABE_ENGINE_ID, PreDigest};
+use sp_runtime::DigestItem;
+
+/// Session key type of BABE authorities
+pub const BABE_KEY_TYPE_ID: [u8; 4] = *b"babe";
+
+/// Kind of slot claim of a BABE block author
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum ClaimType {
+    #[display("Primary")]
+    Primary,
+    #[display("Secondary plain")]
+    SecondaryPlain,
+    #[display("Secondary VRF")]
+    SecondaryVRF,
+}
+
+/// Slot claim of the block author, from the BABE pre-runtime digest
+#[derive(Debug, Clone)]
+pub struct BabeSlotClaim {
+    pub authority_index: u32,
+    pub slot: u64,
+    pub claim_type: ClaimType,
+    /// VRF pre-output, missing for secondary plain claims
+    pub vrf_output: Option<Vec<u8>>,
+}
+
+impl From<PreDigest> for BabeSlotClaim {
+    fn from(pre_digest: PreDigest) -> Self {
+        let claim_type = match pre_digest {
+            PreDigest::Primary(_) => ClaimType::Primary,
+            PreDigest::SecondaryPlain(_) => ClaimType::SecondaryPlain,
+            PreDigest::SecondaryVRF(_) => ClaimType::SecondaryVRF,
+        };
+
+        Self {
+            authority_index: pre_digest.authority_index(),
+            slot: u64::from(pre_digest.slot()),
+            claim_type,
+            vrf_output: pre_digest
+                .vrf_signature()
+                .map(|vrf_signature| vrf_signature.pre_output.encode()),
+        }
+    }
+}
+
+/// Find and decode the BABE pre-runtime digest
+pub fn find_slot_claim(
+    digest_logs: &[DigestItem],
+) -> Result<Option<BabeSlotClaim>, parity_scale_codec::Error> {
+    digest_logs
+        .iter()
+        .find_map(|log| match log {
+            DigestItem::PreRuntime(engine_id, bytes) if *engine_id == BABE_ENGINE_ID => {
+                Some(PreDigest::decode(&mut &bytes[..]).map(BabeSlotClaim::from))
+            }
+            _ => None,
+        })
+        .transpose()
+}
@@ -0,0 +1,169 @@
+use crate::{
+    decoder::metadata::AnyRuntimeMetadata,
+    error::Error,
+    fetch::{constant, fetch, fetch_optional},
+    node_rpc::{
+        client::NodeRPC,
+        models::{BlockHashHex, RuntimeVersion},
+    },
+    pallets::{
+        babe::decoder::{BabeAuthority, BabeEpochConfiguration},
+        schema::{StorageSchema, StorageTypes},
+    },
+};
+
+pub const PALLET_NAME: &str = "Babe";
+
+pub const EPOCH_INDEX: StorageSchema<()> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "EpochIndex",
+    shapes: &[(
+        (),
+        StorageTypes {
+            keys: &[],
+            value: "u64",
+        },
+    )],
+};
+
+pub const AUTHORITIES: StorageSchema<()> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "Authorities",
+    shapes: &[
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "Vec<(AuthorityId, BabeAuthorityWeight)>",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "WeakBoundedVec<(AuthorityId, BabeAuthorityWeight), T::MaxAuthorities>",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "Vec<(sp_consensus_babe::app::Public, u64)>",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "frame_support::storage::weak_bounded_vec::WeakBoundedVec<(sp_consensus_babe::app::Public, u64)>",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "bounded_collections::weak_bounded_vec::WeakBoundedVec<(sp_consensus_babe::app::Public, u64)>",
+            },
+        ),
+    ],
+};
+
+/// Added after the first runtimes with BABE, only shown when present
+pub const NEXT_EPOCH_CONFIG: StorageSchema<()> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "NextEpochConfig",
+    shapes: &[
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "BabeEpochConfiguration",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "sp_consensus_babe::BabeEpochConfiguration",
+            },
+        ),
+    ],
+};
+
+pub async fn fetch_epoch_index(
+    rpc: &NodeRPC,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<u64, Error> {
+    EPOCH_INDEX.resolve(metadata)?;
+
+    fetch(
+        PALLET_NAME,
+        EPOCH_INDEX.storage_entry_name,
+        (),
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await
+}
+
+/// Authorities of the current epoch, indexed by the `authority_index` of the BABE pre-digest
+pub async fn fetch_authorities(
+    rpc: &NodeRPC,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<Vec<BabeAuthority>, Error> {
+    AUTHORITIES.resolve(metadata)?;
+
+    fetch(
+        PALLET_NAME,
+        AUTHORITIES.storage_entry_name,
+        (),
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await
+}
+
+/// Epoch configuration enacted by the next epoch, `None` if it doesn't change
+pub async fn fetch_next_epoch_config(
+    rpc: &NodeRPC,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<Option<BabeEpochConfiguration>, Error> {
+    NEXT_EPOCH_CONFIG.resolve(metadata)?;
+
+    fetch_optional(
+        PALLET_NAME,
+        NEXT_EPOCH_CONFIG.storage_entry_name,
+        (),
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await
+}
+
+/// Number of slots in an epoch
+pub fn epoch_duration(
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<u64, Error> {
+    constant(PALLET_NAME, "EpochDuration", metadata, runtime_version)
+}
+
+/// Slot duration in milliseconds, BABE uses the expected block time as slot duration
+pub fn slot_duration(
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<u64, Error> {
+    constant(PALLET_NAME, "ExpectedBlockTime", metadata, runtime_version)
+}
@@ -0,0 +1,47 @@
+use scale_decode::ext::primitive_types::U256;
+
+use crate::{
+    decoder::metadata::AnyRuntimeMetadata,
+    error::Error,
+    fetch::fetch,
+    node_rpc::{
+        client::NodeRPC,
+        models::{BlockHashHex, RuntimeVersion},
+    },
+    pallets::schema::{StorageSchema, StorageTypes},
+};
+
+pub const PALLET_NAME: &str = "BaseFee";
+
+pub const BASE_FEE_PER_GAS: StorageSchema<()> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "BaseFeePerGas",
+    shapes: &[(
+        (),
+        StorageTypes {
+            keys: &[],
+            value: "primitive_types::U256",
+        },
+    )],
+};
+
+/// Base fee charged to the transactions of the next block, it is updated when a block is finalized
+pub async fn fetch_base_fee_per_gas(
+    rpc: &NodeRPC,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<U256, Error> {
+    BASE_FEE_PER_GAS.resolve(metadata)?;
+
+    fetch(
+        PALLET_NAME,
+        BASE_FEE_PER_GAS.storage_entry_name,
+        (),
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await
+}
@@ -0,0 +1,3 @@
+pub mod fetch;
+
+pub use fetch::fetch_base_fee_per_gas;
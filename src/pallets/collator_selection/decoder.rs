@@ -0,0 +1,20 @@
+use derive_more::Display;
+
+use crate::{decoder::value_decoder::ValueDecoder, pallets::session::decoder::AccountId};
+
+/// `pallet_collator_selection::CandidateInfo`
+#[allow(dead_code)]
+#[derive(Debug, Clone, ValueDecoder)]
+pub struct CandidateInfo {
+    pub who: AccountId,
+    pub deposit: u128,
+}
+
+/// How a collator got into the collator set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum CollatorRole {
+    #[display("invulnerable")]
+    Invulnerable,
+    #[display("candidate")]
+    Candidate,
+}
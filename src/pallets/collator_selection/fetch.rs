@@ -0,0 +1,148 @@
+use crate::{
+    decoder::metadata::{AnyRuntimeMetadata, MetadataError},
+    error::Error,
+    fetch::fetch_optional,
+    node_rpc::{
+        client::NodeRPC,
+        models::{BlockHashHex, RuntimeVersion},
+    },
+    pallets::{
+        collator_selection::decoder::{CandidateInfo, CollatorRole},
+        schema::{StorageSchema, StorageTypes},
+        session::decoder::AccountId,
+    },
+};
+
+pub const PALLET_NAME: &str = "CollatorSelection";
+
+pub const INVULNERABLES: StorageSchema<()> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "Invulnerables",
+    shapes: &[
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "Vec<sp_core::crypto::AccountId32>",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "frame_support::storage::bounded_vec::BoundedVec<sp_core::crypto::AccountId32>",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "bounded_collections::bounded_vec::BoundedVec<sp_core::crypto::AccountId32>",
+            },
+        ),
+    ],
+};
+
+/// Candidates sorted by deposit, replaced `Candidates` in newer runtimes
+pub const CANDIDATE_LIST: StorageSchema<()> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "CandidateList",
+    shapes: &[(
+        (),
+        StorageTypes {
+            keys: &[],
+            value: "bounded_collections::bounded_vec::BoundedVec<pallet_collator_selection::pallet::CandidateInfo<sp_core::crypto::AccountId32, u128>>",
+        },
+    )],
+};
+
+pub const CANDIDATES: StorageSchema<()> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "Candidates",
+    shapes: &[
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "Vec<pallet_collator_selection::pallet::CandidateInfo<sp_core::crypto::AccountId32, u128>>",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "frame_support::storage::bounded_vec::BoundedVec<pallet_collator_selection::pallet::CandidateInfo<sp_core::crypto::AccountId32, u128>>",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "bounded_collections::bounded_vec::BoundedVec<pallet_collator_selection::pallet::CandidateInfo<sp_core::crypto::AccountId32, u128>>",
+            },
+        ),
+    ],
+};
+
+async fn fetch_candidates(
+    rpc: &NodeRPC,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<Vec<CandidateInfo>, Error> {
+    let candidates_schema = match CANDIDATE_LIST.resolve(metadata) {
+        Ok(()) => CANDIDATE_LIST,
+        Err(MetadataError::MetadataNotFound(_)) => {
+            CANDIDATES.resolve(metadata)?;
+            CANDIDATES
+        }
+        Err(error) => Err(error)?,
+    };
+
+    let candidates = fetch_optional(
+        PALLET_NAME,
+        candidates_schema.storage_entry_name,
+        (),
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await?;
+    Ok(candidates.unwrap_or_default())
+}
+
+/// Whether the collator is an invulnerable or a candidate, `None` if it is neither
+pub async fn fetch_collator_role(
+    rpc: &NodeRPC,
+    collator: &[u8; 32],
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<Option<CollatorRole>, Error> {
+    INVULNERABLES.resolve(metadata)?;
+
+    let invulnerables: Vec<AccountId> = fetch_optional(
+        PALLET_NAME,
+        INVULNERABLES.storage_entry_name,
+        (),
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await?
+    .unwrap_or_default();
+    if invulnerables
+        .iter()
+        .any(|AccountId(account_id)| account_id == collator)
+    {
+        return Ok(Some(CollatorRole::Invulnerable));
+    }
+
+    let candidates = fetch_candidates(rpc, block_hash, metadata, runtime_version).await?;
+    Ok(candidates
+        .iter()
+        .any(|candidate| candidate.who.0 == *collator)
+        .then_some(CollatorRole::Candidate))
+}
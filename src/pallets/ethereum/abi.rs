@@ -0,0 +1,182 @@
+use std::{collections::HashMap, fs::File, path::PathBuf};
+
+use ethabi::{Contract, RawLog, Token};
+use ethereum::Log;
+use ethereum_types::H160;
+use thiserror::Error;
+
+use crate::pallets::ethereum::address::checksum_address;
+
+#[derive(Debug, Error)]
+pub enum AbiError {
+    #[error("Invalid ABI registration {0}, expected <contract address>=<ABI JSON path>")]
+    InvalidRegistration(String),
+
+    #[error("Couldn't open ABI file {path}: {source}")]
+    FileOpenFailed {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Couldn't parse ABI file {path}: {source}")]
+    ParsingFailed {
+        path: PathBuf,
+        source: ethabi::Error,
+    },
+
+    #[error("Couldn't decode ABI encoded data: {0}")]
+    DecodingFailed(#[from] ethabi::Error),
+}
+
+/// Parse a `<contract address>=<ABI JSON path>` command line argument
+pub fn parse_abi_registration(arg: &str) -> Result<(H160, PathBuf), AbiError> {
+    let invalid_registration = || AbiError::InvalidRegistration(arg.to_string());
+
+    let (address, path) = arg.split_once('=').ok_or_else(invalid_registration)?;
+    let address =
+        hex::decode(address.trim_start_matches("0x")).map_err(|_| invalid_registration())?;
+    if address.len() != H160::len_bytes() {
+        Err(invalid_registration())?
+    }
+
+    Ok((H160::from_slice(&address), PathBuf::from(path)))
+}
+
+/// Contract ABIs registered by contract address
+#[derive(Debug, Default)]
+pub struct AbiRegistry {
+    contracts: HashMap<H160, Contract>,
+}
+
+impl AbiRegistry {
+    pub fn load(registrations: &[(H160, PathBuf)]) -> Result<Self, AbiError> {
+        let contracts = registrations
+            .iter()
+            .map(|(address, path)| {
+                let file = File::open(path).map_err(|source| AbiError::FileOpenFailed {
+                    path: path.clone(),
+                    source,
+                })?;
+                let contract = Contract::load(file).map_err(|source| AbiError::ParsingFailed {
+                    path: path.clone(),
+                    source,
+                })?;
+                Ok((*address, contract))
+            })
+            .collect::<Result<_, AbiError>>()?;
+
+        Ok(AbiRegistry { contracts })
+    }
+
+    /// Decode the function call of a transaction input
+    /// Returns `None` if the contract or the function selector is unknown
+    pub fn decode_input(
+        &self,
+        address: &H160,
+        input: &[u8],
+    ) -> Result<Option<DecodedAbiItem>, AbiError> {
+        let (Some(contract), Some(selector)) = (self.contracts.get(address), input.get(..4)) else {
+            return Ok(None);
+        };
+
+        let Some(function) = contract
+            .functions()
+            .find(|function| function.short_signature() == selector)
+        else {
+            return Ok(None);
+        };
+
+        let tokens = function.decode_input(&input[4..])?;
+
+        Ok(Some(DecodedAbiItem {
+            name: function.name.clone(),
+            params: function
+                .inputs
+                .iter()
+                .map(|param| param.name.clone())
+                .zip(tokens)
+                .collect(),
+        }))
+    }
+
+    /// Decode the event of a log, with both indexed (topics) and non-indexed (data) params
+    /// Returns `None` if the contract or the event signature is unknown
+    pub fn decode_log(&self, log: &Log) -> Result<Option<DecodedAbiItem>, AbiError> {
+        let (Some(contract), Some(signature)) =
+            (self.contracts.get(&log.address), log.topics.first())
+        else {
+            return Ok(None);
+        };
+
+        let Some(event) = contract
+            .events()
+            .find(|event| !event.anonymous && event.signature().as_bytes() == signature.as_bytes())
+        else {
+            return Ok(None);
+        };
+
+        // `ethabi` depends on a different version of `ethereum-types`
+        let raw_log = RawLog {
+            topics: log
+                .topics
+                .iter()
+                .map(|topic| ethabi::Hash::from_slice(topic.as_bytes()))
+                .collect(),
+            data: log.data.clone(),
+        };
+        let decoded_log = event.parse_log(raw_log)?;
+
+        Ok(Some(DecodedAbiItem {
+            name: event.name.clone(),
+            params: decoded_log
+                .params
+                .into_iter()
+                .map(|param| (param.name, param.value))
+                .collect(),
+        }))
+    }
+}
+
+/// Decoded function call or event with its named params
+#[derive(Debug, Clone)]
+pub struct DecodedAbiItem {
+    pub name: String,
+    pub params: Vec<(String, Token)>,
+}
+
+impl std::fmt::Display for DecodedAbiItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params = self
+            .params
+            .iter()
+            .map(|(name, token)| format!("{name}: {}", format_token(token)))
+            .collect::<Vec<_>>();
+        write!(f, "{}({})", self.name, params.join(", "))
+    }
+}
+
+fn format_token(token: &Token) -> String {
+    match token {
+        Token::Address(address) => checksum_address(&H160::from_slice(address.as_bytes())),
+        Token::FixedBytes(bytes) | Token::Bytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        Token::Uint(uint) => uint.to_string(),
+        // Signed integers are two's complement
+        Token::Int(int) => {
+            if int.bit(255) {
+                format!("-{}", (!*int).overflowing_add(ethabi::Int::one()).0)
+            } else {
+                int.to_string()
+            }
+        }
+        Token::Bool(bool) => bool.to_string(),
+        Token::String(string) => format!("{string:?}"),
+        Token::FixedArray(tokens) | Token::Array(tokens) => {
+            let tokens = tokens.iter().map(format_token).collect::<Vec<_>>();
+            format!("[{}]", tokens.join(", "))
+        }
+        Token::Tuple(tokens) => {
+            let tokens = tokens.iter().map(format_token).collect::<Vec<_>>();
+            format!("({})", tokens.join(", "))
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use ethereum_types::H160;
+use sha3::{Digest, Keccak256};
+
+/// Format an address with the mixed-case checksum of EIP-55
+pub fn checksum_address(address: &H160) -> String {
+    let address = hex::encode(address.as_bytes());
+    let address_hash = Keccak256::digest(address.as_bytes());
+
+    let checksummed = address
+        .chars()
+        .enumerate()
+        .map(|(i, char)| {
+            // Every hex character has a matching nibble in the hash
+            let nibble = (address_hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 {
+                char.to_ascii_uppercase()
+            } else {
+                char
+            }
+        })
+        .collect::<String>();
+
+    format!("0x{checksummed}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eip55_test_vectors() {
+        // All caps, all lower and mixed case addresses from EIP-55
+        for expected in [
+            "0x52908400098527886E0F7030069857D2E4169EE7",
+            "0x8617E340B3D01FA5F11F306F4090FD50E238070D",
+            "0xde709f2102306220921060314715629080e2fb77",
+            "0x27b1fdb04752bbc536007a920d24acb045561c26",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let address: H160 = expected.to_lowercase().parse().unwrap();
+            assert_eq!(checksum_address(&address), expected);
+        }
+    }
+}
@@ -2,14 +2,33 @@ use std::collections::HashMap;
 
 /// Pallet defined storage parsers and verificators for pallet-ethereum
 use ethereum::{
-    Block, Header, LegacyTransaction, TransactionAction, TransactionV2,
+    AccessListItem, AuthorizationListItem, Block, EIP658ReceiptData, EIP1559Transaction,
+    EIP2930Transaction, EIP7702Transaction, FrontierReceiptData, Header, LegacyTransaction, Log,
+    ReceiptV3, ReceiptV4, TransactionAction, TransactionV2, TransactionV3,
+    eip2930::{self, MalleableTransactionSignature},
     legacy::TransactionSignature,
 };
 use ethereum_types::{Bloom, H64};
 use scale_decode::ext::primitive_types::{H160, H256, U256};
-use scale_value::Value;
+use scale_value::{Composite, Primitive, Value, ValueDef};
 
-use crate::decoder::value_decoder::{ValueDecoder, ValueDecoderError, decode_singleton, get_field};
+use crate::decoder::value_decoder::{
+    ValueDecoder, ValueDecoderError, WithErrorSpan, composite_to_elements, decode_singleton,
+    decode_variant, get_element, get_field, get_field_with,
+};
+
+/// Transaction details stored by pallet-ethereum for the RPC (`fp_rpc::TransactionStatus`)
+#[allow(dead_code)]
+#[derive(Debug, Clone, ValueDecoder)]
+pub struct TransactionStatus {
+    pub transaction_hash: H256,
+    pub transaction_index: u32,
+    pub from: H160,
+    pub to: Option<H160>,
+    pub contract_address: Option<H160>,
+    pub logs: Vec<Log>,
+    pub logs_bloom: Bloom,
+}
 
 impl<T, Tx> ValueDecoder<T> for Block<Tx>
 where
@@ -82,7 +101,19 @@ impl<T> ValueDecoder<T> for TransactionAction {
         Self: Sized,
         T: std::fmt::Debug,
     {
-        todo!()
+        let (variant_name, composite) = decode_variant(value)?;
+        match &variant_name[..] {
+            "Call" => decode_variant_field(composite)
+                .map(TransactionAction::Call)
+                .add_error_span("Call"),
+            "Create" => composite_to_elements(composite, 0)
+                .map(|_| TransactionAction::Create)
+                .add_error_span("Create"),
+            _ => Err(ValueDecoderError::UnexpectedVariantName {
+                variant_name,
+                span: String::new(),
+            }),
+        }
     }
 }
 
@@ -92,7 +123,155 @@ impl<T> ValueDecoder<T> for TransactionSignature {
         Self: Sized,
         T: std::fmt::Debug,
     {
-        todo!()
+        let mut record = HashMap::decode(value)?;
+
+        // `TransactionRecoveryId` is a newtype around the `v` value
+        let v = get_field_with("v", &mut record, decode_singleton::<T, u64>)?;
+
+        TransactionSignature::new(
+            v,
+            get_field("r", &mut record)?,
+            get_field("s", &mut record)?,
+        )
+        .ok_or(ValueDecoderError::TransactionSignatureInvalid {
+            span: String::new(),
+        })
+    }
+}
+
+impl<T> ValueDecoder<T> for eip2930::TransactionSignature {
+    fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
+    where
+        Self: Sized,
+        T: std::fmt::Debug,
+    {
+        let mut record = HashMap::decode(value)?;
+
+        eip2930::TransactionSignature::new(
+            get_field("odd_y_parity", &mut record)?,
+            get_field("r", &mut record)?,
+            get_field("s", &mut record)?,
+        )
+        .ok_or(ValueDecoderError::TransactionSignatureInvalid {
+            span: String::new(),
+        })
+    }
+}
+
+impl<T> ValueDecoder<T> for MalleableTransactionSignature {
+    fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
+    where
+        Self: Sized,
+        T: std::fmt::Debug,
+    {
+        let mut record = HashMap::decode(value)?;
+
+        Ok(MalleableTransactionSignature {
+            odd_y_parity: get_field("odd_y_parity", &mut record)?,
+            r: get_field("r", &mut record)?,
+            s: get_field("s", &mut record)?,
+        })
+    }
+}
+
+impl<T> ValueDecoder<T> for AccessListItem {
+    fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
+    where
+        Self: Sized,
+        T: std::fmt::Debug,
+    {
+        let mut record = HashMap::decode(value)?;
+
+        Ok(AccessListItem {
+            address: get_field("address", &mut record)?,
+            storage_keys: get_field("storage_keys", &mut record)?,
+        })
+    }
+}
+
+impl<T> ValueDecoder<T> for AuthorizationListItem {
+    fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
+    where
+        Self: Sized,
+        T: std::fmt::Debug,
+    {
+        let mut record = HashMap::decode(value)?;
+
+        Ok(AuthorizationListItem {
+            chain_id: get_field("chain_id", &mut record)?,
+            address: get_field("address", &mut record)?,
+            nonce: get_field("nonce", &mut record)?,
+            signature: get_field("signature", &mut record)?,
+        })
+    }
+}
+
+impl<T> ValueDecoder<T> for EIP2930Transaction {
+    fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
+    where
+        Self: Sized,
+        T: std::fmt::Debug,
+    {
+        let mut record = HashMap::decode(value)?;
+
+        Ok(EIP2930Transaction {
+            chain_id: get_field("chain_id", &mut record)?,
+            nonce: get_field("nonce", &mut record)?,
+            gas_price: get_field("gas_price", &mut record)?,
+            gas_limit: get_field("gas_limit", &mut record)?,
+            action: get_field("action", &mut record)?,
+            value: get_field("value", &mut record)?,
+            input: get_field("input", &mut record)?,
+            access_list: get_field("access_list", &mut record)?,
+            signature: get_field("signature", &mut record)?,
+        })
+    }
+}
+
+impl<T> ValueDecoder<T> for EIP1559Transaction {
+    fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
+    where
+        Self: Sized,
+        T: std::fmt::Debug,
+    {
+        let mut record = HashMap::decode(value)?;
+
+        Ok(EIP1559Transaction {
+            chain_id: get_field("chain_id", &mut record)?,
+            nonce: get_field("nonce", &mut record)?,
+            max_priority_fee_per_gas: get_field("max_priority_fee_per_gas", &mut record)?,
+            max_fee_per_gas: get_field("max_fee_per_gas", &mut record)?,
+            gas_limit: get_field("gas_limit", &mut record)?,
+            action: get_field("action", &mut record)?,
+            value: get_field("value", &mut record)?,
+            input: get_field("input", &mut record)?,
+            access_list: get_field("access_list", &mut record)?,
+            signature: get_field("signature", &mut record)?,
+        })
+    }
+}
+
+impl<T> ValueDecoder<T> for EIP7702Transaction {
+    fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
+    where
+        Self: Sized,
+        T: std::fmt::Debug,
+    {
+        let mut record = HashMap::decode(value)?;
+
+        Ok(EIP7702Transaction {
+            chain_id: get_field("chain_id", &mut record)?,
+            nonce: get_field("nonce", &mut record)?,
+            max_priority_fee_per_gas: get_field("max_priority_fee_per_gas", &mut record)?,
+            max_fee_per_gas: get_field("max_fee_per_gas", &mut record)?,
+            gas_limit: get_field("gas_limit", &mut record)?,
+            destination: get_field("destination", &mut record)?,
+            value: get_field("value", &mut record)?,
+            data: get_field("data", &mut record)?,
+            access_list: get_field("access_list", &mut record)?,
+            authorization_list: get_field("authorization_list", &mut record)?,
+            signature: get_field("signature", &mut record)?,
+        })
     }
 }
 
@@ -102,10 +281,166 @@ impl<T> ValueDecoder<T> for TransactionV2 {
         Self: Sized,
         T: std::fmt::Debug,
     {
-        todo!()
+        let (variant_name, composite) = decode_variant(value)?;
+        match &variant_name[..] {
+            "Legacy" => decode_variant_field(composite)
+                .map(TransactionV2::Legacy)
+                .add_error_span("Legacy"),
+            "EIP2930" => decode_variant_field(composite)
+                .map(TransactionV2::EIP2930)
+                .add_error_span("EIP2930"),
+            "EIP1559" => decode_variant_field(composite)
+                .map(TransactionV2::EIP1559)
+                .add_error_span("EIP1559"),
+            _ => Err(ValueDecoderError::UnexpectedVariantName {
+                variant_name,
+                span: String::new(),
+            }),
+        }
+    }
+}
+
+impl<T> ValueDecoder<T> for TransactionV3 {
+    fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
+    where
+        Self: Sized,
+        T: std::fmt::Debug,
+    {
+        let (variant_name, composite) = decode_variant(value)?;
+        match &variant_name[..] {
+            "Legacy" => decode_variant_field(composite)
+                .map(TransactionV3::Legacy)
+                .add_error_span("Legacy"),
+            "EIP2930" => decode_variant_field(composite)
+                .map(TransactionV3::EIP2930)
+                .add_error_span("EIP2930"),
+            "EIP1559" => decode_variant_field(composite)
+                .map(TransactionV3::EIP1559)
+                .add_error_span("EIP1559"),
+            "EIP7702" => decode_variant_field(composite)
+                .map(TransactionV3::EIP7702)
+                .add_error_span("EIP7702"),
+            _ => Err(ValueDecoderError::UnexpectedVariantName {
+                variant_name,
+                span: String::new(),
+            }),
+        }
+    }
+}
+
+impl<T> ValueDecoder<T> for Log {
+    fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
+    where
+        Self: Sized,
+        T: std::fmt::Debug,
+    {
+        let mut record = HashMap::decode(value)?;
+
+        Ok(Log {
+            address: get_field("address", &mut record)?,
+            topics: get_field("topics", &mut record)?,
+            data: get_field("data", &mut record)?,
+        })
+    }
+}
+
+impl<T> ValueDecoder<T> for FrontierReceiptData {
+    fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
+    where
+        Self: Sized,
+        T: std::fmt::Debug,
+    {
+        let mut record = HashMap::decode(value)?;
+
+        Ok(FrontierReceiptData {
+            state_root: get_field("state_root", &mut record)?,
+            used_gas: get_field("used_gas", &mut record)?,
+            logs_bloom: get_field("logs_bloom", &mut record)?,
+            logs: get_field("logs", &mut record)?,
+        })
+    }
+}
+
+impl<T> ValueDecoder<T> for EIP658ReceiptData {
+    fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
+    where
+        Self: Sized,
+        T: std::fmt::Debug,
+    {
+        let mut record = HashMap::decode(value)?;
+
+        Ok(EIP658ReceiptData {
+            status_code: get_field("status_code", &mut record)?,
+            used_gas: get_field("used_gas", &mut record)?,
+            logs_bloom: get_field("logs_bloom", &mut record)?,
+            logs: get_field("logs", &mut record)?,
+        })
+    }
+}
+
+impl<T> ValueDecoder<T> for ReceiptV3 {
+    fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
+    where
+        Self: Sized,
+        T: std::fmt::Debug,
+    {
+        let (variant_name, composite) = decode_variant(value)?;
+        match &variant_name[..] {
+            "Legacy" => decode_variant_field(composite)
+                .map(ReceiptV3::Legacy)
+                .add_error_span("Legacy"),
+            "EIP2930" => decode_variant_field(composite)
+                .map(ReceiptV3::EIP2930)
+                .add_error_span("EIP2930"),
+            "EIP1559" => decode_variant_field(composite)
+                .map(ReceiptV3::EIP1559)
+                .add_error_span("EIP1559"),
+            _ => Err(ValueDecoderError::UnexpectedVariantName {
+                variant_name,
+                span: String::new(),
+            }),
+        }
     }
 }
 
+impl<T> ValueDecoder<T> for ReceiptV4 {
+    fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
+    where
+        Self: Sized,
+        T: std::fmt::Debug,
+    {
+        let (variant_name, composite) = decode_variant(value)?;
+        match &variant_name[..] {
+            "Legacy" => decode_variant_field(composite)
+                .map(ReceiptV4::Legacy)
+                .add_error_span("Legacy"),
+            "EIP2930" => decode_variant_field(composite)
+                .map(ReceiptV4::EIP2930)
+                .add_error_span("EIP2930"),
+            "EIP1559" => decode_variant_field(composite)
+                .map(ReceiptV4::EIP1559)
+                .add_error_span("EIP1559"),
+            "EIP7702" => decode_variant_field(composite)
+                .map(ReceiptV4::EIP7702)
+                .add_error_span("EIP7702"),
+            _ => Err(ValueDecoderError::UnexpectedVariantName {
+                variant_name,
+                span: String::new(),
+            }),
+        }
+    }
+}
+
+/// Decode the single field of a tuple variant
+fn decode_variant_field<T, R>(composite: Composite<T>) -> Result<R, ValueDecoderError>
+where
+    T: std::fmt::Debug,
+    R: ValueDecoder<T>,
+{
+    let mut elements = composite_to_elements(composite, 1)?;
+    get_element(0, &mut elements)
+}
+
 impl<T> ValueDecoder<T> for H256 {
     fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
     where
@@ -132,7 +467,13 @@ impl<T> ValueDecoder<T> for U256 {
         Self: Sized,
         T: std::fmt::Debug,
     {
-        Ok(Self(decode_singleton(value)?))
+        match value.value {
+            ValueDef::Primitive(Primitive::U256(bytes)) => Ok(U256::from_little_endian(&bytes)),
+            value_def => Ok(Self(decode_singleton(Value {
+                value: value_def,
+                context: value.context,
+            })?)),
+        }
     }
 }
 
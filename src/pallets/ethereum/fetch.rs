@@ -1,32 +1,134 @@
-use ethereum::{Block, LegacyTransaction, TransactionV2};
+use ethereum::{
+    Block, EIP658ReceiptData, FrontierReceiptData, LegacyTransaction, ReceiptAny, ReceiptV3,
+    ReceiptV4, TransactionV2, TransactionV3,
+};
 use ethereum_types::H256;
 use scale_decode::ext::primitive_types::U256;
 
 use crate::{
-    decoder::metadata::{AnyRuntimeMetadata, MetadataError},
+    decoder::metadata::AnyRuntimeMetadata,
     error::Error,
     fetch::fetch,
     node_rpc::{
         client::NodeRPC,
         models::{BlockHashHex, RuntimeVersion},
     },
+    pallets::{
+        ethereum::decoder::TransactionStatus,
+        schema::{StorageSchema, StorageTypes},
+    },
 };
 
 pub const PALLET_NAME: &str = "Ethereum";
 
-pub const STORAGE_TYPES: [(&str, (&[&str], &str)); 2] = [
-    (
-        "BlockHash",
-        (&["primitive_types::U256"], "primitive_types::H256"),
-    ),
-    (
-        "CurrentBlock",
+pub const BLOCK_HASH: StorageSchema<()> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "BlockHash",
+    shapes: &[(
+        (),
+        StorageTypes {
+            keys: &["primitive_types::U256"],
+            value: "primitive_types::H256",
+        },
+    )],
+};
+
+#[derive(Debug, Clone, Copy)]
+pub enum CurrentBlockShape {
+    Legacy,
+    V2,
+    V3,
+}
+
+pub const CURRENT_BLOCK: StorageSchema<CurrentBlockShape> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "CurrentBlock",
+    shapes: &[
+        (
+            CurrentBlockShape::Legacy,
+            StorageTypes {
+                keys: &[],
+                value: "ethereum::block::Block<ethereum::transaction::LegacyTransaction>",
+            },
+        ),
+        (
+            CurrentBlockShape::Legacy,
+            StorageTypes {
+                keys: &[],
+                value: "ethereum::block::Block<ethereum::transaction::legacy::LegacyTransaction>",
+            },
+        ),
+        (
+            CurrentBlockShape::V2,
+            StorageTypes {
+                keys: &[],
+                value: "ethereum::block::Block<ethereum::transaction::TransactionV2>",
+            },
+        ),
+        (
+            CurrentBlockShape::V3,
+            StorageTypes {
+                keys: &[],
+                value: "ethereum::block::Block<ethereum::transaction::TransactionV3>",
+            },
+        ),
+    ],
+};
+
+#[derive(Debug, Clone, Copy)]
+pub enum CurrentReceiptsShape {
+    Frontier,
+    EIP658,
+    V3,
+    V4,
+}
+
+pub const CURRENT_RECEIPTS: StorageSchema<CurrentReceiptsShape> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "CurrentReceipts",
+    shapes: &[
+        (
+            CurrentReceiptsShape::Frontier,
+            StorageTypes {
+                keys: &[],
+                value: "Vec<ethereum::receipt::FrontierReceiptData>",
+            },
+        ),
+        (
+            CurrentReceiptsShape::EIP658,
+            StorageTypes {
+                keys: &[],
+                value: "Vec<ethereum::receipt::EIP658ReceiptData>",
+            },
+        ),
         (
-            &[],
-            "ethereum::block::Block<ethereum::transaction::LegacyTransaction>",
+            CurrentReceiptsShape::V3,
+            StorageTypes {
+                keys: &[],
+                value: "Vec<ethereum::receipt::ReceiptV3>",
+            },
+        ),
+        (
+            CurrentReceiptsShape::V4,
+            StorageTypes {
+                keys: &[],
+                value: "Vec<ethereum::receipt::ReceiptV4>",
+            },
         ),
-    ),
-];
+    ],
+};
+
+pub const CURRENT_TRANSACTION_STATUSES: StorageSchema<()> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "CurrentTransactionStatuses",
+    shapes: &[(
+        (),
+        StorageTypes {
+            keys: &[],
+            value: "Vec<fp_rpc::TransactionStatus>",
+        },
+    )],
+};
 
 pub async fn fetch_block_hash(
     rpc: &NodeRPC,
@@ -35,40 +137,12 @@ pub async fn fetch_block_hash(
     metadata: AnyRuntimeMetadata<'_>,
     runtime_version: &RuntimeVersion,
 ) -> Result<Vec<u8>, Error> {
-    let storage_entry_name = "BlockHash";
-    let type_registry = metadata.type_registry();
-
-    let (key_types, value_type) = metadata
-        .pallet_metadata(PALLET_NAME)?
-        .storage_entry(storage_entry_name)?
-        .types_as_str(type_registry)?;
-
-    if key_types[..] != ["primitive_types::U256"] {
-        Err(Error::MetadataError(
-            MetadataError::UnexpectedStorageValueType {
-                expected: "primitive_types::U256".to_string(),
-                got: format!("{key_types:?}"),
-                pallet_name: PALLET_NAME.to_string(),
-                storage_entry_name: storage_entry_name.to_string(),
-            },
-        ))?;
-    }
-
-    if value_type != "primitive_types::H256" {
-        Err(Error::MetadataError(
-            MetadataError::UnexpectedStorageValueType {
-                expected: "primitive_types::H256".to_string(),
-                got: value_type.to_string(),
-                pallet_name: PALLET_NAME.to_string(),
-                storage_entry_name: storage_entry_name.to_string(),
-            },
-        ))?;
-    }
+    BLOCK_HASH.resolve(metadata)?;
 
     let block_number = U256::from(block_number).0;
     let H256(bytes) = fetch(
         PALLET_NAME,
-        storage_entry_name,
+        BLOCK_HASH.storage_entry_name,
         [block_number],
         rpc,
         block_hash,
@@ -84,20 +158,12 @@ pub async fn fetch_block(
     block_hash: &BlockHashHex,
     metadata: AnyRuntimeMetadata<'_>,
     runtime_version: &RuntimeVersion,
-) -> Result<Block<TransactionV2>, Error> {
-    let storage_entry_name = "CurrentBlock";
-    let type_registry = metadata.type_registry();
-
-    let (_key_types, value_type) = metadata
-        .pallet_metadata(PALLET_NAME)?
-        .storage_entry(storage_entry_name)?
-        .types_as_str(type_registry)?;
-
-    match &value_type[..] {
-        "ethereum::block::Block<ethereum::transaction::LegacyTransaction>" => {
+) -> Result<Block<TransactionV3>, Error> {
+    match CURRENT_BLOCK.resolve(metadata)? {
+        CurrentBlockShape::Legacy => {
             let legacy_block: Block<LegacyTransaction> = fetch(
                 PALLET_NAME,
-                storage_entry_name,
+                CURRENT_BLOCK.storage_entry_name,
                 (),
                 rpc,
                 block_hash,
@@ -108,10 +174,24 @@ pub async fn fetch_block(
 
             Ok(legacy_block.into())
         }
-        "ethereum::block::Block<ethereum::transaction::TransactionV2>" => {
+        CurrentBlockShape::V2 => {
+            let block: Block<TransactionV2> = fetch(
+                PALLET_NAME,
+                CURRENT_BLOCK.storage_entry_name,
+                (),
+                rpc,
+                block_hash,
+                metadata,
+                runtime_version,
+            )
+            .await?;
+
+            Ok(block.into())
+        }
+        CurrentBlockShape::V3 => {
             fetch(
                 PALLET_NAME,
-                storage_entry_name,
+                CURRENT_BLOCK.storage_entry_name,
                 (),
                 rpc,
                 block_hash,
@@ -120,14 +200,107 @@ pub async fn fetch_block(
             )
             .await
         }
-        other => Err(Error::MetadataError(
-            MetadataError::UnexpectedStorageValueType {
-                expected: "ethereum::block::Block<ethereum::transaction::LegacyTransaction>"
-                    .to_string(),
-                got: other.to_string(),
-                pallet_name: PALLET_NAME.to_string(),
-                storage_entry_name: storage_entry_name.to_string(),
-            },
-        )),
     }
 }
+
+/// Fetch the receipts of the current Ethereum block, in transaction order
+pub async fn fetch_receipts(
+    rpc: &NodeRPC,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<Vec<ReceiptAny>, Error> {
+    Ok(match CURRENT_RECEIPTS.resolve(metadata)? {
+        CurrentReceiptsShape::Frontier => {
+            let receipts: Vec<FrontierReceiptData> = fetch(
+                PALLET_NAME,
+                CURRENT_RECEIPTS.storage_entry_name,
+                (),
+                rpc,
+                block_hash,
+                metadata,
+                runtime_version,
+            )
+            .await?;
+
+            receipts.into_iter().map(ReceiptAny::Frontier).collect()
+        }
+        CurrentReceiptsShape::EIP658 => {
+            let receipts: Vec<EIP658ReceiptData> = fetch(
+                PALLET_NAME,
+                CURRENT_RECEIPTS.storage_entry_name,
+                (),
+                rpc,
+                block_hash,
+                metadata,
+                runtime_version,
+            )
+            .await?;
+
+            receipts.into_iter().map(ReceiptAny::EIP658).collect()
+        }
+        CurrentReceiptsShape::V3 => {
+            let receipts: Vec<ReceiptV3> = fetch(
+                PALLET_NAME,
+                CURRENT_RECEIPTS.storage_entry_name,
+                (),
+                rpc,
+                block_hash,
+                metadata,
+                runtime_version,
+            )
+            .await?;
+
+            receipts
+                .into_iter()
+                .map(|receipt| match receipt {
+                    ReceiptV3::Legacy(receipt) => ReceiptAny::EIP658(receipt),
+                    ReceiptV3::EIP2930(receipt) => ReceiptAny::EIP2930(receipt),
+                    ReceiptV3::EIP1559(receipt) => ReceiptAny::EIP1559(receipt),
+                })
+                .collect()
+        }
+        CurrentReceiptsShape::V4 => {
+            let receipts: Vec<ReceiptV4> = fetch(
+                PALLET_NAME,
+                CURRENT_RECEIPTS.storage_entry_name,
+                (),
+                rpc,
+                block_hash,
+                metadata,
+                runtime_version,
+            )
+            .await?;
+
+            receipts
+                .into_iter()
+                .map(|receipt| match receipt {
+                    ReceiptV4::Legacy(receipt) => ReceiptAny::EIP658(receipt),
+                    ReceiptV4::EIP2930(receipt) => ReceiptAny::EIP2930(receipt),
+                    ReceiptV4::EIP1559(receipt) => ReceiptAny::EIP1559(receipt),
+                    ReceiptV4::EIP7702(receipt) => ReceiptAny::EIP7702(receipt),
+                })
+                .collect()
+        }
+    })
+}
+
+pub async fn fetch_transaction_statuses(
+    rpc: &NodeRPC,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<Vec<TransactionStatus>, Error> {
+    CURRENT_TRANSACTION_STATUSES.resolve(metadata)?;
+
+    fetch(
+        PALLET_NAME,
+        CURRENT_TRANSACTION_STATUSES.storage_entry_name,
+        (),
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await
+}
@@ -1,4 +1,7 @@
+pub mod abi;
+pub mod address;
 pub mod decoder;
 pub mod fetch;
+pub mod transaction;
 
-pub use fetch::{PALLET_NAME, STORAGE_TYPES, fetch_block, fetch_block_hash};
+pub use fetch::{fetch_block, fetch_block_hash, fetch_receipts, fetch_transaction_statuses};
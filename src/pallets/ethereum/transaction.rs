@@ -0,0 +1,224 @@
+use ethereum::{Log, ReceiptAny, TransactionV3};
+use ethereum_types::{H160, H256, U256};
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use sha3::{Digest, Keccak256};
+use thiserror::Error;
+
+use crate::pallets::ethereum::decoder::TransactionStatus;
+
+#[derive(Debug, Error)]
+pub enum SenderRecoveryError {
+    #[error("Transaction signature is not a valid secp256k1 signature")]
+    InvalidSignature,
+
+    #[error("Couldn't recover the public key from the transaction signature")]
+    RecoveryFailed,
+}
+
+/// Recover the sender address of a signed transaction
+pub fn recover_sender(transaction: &TransactionV3) -> Result<H160, SenderRecoveryError> {
+    let (message_hash, r, s, odd_y_parity) = match transaction.clone() {
+        TransactionV3::Legacy(transaction) => {
+            let signature = transaction.signature.clone();
+            (
+                transaction.to_message().hash(),
+                *signature.r(),
+                *signature.s(),
+                signature.standard_v() == 1,
+            )
+        }
+        TransactionV3::EIP2930(transaction) => {
+            let signature = transaction.signature.clone();
+            (
+                transaction.to_message().hash(),
+                *signature.r(),
+                *signature.s(),
+                signature.odd_y_parity(),
+            )
+        }
+        TransactionV3::EIP1559(transaction) => {
+            let signature = transaction.signature.clone();
+            (
+                transaction.to_message().hash(),
+                *signature.r(),
+                *signature.s(),
+                signature.odd_y_parity(),
+            )
+        }
+        TransactionV3::EIP7702(transaction) => {
+            let signature = transaction.signature.clone();
+            (
+                transaction.to_message().hash(),
+                *signature.r(),
+                *signature.s(),
+                signature.odd_y_parity(),
+            )
+        }
+    };
+
+    recover_address(message_hash, r, s, odd_y_parity)
+}
+
+fn recover_address(
+    message_hash: H256,
+    r: H256,
+    s: H256,
+    odd_y_parity: bool,
+) -> Result<H160, SenderRecoveryError> {
+    let mut signature_bytes = [0u8; 64];
+    signature_bytes[..32].copy_from_slice(r.as_bytes());
+    signature_bytes[32..].copy_from_slice(s.as_bytes());
+
+    let signature = Signature::from_slice(&signature_bytes)
+        .map_err(|_| SenderRecoveryError::InvalidSignature)?;
+    let recovery_id = RecoveryId::new(odd_y_parity, false);
+
+    let verifying_key =
+        VerifyingKey::recover_from_prehash(message_hash.as_bytes(), &signature, recovery_id)
+            .map_err(|_| SenderRecoveryError::RecoveryFailed)?;
+
+    // The address is the last 20 bytes of the hash of the uncompressed public key,
+    // without the 0x04 prefix
+    let public_key = verifying_key.to_encoded_point(false);
+    let public_key_hash = Keccak256::digest(&public_key.as_bytes()[1..]);
+
+    Ok(H160::from_slice(&public_key_hash[12..]))
+}
+
+/// Short name of the transaction type, following the EIP numbers
+pub fn transaction_type(transaction: &TransactionV3) -> &'static str {
+    match transaction {
+        TransactionV3::Legacy(_) => "Legacy",
+        TransactionV3::EIP2930(_) => "EIP-2930",
+        TransactionV3::EIP1559(_) => "EIP-1559",
+        TransactionV3::EIP7702(_) => "EIP-7702",
+    }
+}
+
+/// A transaction of the current Ethereum block, matched with its receipt and status
+#[derive(Debug, Clone)]
+pub struct BlockTransaction {
+    pub transaction: TransactionV3,
+    pub receipt: Option<ReceiptAny>,
+    pub status: Option<TransactionStatus>,
+    pub gas_used: Option<U256>,
+}
+
+impl BlockTransaction {
+    /// `Some(true)` if the transaction succeeded, `None` for pre EIP-658 receipts without status code
+    pub fn succeeded(&self) -> Option<bool> {
+        match self.receipt.as_ref()? {
+            ReceiptAny::Frontier(_) => None,
+            ReceiptAny::EIP658(receipt)
+            | ReceiptAny::EIP2930(receipt)
+            | ReceiptAny::EIP1559(receipt)
+            | ReceiptAny::EIP7702(receipt) => Some(receipt.status_code == 1),
+        }
+    }
+
+    pub fn logs(&self) -> &[Log] {
+        match &self.receipt {
+            Some(ReceiptAny::Frontier(receipt)) => &receipt.logs,
+            Some(
+                ReceiptAny::EIP658(receipt)
+                | ReceiptAny::EIP2930(receipt)
+                | ReceiptAny::EIP1559(receipt)
+                | ReceiptAny::EIP7702(receipt),
+            ) => &receipt.logs,
+            None => self
+                .status
+                .as_ref()
+                .map(|status| &status.logs[..])
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Gas used by the block up to and including the receipt's transaction
+fn cumulative_gas_used(receipt: &ReceiptAny) -> U256 {
+    match receipt {
+        ReceiptAny::Frontier(receipt) => receipt.used_gas,
+        ReceiptAny::EIP658(receipt)
+        | ReceiptAny::EIP2930(receipt)
+        | ReceiptAny::EIP1559(receipt)
+        | ReceiptAny::EIP7702(receipt) => receipt.used_gas,
+    }
+}
+
+/// Match receipts (stored in transaction order) and statuses (matched by hash) to the transactions
+pub fn match_transactions(
+    transactions: Vec<TransactionV3>,
+    receipts: Vec<ReceiptAny>,
+    mut statuses: Vec<TransactionStatus>,
+) -> Vec<BlockTransaction> {
+    let mut receipts = receipts.into_iter();
+    let mut previous_cumulative_gas_used = U256::zero();
+
+    transactions
+        .into_iter()
+        .map(|transaction| {
+            let hash = transaction.hash();
+            let status = statuses
+                .iter()
+                .position(|status| status.transaction_hash == hash)
+                .map(|index| statuses.swap_remove(index));
+
+            let receipt = receipts.next();
+            // Receipts store the cumulative gas used of the block
+            let gas_used = receipt.as_ref().map(|receipt| {
+                let cumulative_gas_used = cumulative_gas_used(receipt);
+                let gas_used = cumulative_gas_used.saturating_sub(previous_cumulative_gas_used);
+                previous_cumulative_gas_used = cumulative_gas_used;
+                gas_used
+            });
+
+            BlockTransaction {
+                transaction,
+                receipt,
+                status,
+                gas_used,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ethereum::{LegacyTransaction, TransactionAction, legacy::TransactionSignature};
+
+    use super::*;
+
+    #[test]
+    fn recovers_the_sender_of_the_eip155_example() {
+        // Signed with the private key 0x4646...46 in the example of EIP-155
+        let transaction = TransactionV3::Legacy(LegacyTransaction {
+            nonce: U256::from(9),
+            gas_price: U256::from(20_000_000_000u64),
+            gas_limit: U256::from(21_000),
+            action: TransactionAction::Call(
+                "0x3535353535353535353535353535353535353535"
+                    .parse()
+                    .unwrap(),
+            ),
+            value: U256::from(1_000_000_000_000_000_000u64),
+            input: vec![],
+            signature: TransactionSignature::new(
+                37,
+                "0x28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276"
+                    .parse()
+                    .unwrap(),
+                "0x67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"
+                    .parse()
+                    .unwrap(),
+            )
+            .unwrap(),
+        });
+
+        assert_eq!(
+            recover_sender(&transaction).unwrap(),
+            "0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f"
+                .parse()
+                .unwrap()
+        );
+    }
+}
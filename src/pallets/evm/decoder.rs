@@ -0,0 +1,45 @@
+use ethereum::Log;
+use scale_value::Composite;
+
+use crate::{
+    decoder::value_decoder::{
+        ValueDecoder, ValueDecoderError, composite_to_elements, composite_to_record, get_element,
+        get_field,
+    },
+    pallets::system::decoder::Event,
+};
+
+pub const PALLET_NAME: &str = "EVM";
+
+/// `frame_system::AccountInfo` of an EVM account, the nonce is the EVM transaction count
+#[derive(Debug, Clone, Default, ValueDecoder)]
+pub struct AccountInfo {
+    pub nonce: u64,
+    pub data: AccountData,
+}
+
+/// `pallet_balances::AccountData`, `frozen` is missing before the fungible balances migration
+#[derive(Debug, Clone, Default, ValueDecoder)]
+pub struct AccountData {
+    pub free: u128,
+    pub reserved: u128,
+    #[value_decoder(default)]
+    pub frozen: u128,
+}
+
+/// Decode the log of an `EVM.Log` event, emitted for logs of contracts called through pallet-evm
+/// Returns `None` for any other event
+pub fn decode_log_event(event: &Event) -> Option<Result<Log, ValueDecoderError>> {
+    if event.name != PALLET_NAME || event.action != "Log" {
+        return None;
+    }
+
+    // Older runtimes emit `Log(Log)`, newer ones `Log { log: Log }`
+    Some(match event.values.clone() {
+        Composite::Named(named) => composite_to_record(Composite::Named(named))
+            .and_then(|mut record| get_field("log", &mut record)),
+        unnamed => {
+            composite_to_elements(unnamed, 1).and_then(|mut elements| get_element(0, &mut elements))
+        }
+    })
+}
@@ -0,0 +1,193 @@
+use ethereum_types::{H160, H256};
+use sha3::{Digest, Keccak256};
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+use crate::{
+    decoder::metadata::AnyRuntimeMetadata,
+    error::Error,
+    fetch::fetch_optional,
+    node_rpc::{
+        client::NodeRPC,
+        models::{BlockHashHex, RuntimeVersion},
+    },
+    pallets::{
+        evm::decoder::{AccountInfo, PALLET_NAME},
+        schema::{StorageSchema, StorageTypes},
+        system,
+    },
+};
+
+pub const ACCOUNT_CODES: StorageSchema<()> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "AccountCodes",
+    shapes: &[
+        (
+            (),
+            StorageTypes {
+                keys: &["H160"],
+                value: "Vec<u8>",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &["primitive_types::H160"],
+                value: "Vec<u8>",
+            },
+        ),
+    ],
+};
+
+pub const ACCOUNT_STORAGES: StorageSchema<()> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "AccountStorages",
+    shapes: &[
+        (
+            (),
+            StorageTypes {
+                keys: &["H160", "H256"],
+                value: "H256",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &["primitive_types::H160", "primitive_types::H256"],
+                value: "primitive_types::H256",
+            },
+        ),
+    ],
+};
+
+/// How the runtime maps an H160 address to its `System.Account` key
+#[derive(Debug, Clone, Copy)]
+pub enum AccountShape {
+    /// Runtimes with 20 bytes account ids, the address is the account id
+    AccountId20,
+    /// Runtimes with 32 bytes account ids using pallet-evm's `HashedAddressMapping`
+    HashedAddressMapping,
+}
+
+/// `System.Account` of EVM accounts, only Frontier runtimes map H160 addresses to accounts
+pub const ACCOUNT: StorageSchema<AccountShape> = StorageSchema {
+    pallet_name: system::fetch::PALLET_NAME,
+    storage_entry_name: "Account",
+    shapes: &[
+        (
+            AccountShape::AccountId20,
+            StorageTypes {
+                keys: &["account::AccountId20"],
+                value: "frame_system::AccountInfo<u32, pallet_balances::AccountData<u128>>",
+            },
+        ),
+        (
+            AccountShape::AccountId20,
+            StorageTypes {
+                keys: &["account::AccountId20"],
+                value: "frame_system::AccountInfo<u32, pallet_balances::types::AccountData<u128>>",
+            },
+        ),
+        (
+            AccountShape::AccountId20,
+            StorageTypes {
+                keys: &["fp_account::AccountId20"],
+                value: "frame_system::AccountInfo<u32, pallet_balances::types::AccountData<u128>>",
+            },
+        ),
+        (
+            AccountShape::HashedAddressMapping,
+            StorageTypes {
+                keys: &["sp_core::crypto::AccountId32"],
+                value: "frame_system::AccountInfo<u32, pallet_balances::AccountData<u128>>",
+            },
+        ),
+        (
+            AccountShape::HashedAddressMapping,
+            StorageTypes {
+                keys: &["sp_core::crypto::AccountId32"],
+                value: "frame_system::AccountInfo<u32, pallet_balances::types::AccountData<u128>>",
+            },
+        ),
+    ],
+};
+
+/// Fetch the code of a contract, empty for externally owned accounts
+pub async fn fetch_account_code(
+    rpc: &NodeRPC,
+    address: &H160,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<Vec<u8>, Error> {
+    ACCOUNT_CODES.resolve(metadata)?;
+
+    let code = fetch_optional(
+        PALLET_NAME,
+        ACCOUNT_CODES.storage_entry_name,
+        [address.0],
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await?;
+    Ok(code.unwrap_or_default())
+}
+
+/// Keccak-256 hash of the contract code, as returned by `EXTCODEHASH`
+pub fn code_hash(code: &[u8]) -> H256 {
+    H256::from_slice(&Keccak256::digest(code))
+}
+
+/// Fetch a contract storage slot, zero if it was never written
+pub async fn fetch_account_storage(
+    rpc: &NodeRPC,
+    address: &H160,
+    slot: &H256,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<H256, Error> {
+    ACCOUNT_STORAGES.resolve(metadata)?;
+
+    let value = fetch_optional(
+        PALLET_NAME,
+        ACCOUNT_STORAGES.storage_entry_name,
+        (address.0, slot.0),
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await?;
+    Ok(value.unwrap_or_default())
+}
+
+/// Fetch the balance and nonce of an EVM account
+pub async fn fetch_account(
+    rpc: &NodeRPC,
+    address: &H160,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<AccountInfo, Error> {
+    let account_id = match ACCOUNT.resolve(metadata)? {
+        AccountShape::AccountId20 => address.0.to_vec(),
+        AccountShape::HashedAddressMapping => {
+            let preimage = [&b"evm:"[..], address.as_bytes()].concat();
+            BlakeTwo256::hash(&preimage).0.to_vec()
+        }
+    };
+
+    let account = fetch_optional(
+        system::fetch::PALLET_NAME,
+        ACCOUNT.storage_entry_name,
+        [account_id],
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await?;
+    Ok(account.unwrap_or_default())
+}
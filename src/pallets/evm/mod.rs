@@ -0,0 +1,4 @@
+pub mod decoder;
+pub mod fetch;
+
+pub use fetch::{code_hash, fetch_account, fetch_account_code, fetch_account_storage};
@@ -0,0 +1,10 @@
+use parity_scale_codec::Decode;
+
+use crate::{decoder::value_decoder::ValueDecoder, pallets::session::decoder::decode_public_key};
+
+/// GRANDPA authority ed25519 public key and its weight
+#[derive(Debug, Clone, Decode, ValueDecoder)]
+pub struct GrandpaAuthority(
+    #[value_decoder(with = "decode_public_key")] pub [u8; 32],
+    pub u64,
+);
@@ -0,0 +1,125 @@
+use parity_scale_codec::Decode;
+use tracing::warn;
+
+use crate::{
+    decoder::metadata::{AnyRuntimeMetadata, MetadataError},
+    error::Error,
+    fetch::{fetch, fetch_storage_value},
+    node_rpc::{
+        client::NodeRPC,
+        models::{BlockHashHex, RuntimeVersion, StorageKeyHex, StorageValueBytes},
+    },
+    pallets::{
+        grandpa::decoder::GrandpaAuthority,
+        schema::{StorageSchema, StorageTypes},
+    },
+};
+
+pub const PALLET_NAME: &str = "Grandpa";
+
+/// Well-known storage key of the authority set, written by runtimes before `Grandpa.Authorities`
+pub const AUTHORITIES_KEY: &[u8] = b":grandpa_authorities";
+
+/// Older runtimes only keep the authority set under the `:grandpa_authorities` well-known key
+pub const AUTHORITIES: StorageSchema<()> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "Authorities",
+    shapes: &[(
+        (),
+        StorageTypes {
+            keys: &[],
+            value: "bounded_collections::weak_bounded_vec::WeakBoundedVec<(sp_consensus_grandpa::app::Public, u64)>",
+        },
+    )],
+};
+
+/// The set id type was declared under different names by the first runtimes with GRANDPA
+pub const CURRENT_SET_ID: StorageSchema<()> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "CurrentSetId",
+    shapes: &[
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "SetId",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "fg_primitives::SetId",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "u64",
+            },
+        ),
+    ],
+};
+
+/// Authority set of the block, from `Grandpa.Authorities` or the `:grandpa_authorities`
+/// well-known key, also read when `Grandpa.Authorities` has an unknown type
+pub async fn fetch_authorities(
+    rpc: &NodeRPC,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<Vec<GrandpaAuthority>, Error> {
+    match AUTHORITIES.resolve(metadata) {
+        Ok(()) => {
+            return fetch(
+                PALLET_NAME,
+                AUTHORITIES.storage_entry_name,
+                (),
+                rpc,
+                block_hash,
+                metadata,
+                runtime_version,
+            )
+            .await;
+        }
+        Err(MetadataError::MetadataNotFound(_)) => {}
+        Err(error) => warn!(%error, "reading the authority set from the well-known key"),
+    }
+
+    let key_hex = StorageKeyHex::from(AUTHORITIES_KEY.to_vec());
+    let StorageValueBytes(storage_bytes) = fetch_storage_value(rpc, &key_hex, block_hash)
+        .await?
+        .ok_or(Error::StorageValueNotFound {
+            pallet_name: PALLET_NAME.to_string(),
+            storage_entry_name: String::from_utf8_lossy(AUTHORITIES_KEY).into_owned(),
+            storage_entry_keys: None,
+            storage_entry_key_hash: key_hex,
+        })?;
+
+    // `VersionedAuthorityList`, a version byte followed by the authority list
+    let (_version, authorities) = <(u8, Vec<GrandpaAuthority>)>::decode(&mut &storage_bytes[..])
+        .map_err(Error::ParsingGrandpaAuthoritiesFailed)?;
+    Ok(authorities)
+}
+
+/// Id of the authority set, part of the message signed by GRANDPA voters
+pub async fn fetch_current_set_id(
+    rpc: &NodeRPC,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<u64, Error> {
+    CURRENT_SET_ID.resolve(metadata)?;
+
+    fetch(
+        PALLET_NAME,
+        CURRENT_SET_ID.storage_entry_name,
+        (),
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await
+}
@@ -0,0 +1,238 @@
+use std::collections::{HashMap, HashSet};
+
+use derive_more::Display;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use parity_scale_codec::{Decode, Encode};
+use sp_runtime::{generic::Header, traits::BlakeTwo256};
+
+use crate::{node_rpc::models::BlockHashHex, pallets::grandpa::decoder::GrandpaAuthority};
+
+pub const GRANDPA_ENGINE_ID: [u8; 4] = *b"FRNK";
+
+/// Index of the `Precommit` variant of `finality_grandpa::Message`
+const PRECOMMIT_MESSAGE_INDEX: u8 = 1;
+
+pub type GrandpaHeader = Header<u32, BlakeTwo256>;
+
+/// `finality_grandpa::Precommit`
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct Precommit {
+    pub target_hash: [u8; 32],
+    pub target_number: u32,
+}
+
+/// `finality_grandpa::SignedPrecommit` with an ed25519 signature
+#[derive(Debug, Clone, Decode)]
+pub struct SignedPrecommit {
+    pub precommit: Precommit,
+    pub signature: [u8; 64],
+    pub id: [u8; 32],
+}
+
+/// `finality_grandpa::Commit`
+#[derive(Debug, Clone, Decode)]
+pub struct Commit {
+    pub target_hash: [u8; 32],
+    pub target_number: u32,
+    pub precommits: Vec<SignedPrecommit>,
+}
+
+/// `sc_consensus_grandpa::GrandpaJustification`
+///
+/// Precommits may target descendants of the commit target, `votes_ancestries` holds the headers
+/// linking them back to it.
+#[derive(Debug, Clone, Decode)]
+pub struct GrandpaJustification {
+    pub round: u64,
+    pub commit: Commit,
+    pub votes_ancestries: Vec<GrandpaHeader>,
+}
+
+/// `sc_consensus_grandpa::FinalityProof`, returned by `grandpa_proveFinality`
+///
+/// The justification may finalize a descendant of the requested block, `unknown_headers` holds the
+/// headers from the requested block up to the justified block.
+#[derive(Debug, Clone, Decode)]
+pub struct FinalityProof {
+    pub block: [u8; 32],
+    pub justification: Vec<u8>,
+    pub unknown_headers: Vec<GrandpaHeader>,
+}
+
+impl FinalityProof {
+    /// Whether the headers of the proof link the justified block back to the block
+    pub fn proves(&self, block_hash: &BlockHashHex) -> bool {
+        let parent_by_hash = self
+            .unknown_headers
+            .iter()
+            .map(|header| (header.hash().0, header.parent_hash.0))
+            .collect::<HashMap<_, _>>();
+
+        let mut hash = self.block;
+        loop {
+            if BlockHashHex::from(hash) == *block_hash {
+                return true;
+            }
+            match parent_by_hash.get(&hash) {
+                Some(parent_hash) => hash = *parent_hash,
+                None => return false,
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum PrecommitStatus {
+    #[display("valid")]
+    Valid,
+    #[display("invalid signature")]
+    InvalidSignature,
+    #[display("not an authority")]
+    UnknownAuthority,
+    #[display("duplicate")]
+    Duplicate,
+    #[display("not a descendant of the target")]
+    NotDescendant,
+}
+
+#[derive(Debug, Clone)]
+pub struct VerifiedPrecommit {
+    pub authority: [u8; 32],
+    pub target_number: u32,
+    pub status: PrecommitStatus,
+}
+
+/// Outcome of checking every precommit of a justification against an authority set
+#[derive(Debug, Clone)]
+pub struct JustificationVerification {
+    pub precommits: Vec<VerifiedPrecommit>,
+    pub signed_weight: u64,
+    pub total_weight: u64,
+}
+
+impl JustificationVerification {
+    /// Supermajority weight, the authority set tolerates `(total - 1) / 3` faulty weight
+    pub fn threshold(&self) -> u64 {
+        self.total_weight - self.total_weight.saturating_sub(1) / 3
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.total_weight > 0 && self.signed_weight >= self.threshold()
+    }
+
+    /// Precommit of an authority, the valid one if it signed several
+    pub fn precommit_of(&self, authority: &[u8; 32]) -> Option<&VerifiedPrecommit> {
+        let mut precommits = self
+            .precommits
+            .iter()
+            .filter(|precommit| precommit.authority == *authority);
+        let first = precommits.next()?;
+
+        Some(
+            std::iter::once(first)
+                .chain(precommits)
+                .find(|precommit| precommit.status == PrecommitStatus::Valid)
+                .unwrap_or(first),
+        )
+    }
+}
+
+impl GrandpaJustification {
+    fn is_descendant_of_target(
+        &self,
+        parent_by_hash: &HashMap<[u8; 32], [u8; 32]>,
+        mut hash: [u8; 32],
+    ) -> bool {
+        loop {
+            if hash == self.commit.target_hash {
+                return true;
+            }
+            match parent_by_hash.get(&hash) {
+                Some(parent_hash) => hash = *parent_hash,
+                None => return false,
+            }
+        }
+    }
+
+    /// Verify the precommit signatures against the authority set and sum the weight of the
+    /// authorities voting for the target or one of its descendants
+    pub fn verify(
+        &self,
+        set_id: u64,
+        authorities: &[GrandpaAuthority],
+    ) -> JustificationVerification {
+        let weight_by_authority = authorities
+            .iter()
+            .map(|GrandpaAuthority(public_key, weight)| (*public_key, *weight))
+            .collect::<HashMap<_, _>>();
+        let parent_by_hash = self
+            .votes_ancestries
+            .iter()
+            .map(|header| (header.hash().0, header.parent_hash.0))
+            .collect::<HashMap<_, _>>();
+
+        let mut signers = HashSet::new();
+        let mut signed_weight = 0;
+
+        let precommits = self
+            .commit
+            .precommits
+            .iter()
+            .map(|signed_precommit| {
+                let status = match weight_by_authority.get(&signed_precommit.id) {
+                    None => PrecommitStatus::UnknownAuthority,
+                    Some(_) if !self.is_signature_valid(signed_precommit, set_id) => {
+                        PrecommitStatus::InvalidSignature
+                    }
+                    Some(_) if signers.contains(&signed_precommit.id) => PrecommitStatus::Duplicate,
+                    Some(_)
+                        if !self.is_descendant_of_target(
+                            &parent_by_hash,
+                            signed_precommit.precommit.target_hash,
+                        ) =>
+                    {
+                        PrecommitStatus::NotDescendant
+                    }
+                    Some(weight) => {
+                        signers.insert(signed_precommit.id);
+                        signed_weight += weight;
+                        PrecommitStatus::Valid
+                    }
+                };
+
+                VerifiedPrecommit {
+                    authority: signed_precommit.id,
+                    target_number: signed_precommit.precommit.target_number,
+                    status,
+                }
+            })
+            .collect();
+
+        JustificationVerification {
+            precommits,
+            signed_weight,
+            total_weight: weight_by_authority.values().sum(),
+        }
+    }
+
+    /// The voter signs the SCALE encoded `(Message::Precommit(precommit), round, set_id)`
+    fn is_signature_valid(&self, signed_precommit: &SignedPrecommit, set_id: u64) -> bool {
+        let Ok(public_key) = VerifyingKey::from_bytes(&signed_precommit.id) else {
+            return false;
+        };
+        let message = (
+            PRECOMMIT_MESSAGE_INDEX,
+            &signed_precommit.precommit,
+            self.round,
+            set_id,
+        )
+            .encode();
+
+        public_key
+            .verify(
+                &message,
+                &Signature::from_bytes(&signed_precommit.signature),
+            )
+            .is_ok()
+    }
+}
@@ -0,0 +1,5 @@
+pub mod decoder;
+pub mod fetch;
+pub mod justification;
+
+pub use fetch::{fetch_authorities, fetch_current_set_id};
@@ -1,4 +1,47 @@
+use crate::pallets::schema::{
+    Requirement::{self, Optional, Required},
+    SchemaCheck,
+};
+
+pub mod aura;
+pub mod babe;
+pub mod base_fee;
+pub mod collator_selection;
 pub mod ethereum;
+pub mod evm;
+pub mod grandpa;
+pub mod para_inclusion;
+pub mod para_inherent;
+pub mod parachain_info;
+pub mod parachain_system;
+pub mod schema;
 pub mod session;
 pub mod system;
 pub mod timestamp;
+
+/// Storage schemas of every pallet module, validated against the runtime before decoding a block
+///
+/// A runtime failing an optional schema still decodes its blocks, without the feature the storage
+/// entry backs.
+pub const STORAGE_SCHEMAS: &[(Requirement, &dyn SchemaCheck)] = &[
+    (Optional, &babe::fetch::EPOCH_INDEX),
+    (Optional, &babe::fetch::AUTHORITIES),
+    (Optional, &babe::fetch::NEXT_EPOCH_CONFIG),
+    (Optional, &aura::fetch::AUTHORITIES),
+    (Optional, &base_fee::fetch::BASE_FEE_PER_GAS),
+    (Optional, &collator_selection::fetch::INVULNERABLES),
+    (Optional, &collator_selection::fetch::CANDIDATE_LIST),
+    (Optional, &collator_selection::fetch::CANDIDATES),
+    (Optional, &ethereum::fetch::BLOCK_HASH),
+    (Optional, &ethereum::fetch::CURRENT_BLOCK),
+    (Optional, &ethereum::fetch::CURRENT_RECEIPTS),
+    (Optional, &ethereum::fetch::CURRENT_TRANSACTION_STATUSES),
+    (Optional, &evm::fetch::ACCOUNT_CODES),
+    (Optional, &evm::fetch::ACCOUNT_STORAGES),
+    (Optional, &evm::fetch::ACCOUNT),
+    (Optional, &grandpa::fetch::AUTHORITIES),
+    (Optional, &grandpa::fetch::CURRENT_SET_ID),
+    (Optional, &parachain_info::fetch::PARACHAIN_ID),
+    (Optional, &session::fetch::KEY_OWNER),
+    (Required, &timestamp::fetch::NOW),
+];
@@ -0,0 +1,81 @@
+use derive_more::Display;
+use scale_decode::DecodeAsType;
+use scale_decode::ext::primitive_types::H256;
+
+use crate::{
+    decoder::value_decoder::{ValueDecoder, ValueDecoderError, composite_to_elements, get_element},
+    pallets::{
+        parachain_info::decoder::ParaId, parachain_system::decoder::HeadData,
+        system::decoder::Event,
+    },
+};
+
+pub const PALLET_NAME: &str = "ParaInclusion";
+
+/// Fields shared by `CandidateDescriptor` and `CandidateDescriptorV2`
+#[allow(dead_code)]
+#[derive(Debug, Clone, ValueDecoder, DecodeAsType)]
+pub struct CandidateDescriptor {
+    pub para_id: ParaId,
+    pub relay_parent: H256,
+    /// Hash of the parablock header
+    pub para_head: H256,
+}
+
+/// `polkadot_primitives::CandidateReceipt`
+#[derive(Debug, Clone, ValueDecoder)]
+pub struct CandidateReceipt {
+    pub descriptor: CandidateDescriptor,
+}
+
+/// `polkadot_primitives::CoreIndex`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, ValueDecoder)]
+pub struct CoreIndex(pub u32);
+
+/// `polkadot_primitives::GroupIndex`, the backing group of validators
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, ValueDecoder)]
+pub struct GroupIndex(pub u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum CandidateStatus {
+    Backed,
+    Included,
+}
+
+/// A `ParaInclusion.CandidateBacked` or `ParaInclusion.CandidateIncluded` event
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct CandidateEvent {
+    pub status: CandidateStatus,
+    pub receipt: CandidateReceipt,
+    pub head_data: HeadData,
+    pub core_index: CoreIndex,
+    pub group_index: GroupIndex,
+}
+
+/// Decode a `ParaInclusion.CandidateBacked` or `ParaInclusion.CandidateIncluded` event, emitted on
+/// the relay chain when a parablock is backed or included
+/// Returns `None` for any other event
+pub fn decode_candidate_event(event: &Event) -> Option<Result<CandidateEvent, ValueDecoderError>> {
+    if event.name != PALLET_NAME {
+        return None;
+    }
+    let status = match event.action.as_str() {
+        "CandidateBacked" => CandidateStatus::Backed,
+        "CandidateIncluded" => CandidateStatus::Included,
+        _ => return None,
+    };
+
+    // `(CandidateReceipt, HeadData, CoreIndex, GroupIndex)`
+    Some(
+        composite_to_elements(event.values.clone(), 4).and_then(|mut elements| {
+            Ok(CandidateEvent {
+                status,
+                receipt: get_element(0, &mut elements)?,
+                head_data: get_element(1, &mut elements)?,
+                core_index: get_element(2, &mut elements)?,
+                group_index: get_element(3, &mut elements)?,
+            })
+        }),
+    )
+}
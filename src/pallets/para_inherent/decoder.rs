@@ -0,0 +1,156 @@
+use scale_decode::DecodeAsType;
+use scale_value::Value;
+
+use crate::pallets::para_inclusion::decoder::CandidateDescriptor;
+
+pub const PALLET_NAME: &str = "ParaInherent";
+
+/// Messages sent to the relay chain, a `BoundedVec` of SCALE encoded `xcm::VersionedXcm`s
+#[derive(Debug, Clone, DecodeAsType)]
+pub struct UpwardMessages(pub Vec<Vec<u8>>);
+
+/// `polkadot_primitives::CandidateCommitments`
+#[derive(Debug, Clone, DecodeAsType)]
+pub struct CandidateCommitments {
+    pub upward_messages: UpwardMessages,
+}
+
+/// `polkadot_primitives::CommittedCandidateReceipt`
+#[derive(Debug, Clone, DecodeAsType)]
+pub struct CommittedCandidateReceipt {
+    pub descriptor: CandidateDescriptor,
+    pub commitments: CandidateCommitments,
+}
+
+/// `polkadot_primitives::BackedCandidate`
+#[derive(Debug, Clone, DecodeAsType)]
+pub struct BackedCandidate {
+    pub candidate: CommittedCandidateReceipt,
+}
+
+/// `polkadot_primitives::InherentData`, the bitfields and disputes are only counted
+#[derive(Debug, Clone, DecodeAsType)]
+struct InherentData {
+    bitfields: Vec<Value>,
+    backed_candidates: Vec<BackedCandidate>,
+    disputes: Vec<Value>,
+}
+
+/// Arguments of the `ParaInherent.enter` call, decoded straight from the SCALE bytes
+#[derive(Debug, Clone, DecodeAsType)]
+pub struct EnterArgs {
+    data: InherentData,
+}
+
+/// Argument of the `ParaInherent.enter` inherent of a relay chain block
+#[derive(Debug, Clone)]
+pub struct ParaInherentData {
+    pub backed_candidates: Vec<BackedCandidate>,
+    /// Availability bitfields signed by the validators
+    pub bitfield_count: usize,
+    pub dispute_count: usize,
+}
+
+impl From<EnterArgs> for ParaInherentData {
+    fn from(EnterArgs { data }: EnterArgs) -> Self {
+        Self {
+            backed_candidates: data.backed_candidates,
+            bitfield_count: data.bitfields.len(),
+            dispute_count: data.disputes.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_scale_codec::Encode;
+    use scale_info::{PortableRegistry, Registry, TypeInfo, meta_type};
+
+    use super::*;
+    use crate::decoder::extrinsic::decode_args_as;
+
+    // Shapes of the `polkadot_primitives` types, with fields the decoders skip
+
+    #[derive(Encode, TypeInfo)]
+    struct Id(u32);
+
+    #[derive(Encode, TypeInfo)]
+    struct Descriptor {
+        para_id: Id,
+        relay_parent: [u8; 32],
+        collator: [u8; 32],
+        para_head: [u8; 32],
+    }
+
+    #[derive(Encode, TypeInfo)]
+    struct BoundedVec(Vec<Vec<u8>>);
+
+    #[derive(Encode, TypeInfo)]
+    struct Commitments {
+        upward_messages: BoundedVec,
+        head_data: Vec<u8>,
+    }
+
+    #[derive(Encode, TypeInfo)]
+    struct Receipt {
+        descriptor: Descriptor,
+        commitments: Commitments,
+    }
+
+    #[derive(Encode, TypeInfo)]
+    struct Backed {
+        candidate: Receipt,
+        validity_votes: Vec<u8>,
+    }
+
+    #[derive(Encode, TypeInfo)]
+    struct Data {
+        bitfields: Vec<Vec<u8>>,
+        backed_candidates: Vec<Backed>,
+        disputes: Vec<u32>,
+        parent_header: u32,
+    }
+
+    #[test]
+    fn enter_args_round_trip_from_scale_bytes() {
+        let data = Data {
+            bitfields: vec![vec![1], vec![0], vec![1]],
+            backed_candidates: vec![Backed {
+                candidate: Receipt {
+                    descriptor: Descriptor {
+                        para_id: Id(2004),
+                        relay_parent: [1; 32],
+                        collator: [2; 32],
+                        para_head: [3; 32],
+                    },
+                    commitments: Commitments {
+                        upward_messages: BoundedVec(vec![vec![4, 5]]),
+                        head_data: vec![6],
+                    },
+                },
+                validity_votes: vec![7],
+            }],
+            disputes: vec![],
+            parent_header: 8,
+        };
+
+        let mut registry = Registry::new();
+        let data_type = registry.register_type(&meta_type::<Data>()).id;
+        let types = PortableRegistry::from(registry);
+
+        let args: EnterArgs =
+            decode_args_as(&data.encode(), [("data", data_type)].into_iter(), &types).unwrap();
+        let para_inherent = ParaInherentData::from(args);
+
+        assert_eq!(para_inherent.bitfield_count, 3);
+        assert_eq!(para_inherent.dispute_count, 0);
+        let [backed_candidate] = &para_inherent.backed_candidates[..] else {
+            panic!("expected one backed candidate");
+        };
+        let candidate = &backed_candidate.candidate;
+        assert_eq!(candidate.descriptor.para_id.0, 2004);
+        assert_eq!(candidate.descriptor.relay_parent.0, [1; 32]);
+        assert_eq!(candidate.descriptor.para_head.0, [3; 32]);
+        assert_eq!(candidate.commitments.upward_messages.0, vec![vec![4, 5]]);
+    }
+}
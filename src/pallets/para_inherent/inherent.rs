@@ -0,0 +1,25 @@
+use crate::{
+    decoder::{extrinsic::decode_extrinsic_any, metadata::AnyRuntimeMetadata},
+    error::Error,
+    node_rpc::models::ExtrinsicBytes,
+    pallets::para_inherent::decoder::{EnterArgs, PALLET_NAME, ParaInherentData},
+};
+
+/// Find and decode the `ParaInherent.enter` inherent of a relay chain block
+pub fn find_para_inherent(
+    extrinsics: &[ExtrinsicBytes],
+    metadata: AnyRuntimeMetadata<'_>,
+    spec_version: u64,
+) -> Result<Option<ParaInherentData>, Error> {
+    for ExtrinsicBytes(ext) in extrinsics {
+        let extrinsic = decode_extrinsic_any(ext, metadata, spec_version)?;
+        if extrinsic.pallet_name() != PALLET_NAME || extrinsic.call_name() != "enter" {
+            continue;
+        }
+
+        let args: EnterArgs = extrinsic.call_args_as(ext, metadata)?;
+        return Ok(Some(args.into()));
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,10 @@
+use derive_more::Display;
+use scale_decode::DecodeAsType;
+
+use crate::decoder::value_decoder::ValueDecoder;
+
+/// `polkadot_parachain_primitives::primitives::Id`
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display, ValueDecoder, DecodeAsType,
+)]
+pub struct ParaId(pub u32);
@@ -0,0 +1,57 @@
+use crate::{
+    decoder::metadata::AnyRuntimeMetadata,
+    error::Error,
+    fetch::fetch_as,
+    node_rpc::{
+        client::NodeRPC,
+        models::{BlockHashHex, RuntimeVersion},
+    },
+    pallets::{
+        parachain_info::decoder::ParaId,
+        schema::{StorageSchema, StorageTypes},
+    },
+};
+
+pub const PALLET_NAME: &str = "ParachainInfo";
+
+/// Only read for the parachain view
+pub const PARACHAIN_ID: StorageSchema<()> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "ParachainId",
+    shapes: &[
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "polkadot_parachain::primitives::Id",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "polkadot_parachain_primitives::primitives::Id",
+            },
+        ),
+    ],
+};
+
+pub async fn fetch_parachain_id(
+    rpc: &NodeRPC,
+    block_hash: &BlockHashHex,
+    metadata: AnyRuntimeMetadata<'_>,
+    runtime_version: &RuntimeVersion,
+) -> Result<ParaId, Error> {
+    PARACHAIN_ID.resolve(metadata)?;
+
+    fetch_as(
+        PALLET_NAME,
+        PARACHAIN_ID.storage_entry_name,
+        (),
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await
+}
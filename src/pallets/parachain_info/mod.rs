@@ -0,0 +1,4 @@
+pub mod decoder;
+pub mod fetch;
+
+pub use fetch::fetch_parachain_id;
@@ -0,0 +1,96 @@
+use std::collections::{BTreeMap, HashMap};
+
+use scale_decode::ext::primitive_types::H256;
+use scale_value::Value;
+
+use crate::{
+    decoder::value_decoder::{ValueDecoder, ValueDecoderError, get_field},
+    pallets::parachain_info::decoder::ParaId,
+};
+
+pub const PALLET_NAME: &str = "ParachainSystem";
+
+/// SCALE encoded header of the parent parablock
+#[allow(dead_code)]
+#[derive(Debug, Clone, ValueDecoder)]
+pub struct HeadData(pub Vec<u8>);
+
+/// `polkadot_primitives::PersistedValidationData`
+#[allow(dead_code)]
+#[derive(Debug, Clone, ValueDecoder)]
+pub struct PersistedValidationData {
+    pub parent_head: HeadData,
+    pub relay_parent_number: u32,
+    pub relay_parent_storage_root: H256,
+    pub max_pov_size: u32,
+}
+
+/// `polkadot_core_primitives::InboundDownwardMessage`
+#[allow(dead_code)]
+#[derive(Debug, Clone, ValueDecoder)]
+pub struct InboundDownwardMessage {
+    pub sent_at: u32,
+    pub msg: Vec<u8>,
+}
+
+/// `polkadot_core_primitives::InboundHrmpMessage`
+#[allow(dead_code)]
+#[derive(Debug, Clone, ValueDecoder)]
+pub struct InboundHrmpMessage {
+    pub sent_at: u32,
+    pub data: Vec<u8>,
+}
+
+/// Arguments of the `ParachainSystem.set_validation_data` inherent
+#[derive(Debug, Clone)]
+pub struct ValidationDataInherent {
+    pub validation_data: PersistedValidationData,
+    pub downward_messages: Vec<InboundDownwardMessage>,
+    /// Messages by sender parachain
+    pub horizontal_messages: Vec<(ParaId, InboundHrmpMessage)>,
+}
+
+impl ValidationDataInherent {
+    /// Older runtimes take a single `data: ParachainInherentData` argument with every message,
+    /// newer ones split the messages into `inbound_messages_data: InboundMessagesData`
+    pub fn decode_args<T>(args: Vec<(String, Value<T>)>) -> Result<Self, ValueDecoderError>
+    where
+        T: std::fmt::Debug,
+    {
+        let mut args = args.into_iter().collect::<HashMap<_, _>>();
+        let mut data: HashMap<String, Value<T>> = get_field("data", &mut args)?;
+        let validation_data = get_field("validation_data", &mut data)?;
+
+        match args.remove("inbound_messages_data") {
+            Some(inbound_messages_data) => {
+                let mut inbound_messages_data = HashMap::decode(inbound_messages_data)?;
+                let mut downward_messages: HashMap<String, Value<T>> =
+                    get_field("downward_messages", &mut inbound_messages_data)?;
+                let mut horizontal_messages: HashMap<String, Value<T>> =
+                    get_field("horizontal_messages", &mut inbound_messages_data)?;
+
+                // Hashed messages don't fit in the block and are only processed by later blocks
+                Ok(Self {
+                    validation_data,
+                    downward_messages: get_field("full_messages", &mut downward_messages)?,
+                    horizontal_messages: get_field("full_messages", &mut horizontal_messages)?,
+                })
+            }
+            None => {
+                let horizontal_messages: BTreeMap<ParaId, Vec<InboundHrmpMessage>> =
+                    get_field("horizontal_messages", &mut data)?;
+
+                Ok(Self {
+                    validation_data,
+                    downward_messages: get_field("downward_messages", &mut data)?,
+                    horizontal_messages: horizontal_messages
+                        .into_iter()
+                        .flat_map(|(sender, messages)| {
+                            messages.into_iter().map(move |message| (sender, message))
+                        })
+                        .collect(),
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{
+    decoder::{
+        extrinsic::decode_extrinsic_any, metadata::AnyRuntimeMetadata, value_decoder::WithErrorSpan,
+    },
+    error::Error,
+    node_rpc::models::ExtrinsicBytes,
+    pallets::parachain_system::decoder::{PALLET_NAME, ValidationDataInherent},
+};
+
+/// Find and decode the `ParachainSystem.set_validation_data` inherent of a Cumulus parablock
+pub fn find_validation_data(
+    extrinsics: &[ExtrinsicBytes],
+    metadata: AnyRuntimeMetadata<'_>,
+    spec_version: u64,
+) -> Result<Option<ValidationDataInherent>, Error> {
+    for ExtrinsicBytes(ext) in extrinsics {
+        let extrinsic = decode_extrinsic_any(ext, metadata, spec_version)?;
+        if extrinsic.pallet_name() != PALLET_NAME || extrinsic.call_name() != "set_validation_data"
+        {
+            continue;
+        }
+
+        let args = extrinsic.call_args(ext, metadata)?;
+        return Ok(Some(
+            ValidationDataInherent::decode_args(args).add_error_span("set_validation_data")?,
+        ));
+    }
+
+    Ok(None)
+}
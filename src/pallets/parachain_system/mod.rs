@@ -0,0 +1,4 @@
+pub mod decoder;
+pub mod inherent;
+
+pub use inherent::find_validation_data;
@@ -0,0 +1,189 @@
+use crate::decoder::metadata::{AnyRuntimeMetadata, MetadataError};
+
+/// Key and value types of a storage entry, as rendered by `AnyStorageEntry::types_as_str`
+#[derive(Debug)]
+pub struct StorageTypes {
+    pub keys: &'static [&'static str],
+    pub value: &'static str,
+}
+
+impl StorageTypes {
+    fn matches(&self, keys: &[String], value: &str) -> bool {
+        self.value == value
+            && keys
+                .iter()
+                .map(String::as_str)
+                .eq(self.keys.iter().copied())
+    }
+}
+
+impl std::fmt::Display for StorageTypes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        format_storage_types(f, self.keys, self.value)
+    }
+}
+
+fn format_storage_types<K: std::fmt::Display>(
+    f: &mut std::fmt::Formatter<'_>,
+    keys: &[K],
+    value: &str,
+) -> std::fmt::Result {
+    if keys.is_empty() {
+        write!(f, "{value}")
+    } else {
+        let keys = keys.iter().map(K::to_string).collect::<Vec<_>>();
+        write!(f, "({}) -> {value}", keys.join(", "))
+    }
+}
+
+/// Storage entry types a pallet module knows how to decode
+///
+/// Every runtime version with a different storage layout is declared as a separate shape, and
+/// fetchers dispatch on the shape resolved from the metadata instead of the type names.
+pub struct StorageSchema<S: 'static> {
+    pub pallet_name: &'static str,
+    pub storage_entry_name: &'static str,
+    pub shapes: &'static [(S, StorageTypes)],
+}
+
+impl<S: Copy> StorageSchema<S> {
+    /// Find the shape matching the storage entry types in the metadata
+    pub fn resolve(&self, metadata: AnyRuntimeMetadata<'_>) -> Result<S, MetadataError> {
+        self.resolve_with_types(metadata).map(|(shape, _)| *shape)
+    }
+
+    fn resolve_with_types(
+        &self,
+        metadata: AnyRuntimeMetadata<'_>,
+    ) -> Result<&(S, StorageTypes), MetadataError> {
+        let (key_types, value_type) = metadata
+            .pallet_metadata(self.pallet_name)?
+            .storage_entry(self.storage_entry_name)?
+            .types_as_str(metadata.type_registry())?;
+
+        self.shapes
+            .iter()
+            .find(|(_, types)| types.matches(&key_types, &value_type))
+            .ok_or_else(|| MetadataError::UnexpectedStorageType {
+                expected: self
+                    .shapes
+                    .iter()
+                    .map(|(_, types)| format!("`{types}`"))
+                    .collect::<Vec<_>>()
+                    .join(" or "),
+                got: format!("`{}`", DisplayStorageTypes(&key_types, &value_type)),
+                pallet_name: self.pallet_name.to_string(),
+                storage_entry_name: self.storage_entry_name.to_string(),
+            })
+    }
+}
+
+struct DisplayStorageTypes<'a>(&'a [String], &'a str);
+
+impl std::fmt::Display for DisplayStorageTypes<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        format_storage_types(f, self.0, self.1)
+    }
+}
+
+/// Whether blocks can be decoded when a runtime doesn't match a registered schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Requirement {
+    /// Every block decodes the storage entry
+    Required,
+    /// The storage entry backs a feature that is skipped when the runtime doesn't match it
+    Optional,
+}
+
+/// Type erased schema check, used to validate every registered schema against a runtime
+pub trait SchemaCheck: Sync {
+    fn pallet_name(&self) -> &'static str;
+
+    fn storage_entry_name(&self) -> &'static str;
+
+    /// Returns the matching storage types
+    fn check(&self, metadata: AnyRuntimeMetadata<'_>) -> Result<String, MetadataError>;
+}
+
+impl<S: Copy + Sync> SchemaCheck for StorageSchema<S> {
+    fn pallet_name(&self) -> &'static str {
+        self.pallet_name
+    }
+
+    fn storage_entry_name(&self) -> &'static str {
+        self.storage_entry_name
+    }
+
+    fn check(&self, metadata: AnyRuntimeMetadata<'_>) -> Result<String, MetadataError> {
+        self.resolve_with_types(metadata)
+            .map(|(_, types)| types.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub enum Compatibility {
+    Compatible { storage_types: String },
+    PalletNotPresent,
+    Incompatible(MetadataError),
+}
+
+#[derive(Debug)]
+pub struct CompatibilityReportEntry {
+    pub pallet_name: &'static str,
+    pub storage_entry_name: &'static str,
+    pub requirement: Requirement,
+    pub compatibility: Compatibility,
+}
+
+#[derive(Debug)]
+pub struct CompatibilityReport {
+    pub entries: Vec<CompatibilityReportEntry>,
+}
+
+impl CompatibilityReport {
+    /// Number of required schemas the runtime doesn't match, optional schemas are not counted
+    pub fn incompatible_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| {
+                entry.requirement == Requirement::Required
+                    && matches!(entry.compatibility, Compatibility::Incompatible(_))
+            })
+            .count()
+    }
+
+    pub fn is_compatible(&self) -> bool {
+        self.incompatible_count() == 0
+    }
+}
+
+/// Check every storage schema against the runtime metadata
+/// Schemas of pallets missing from the runtime are skipped
+pub fn check_compatibility(
+    schemas: &[(Requirement, &dyn SchemaCheck)],
+    metadata: AnyRuntimeMetadata<'_>,
+) -> Result<CompatibilityReport, MetadataError> {
+    let pallet_names = metadata.pallet_names()?;
+
+    let entries = schemas
+        .iter()
+        .map(|(requirement, schema)| {
+            let compatibility = if !pallet_names.contains(schema.pallet_name()) {
+                Compatibility::PalletNotPresent
+            } else {
+                match schema.check(metadata) {
+                    Ok(storage_types) => Compatibility::Compatible { storage_types },
+                    Err(error) => Compatibility::Incompatible(error),
+                }
+            };
+            CompatibilityReportEntry {
+                pallet_name: schema.pallet_name(),
+                storage_entry_name: schema.storage_entry_name(),
+                requirement: *requirement,
+                compatibility,
+            }
+        })
+        .collect();
+
+    Ok(CompatibilityReport { entries })
+}
@@ -1,35 +1,25 @@
-use scale_value::{Composite, Value, ValueDef};
+use scale_value::{Value, ValueDef};
 
-use crate::decoder::value_decoder::{ValueDecoder, ValueDecoderError, decode_singleton};
+use crate::decoder::value_decoder::{ValueDecoder, ValueDecoderError};
 
-#[derive(Debug)]
-pub struct ValidatorId(pub [u8; 32]);
+/// 32 bytes account id, such as the owner of a session key or a block author
+#[derive(Debug, Clone, Copy, ValueDecoder)]
+#[value_decoder(transparent)]
+pub struct AccountId(#[value_decoder(with = "decode_public_key")] pub [u8; 32]);
 
-#[derive(Debug)]
-pub struct LegacyValidatorId(pub [u8; 32]);
-
-impl From<LegacyValidatorId> for ValidatorId {
-    fn from(value: LegacyValidatorId) -> Self {
-        ValidatorId(value.0)
-    }
-}
-
-impl<T> ValueDecoder<T> for ValidatorId {
-    fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
-    where
-        Self: Sized,
-        T: std::fmt::Debug,
-    {
-        Ok(Self(ValueDecoder::decode(value)?))
-    }
-}
-
-impl<T> ValueDecoder<T> for LegacyValidatorId {
-    fn decode(value: Value<T>) -> Result<Self, ValueDecoderError>
-    where
-        Self: Sized,
-        T: std::fmt::Debug,
-    {
-        Ok(Self(decode_singleton(value)?))
+/// Decode a public key or account id of `N` bytes, unwrapping the newtypes around it
+/// (`sp_consensus_babe::app::Public`, `sp_core::sr25519::Public`, `AccountId32`, `AccountId20`)
+pub fn decode_public_key<T, const N: usize>(value: Value<T>) -> Result<[u8; N], ValueDecoderError>
+where
+    T: std::fmt::Debug,
+{
+    match value.value {
+        ValueDef::Composite(composite) if composite.len() == 1 => {
+            decode_public_key(composite.into_values().next().unwrap())
+        }
+        other => ValueDecoder::decode(Value {
+            value: other,
+            context: value.context,
+        }),
     }
 }
@@ -1,66 +1,59 @@
 use crate::{
-    decoder::metadata::{AnyRuntimeMetadata, MetadataError},
+    decoder::metadata::AnyRuntimeMetadata,
     error::Error,
-    fetch::fetch,
+    fetch::fetch_optional,
     node_rpc::{
         client::NodeRPC,
         models::{BlockHashHex, RuntimeVersion},
     },
-    pallets::session::decoder::{LegacyValidatorId, ValidatorId},
+    pallets::{
+        schema::{StorageSchema, StorageTypes},
+        session::decoder::AccountId,
+    },
 };
 
 pub const PALLET_NAME: &str = "Session";
 
-pub async fn fetch_validators(
+pub const KEY_OWNER: StorageSchema<()> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "KeyOwner",
+    shapes: &[
+        (
+            (),
+            StorageTypes {
+                keys: &["(KeyTypeId, Vec<u8>)"],
+                value: "T::ValidatorId",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &["(sp_core::crypto::KeyTypeId, Vec<u8>)"],
+                value: "sp_core::crypto::AccountId32",
+            },
+        ),
+    ],
+};
+
+/// Find the validator owning a session key, `None` if the key isn't registered
+pub async fn fetch_key_owner(
     rpc: &NodeRPC,
+    key_type_id: [u8; 4],
+    public_key: &[u8],
     block_hash: &BlockHashHex,
     metadata: AnyRuntimeMetadata<'_>,
     runtime_version: &RuntimeVersion,
-) -> Result<Vec<ValidatorId>, Error> {
-    let storage_entry_name = "Validators";
-    let type_registry = metadata.type_registry();
-
-    let (_key_types, value_type) = metadata
-        .pallet_metadata(PALLET_NAME)?
-        .storage_entry(storage_entry_name)?
-        .types_as_str(type_registry)?;
+) -> Result<Option<AccountId>, Error> {
+    KEY_OWNER.resolve(metadata)?;
 
-    match &value_type[..] {
-        // Legacy version
-        "Vec<T::ValidatorId>" => Ok(fetch(
-            PALLET_NAME,
-            storage_entry_name,
-            (),
-            rpc,
-            block_hash,
-            metadata,
-            runtime_version,
-        )
-        .await?),
-        // Modern version (builtin)
-        "" => {
-            let legacy_validator_id: Vec<LegacyValidatorId> = fetch(
-                PALLET_NAME,
-                storage_entry_name,
-                (),
-                rpc,
-                block_hash,
-                metadata,
-                runtime_version,
-            )
-            .await?;
-            Ok(legacy_validator_id
-                .into_iter()
-                .map(ValidatorId::from)
-                .collect())
-        }
-        other => Err(Error::MetadataError(
-            MetadataError::UnexpectedStorageValueType {
-                expected: "`Vec<T::ValidatorId>` or ``".to_string(),
-                got: other.to_string(),
-                pallet_name: PALLET_NAME.to_string(),
-                storage_entry_name: storage_entry_name.to_string(),
-            },
-        )),
-    }
+    fetch_optional(
+        PALLET_NAME,
+        KEY_OWNER.storage_entry_name,
+        [(key_type_id, public_key.to_vec())],
+        rpc,
+        block_hash,
+        metadata,
+        runtime_version,
+    )
+    .await
 }
@@ -1,4 +1,4 @@
 pub mod decoder;
 pub mod fetch;
 
-pub use fetch::fetch_validators;
+pub use fetch::fetch_key_owner;
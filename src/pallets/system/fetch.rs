@@ -5,6 +5,7 @@ use crate::{
         value_decoder::{ValueDecoder, WithErrorSpan},
     },
     error::Error,
+    fetch::fetch_storage_value,
     node_rpc::{
         client::NodeRPC,
         models::{BlockHashHex, RuntimeVersion, StorageKeyHex, StorageValueBytes},
@@ -26,15 +27,15 @@ pub async fn fetch_events(
 ) -> Result<Vec<EventRecord>, Error> {
     let system_events_key_hex = StorageKeyHex(SYSTEM_EVENTS_KEY.to_string());
 
-    let StorageValueBytes(events_bytes) = rpc
-        .state_get_storage(&system_events_key_hex, block_hash)
-        .await?
-        .ok_or(Error::StorageValueNotFound {
-            pallet_name: PALLET_NAME.to_string(),
-            storage_entry_name: "Events".to_string(),
-            storage_entry_keys: None,
-            storage_entry_key_hash: system_events_key_hex,
-        })?;
+    let StorageValueBytes(events_bytes) =
+        fetch_storage_value(rpc, &system_events_key_hex, block_hash)
+            .await?
+            .ok_or(Error::StorageValueNotFound {
+                pallet_name: PALLET_NAME.to_string(),
+                storage_entry_name: "Events".to_string(),
+                storage_entry_keys: None,
+                storage_entry_key_hash: system_events_key_hex,
+            })?;
 
     let raw_value = decode_storage_value_any(
         events_bytes,
@@ -1,31 +1,62 @@
 use chrono::{DateTime, Utc};
 
 use crate::{
-    decoder::metadata::AnyRuntimeMetadata,
+    decoder::{metadata::AnyRuntimeMetadata, value_decoder::ValueDecoderError},
     error::Error,
-    fetch::fetch,
+    fetch::fetch_as,
     node_rpc::{
         client::NodeRPC,
         models::{BlockHashHex, RuntimeVersion},
     },
+    pallets::schema::{StorageSchema, StorageTypes},
 };
 
 pub const PALLET_NAME: &str = "Timestamp";
 
+pub const NOW: StorageSchema<()> = StorageSchema {
+    pallet_name: PALLET_NAME,
+    storage_entry_name: "Now",
+    shapes: &[
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "T::Moment",
+            },
+        ),
+        (
+            (),
+            StorageTypes {
+                keys: &[],
+                value: "u64",
+            },
+        ),
+    ],
+};
+
 pub async fn fetch_timestamp(
     rpc: &NodeRPC,
     block_hash: &BlockHashHex,
     metadata: AnyRuntimeMetadata<'_>,
     runtime_version: &RuntimeVersion,
 ) -> Result<DateTime<Utc>, Error> {
-    fetch(
+    NOW.resolve(metadata)?;
+
+    let millis: i64 = fetch_as(
         PALLET_NAME,
-        "Now",
+        NOW.storage_entry_name,
         (),
         rpc,
         block_hash,
         metadata,
         runtime_version,
     )
-    .await
+    .await?;
+    Ok(
+        DateTime::from_timestamp_millis(millis).ok_or(
+            ValueDecoderError::TimestampValueInvalid {
+                span: format!("{PALLET_NAME}:{}", NOW.storage_entry_name),
+            },
+        )?,
+    )
 }
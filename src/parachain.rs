@@ -0,0 +1,93 @@
+use frame_metadata::RuntimeMetadata;
+use tracing::{debug, warn};
+
+use crate::{
+    decoder::metadata::AnyRuntimeMetadata,
+    error::Error,
+    fetch::fetch_metadata,
+    node_rpc::{
+        client::NodeRPC,
+        models::{BlockHashHex, BlockNumberHex},
+    },
+    pallets::{
+        para_inclusion::decoder::{CandidateStatus, decode_candidate_event},
+        parachain_info::decoder::ParaId,
+        system,
+    },
+};
+
+/// Relay chain blocks scanned after the relay parent to find where a parablock was included
+const MAX_INCLUSION_DELAY: u32 = 10;
+
+#[derive(Debug, Clone)]
+pub struct InclusionBlock {
+    pub number: u32,
+    pub hash: BlockHashHex,
+}
+
+/// Find the relay chain block whose `ParaInclusion.CandidateIncluded` event includes the parablock
+///
+/// A candidate is backed in a relay block built on top of its relay parent and included in a later
+/// one, so the blocks following the relay parent are scanned.
+pub async fn find_inclusion_block(
+    relay_rpc: &NodeRPC,
+    para_id: ParaId,
+    parablock_hash: &BlockHashHex,
+    relay_parent_number: u32,
+) -> Result<Option<InclusionBlock>, Error> {
+    let best_header = relay_rpc.chain_get_best_header().await?;
+    let best_number = u32::try_from(&best_header.number)
+        .map_err(|err| Error::ParsingBlockNumberFailed(best_header.number.clone(), err))?;
+    let last_number = best_number.min(relay_parent_number.saturating_add(MAX_INCLUSION_DELAY));
+
+    let mut metadata: Option<(u64, RuntimeMetadata)> = None;
+
+    for number in relay_parent_number + 1..=last_number {
+        let block_hash = relay_rpc
+            .chain_get_block_hash(&BlockNumberHex::from(number))
+            .await?;
+        let runtime_version = relay_rpc.state_get_runtime_version(&block_hash).await?;
+
+        let metadata = match &mut metadata {
+            Some((spec_version, metadata)) if *spec_version == runtime_version.spec_version => {
+                metadata
+            }
+            metadata => {
+                let (_, metadata) = metadata.insert((
+                    runtime_version.spec_version,
+                    fetch_metadata(relay_rpc, &block_hash).await?,
+                ));
+                metadata
+            }
+        };
+
+        let events = system::fetch_events(
+            relay_rpc,
+            &block_hash,
+            AnyRuntimeMetadata(metadata),
+            &runtime_version,
+        )
+        .await?;
+        debug!(number, events = events.len(), "scanning relay chain block");
+
+        for record in &events {
+            match decode_candidate_event(&record.event) {
+                Some(Ok(candidate))
+                    if candidate.status == CandidateStatus::Included
+                        && candidate.receipt.descriptor.para_id == para_id
+                        && BlockHashHex::from(candidate.receipt.descriptor.para_head.0)
+                            == *parablock_hash =>
+                {
+                    return Ok(Some(InclusionBlock {
+                        number,
+                        hash: block_hash,
+                    }));
+                }
+                Some(Err(error)) => warn!(%error),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(None)
+}
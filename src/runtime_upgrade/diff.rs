@@ -0,0 +1,165 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use derive_more::Display;
+
+use crate::decoder::metadata::{AnyPalletMetadata, AnyRuntimeMetadata, MetadataError};
+
+/// Kind of a pallet metadata item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display)]
+pub enum PalletItemKind {
+    Call,
+    Event,
+    Error,
+    #[display("Storage")]
+    StorageEntry,
+    Constant,
+}
+
+/// A pallet item that was added, removed or changed between two runtimes
+/// `old` is `None` for added items, `new` is `None` for removed items
+#[derive(Debug, Clone)]
+pub struct PalletItemChange {
+    pub pallet_name: String,
+    pub kind: PalletItemKind,
+    pub item_name: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MetadataDiff {
+    pub pallets_added: Vec<String>,
+    pub pallets_removed: Vec<String>,
+    pub item_changes: Vec<PalletItemChange>,
+}
+
+impl MetadataDiff {
+    pub fn is_empty(&self) -> bool {
+        self.pallets_added.is_empty()
+            && self.pallets_removed.is_empty()
+            && self.item_changes.is_empty()
+    }
+}
+
+/// Signatures of every item of a pallet, keyed by kind and item name
+type PalletItems = BTreeMap<(PalletItemKind, String), String>;
+
+/// Compare two runtime metadata and collect the differences
+pub fn diff_metadata(
+    old_metadata: AnyRuntimeMetadata<'_>,
+    new_metadata: AnyRuntimeMetadata<'_>,
+) -> Result<MetadataDiff, MetadataError> {
+    let old_pallets = collect_pallets(old_metadata)?;
+    let new_pallets = collect_pallets(new_metadata)?;
+
+    let pallet_names = old_pallets
+        .keys()
+        .chain(new_pallets.keys())
+        .collect::<BTreeSet<_>>();
+
+    let mut diff = MetadataDiff::default();
+
+    for pallet_name in pallet_names {
+        match (old_pallets.get(pallet_name), new_pallets.get(pallet_name)) {
+            (Some(old_items), Some(new_items)) => {
+                let item_keys = old_items
+                    .keys()
+                    .chain(new_items.keys())
+                    .collect::<BTreeSet<_>>();
+
+                for key @ (kind, item_name) in item_keys {
+                    let old = old_items.get(key);
+                    let new = new_items.get(key);
+
+                    if old != new {
+                        diff.item_changes.push(PalletItemChange {
+                            pallet_name: pallet_name.clone(),
+                            kind: *kind,
+                            item_name: item_name.clone(),
+                            old: old.cloned(),
+                            new: new.cloned(),
+                        });
+                    }
+                }
+            }
+            (Some(_), None) => diff.pallets_removed.push(pallet_name.clone()),
+            (None, Some(_)) => diff.pallets_added.push(pallet_name.clone()),
+            (None, None) => {}
+        }
+    }
+
+    Ok(diff)
+}
+
+fn collect_pallets(
+    metadata: AnyRuntimeMetadata<'_>,
+) -> Result<BTreeMap<String, PalletItems>, MetadataError> {
+    metadata
+        .pallets()?
+        .iter()
+        .map(|pallet| {
+            Ok((
+                pallet.name()?.to_string(),
+                collect_pallet_items(pallet, metadata)?,
+            ))
+        })
+        .collect()
+}
+
+fn collect_pallet_items(
+    pallet: &AnyPalletMetadata<'_>,
+    metadata: AnyRuntimeMetadata<'_>,
+) -> Result<PalletItems, MetadataError> {
+    let type_registry = metadata.type_registry();
+    let mut items = PalletItems::new();
+
+    for (kind, signatures) in [
+        (PalletItemKind::Call, pallet.calls(type_registry)?),
+        (PalletItemKind::Event, pallet.events(type_registry)?),
+        (PalletItemKind::Error, pallet.errors(type_registry)?),
+    ] {
+        for signature in signatures {
+            items.insert((kind, signature.name.clone()), signature.to_string());
+        }
+    }
+
+    let storage_entries = match pallet.storage_entries() {
+        Ok(storage_entries) => storage_entries,
+        // Pallet without storage
+        Err(MetadataError::MetadataNotFound(_)) => Vec::new(),
+        Err(err) => Err(err)?,
+    };
+
+    for entry in storage_entries {
+        let (key_types, value_type) = entry.types_as_str(type_registry)?;
+        let signature = if key_types.is_empty() {
+            value_type
+        } else {
+            let keys = entry
+                .hashers()?
+                .iter()
+                .zip(key_types)
+                .map(|(hasher, key_type)| format!("{hasher:?}: {key_type}"))
+                .collect::<Vec<_>>();
+            format!("({}) -> {value_type}", keys.join(", "))
+        };
+        items.insert(
+            (PalletItemKind::StorageEntry, entry.name()?.to_string()),
+            signature,
+        );
+    }
+
+    for constant in pallet.constants()? {
+        let signature = format!(
+            "{} = 0x{}",
+            constant.type_as_str(type_registry)?,
+            hex::encode(constant.value()?)
+        );
+        items.insert(
+            (PalletItemKind::Constant, constant.name()?.to_string()),
+            signature,
+        );
+    }
+
+    Ok(items)
+}
@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+
+use tracing::{debug, warn};
+
+use crate::{
+    decoder::metadata::AnyRuntimeMetadata,
+    error::Error,
+    fetch::fetch_metadata,
+    node_rpc::{
+        client::NodeRPC,
+        models::{BlockHashHex, BlockNumberHex, RuntimeVersion},
+    },
+    pallets::system::{self, decoder::EventRecord},
+};
+
+#[derive(Debug, Clone)]
+pub struct RuntimeUpgrade {
+    pub block_number: u32,
+    pub block_hash: BlockHashHex,
+    pub old_spec_version: u64,
+    pub new_spec_version: u64,
+    /// Index and record of the `System.CodeUpdated` event in the upgrade block
+    pub code_updated_event: Option<(usize, EventRecord)>,
+}
+
+/// Runtime versions of the blocks already queried, keyed by block number
+type RuntimeVersions = BTreeMap<u32, (BlockHashHex, RuntimeVersion)>;
+
+/// Find every block in the range where the spec version changed
+///
+/// The state of a block already contains the new runtime code, so the runtime version of the
+/// upgrade block is the new one. Bisects the range, only descending into halves where the spec
+/// version at the edges differs. Upgrades that are reverted within a half are not detected.
+pub async fn find_runtime_upgrades(
+    rpc: &NodeRPC,
+    from_block: u32,
+    to_block: u32,
+) -> Result<Vec<RuntimeUpgrade>, Error> {
+    if from_block >= to_block {
+        Err(Error::InvalidBlockRange {
+            from_block,
+            to_block,
+        })?;
+    }
+
+    let mut runtime_versions = RuntimeVersions::new();
+    fetch_runtime_version(rpc, from_block, &mut runtime_versions).await?;
+    fetch_runtime_version(rpc, to_block, &mut runtime_versions).await?;
+
+    let mut upgrade_blocks = Vec::new();
+    let mut ranges = vec![(from_block, to_block)];
+
+    while let Some((lo, hi)) = ranges.pop() {
+        let lo_spec_version = runtime_versions[&lo].1.spec_version;
+        let hi_spec_version = runtime_versions[&hi].1.spec_version;
+
+        if lo_spec_version == hi_spec_version {
+            continue;
+        }
+
+        if hi - lo == 1 {
+            upgrade_blocks.push(hi);
+            continue;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        fetch_runtime_version(rpc, mid, &mut runtime_versions).await?;
+        ranges.push((lo, mid));
+        ranges.push((mid, hi));
+    }
+
+    upgrade_blocks.sort();
+
+    let mut upgrades = Vec::with_capacity(upgrade_blocks.len());
+    for block_number in upgrade_blocks {
+        let (parent_hash, parent_runtime_version) = &runtime_versions[&(block_number - 1)];
+        let (block_hash, runtime_version) = &runtime_versions[&block_number];
+
+        let code_updated_event =
+            match find_code_updated_event(rpc, block_hash, parent_hash, parent_runtime_version)
+                .await
+            {
+                Ok(event) => event,
+                Err(error) => {
+                    warn!(block_number, %error, "Couldn't fetch System.CodeUpdated event");
+                    None
+                }
+            };
+
+        upgrades.push(RuntimeUpgrade {
+            block_number,
+            block_hash: block_hash.clone(),
+            old_spec_version: parent_runtime_version.spec_version,
+            new_spec_version: runtime_version.spec_version,
+            code_updated_event,
+        });
+    }
+
+    Ok(upgrades)
+}
+
+async fn fetch_runtime_version(
+    rpc: &NodeRPC,
+    block_number: u32,
+    runtime_versions: &mut RuntimeVersions,
+) -> Result<(), Error> {
+    if runtime_versions.contains_key(&block_number) {
+        return Ok(());
+    }
+
+    let block_hash = rpc
+        .chain_get_block_hash(&BlockNumberHex::from(block_number))
+        .await?;
+    let runtime_version = rpc.state_get_runtime_version(&block_hash).await?;
+
+    debug!(block_number, spec_version = runtime_version.spec_version);
+
+    runtime_versions.insert(block_number, (block_hash, runtime_version));
+    Ok(())
+}
+
+/// Events of the upgrade block were emitted by the old runtime, so they are decoded with the
+/// metadata of the parent block
+async fn find_code_updated_event(
+    rpc: &NodeRPC,
+    block_hash: &BlockHashHex,
+    parent_hash: &BlockHashHex,
+    parent_runtime_version: &RuntimeVersion,
+) -> Result<Option<(usize, EventRecord)>, Error> {
+    let parent_metadata = fetch_metadata(rpc, parent_hash).await?;
+
+    let events = system::fetch_events(
+        rpc,
+        block_hash,
+        AnyRuntimeMetadata(&parent_metadata),
+        parent_runtime_version,
+    )
+    .await?;
+
+    Ok(events.into_iter().enumerate().find(|(_, event_record)| {
+        event_record.event.name == "System" && event_record.event.action == "CodeUpdated"
+    }))
+}
@@ -0,0 +1,5 @@
+pub mod diff;
+pub mod history;
+
+pub use diff::diff_metadata;
+pub use history::find_runtime_upgrades;
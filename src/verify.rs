@@ -0,0 +1,270 @@
+use std::{collections::HashMap, sync::Arc};
+
+use hex::FromHex;
+use parity_scale_codec::{Compact, Decode, Encode};
+use parking_lot::Mutex;
+use sc_executor::{WasmExecutor, read_embedded_version};
+use sc_executor_common::runtime_blob::RuntimeBlob;
+use sp_runtime::{
+    StateVersion,
+    traits::{BlakeTwo256, Hash},
+};
+use sp_state_machine::BasicExternalities;
+use sp_trie::{LayoutV1, StorageProof, read_trie_value};
+use tracing::{debug, info};
+
+use crate::{
+    error::Error,
+    node_rpc::{
+        client::NodeRPC,
+        models::{
+            Block, BlockHashHex, BlockHeader, ExtrinsicBytes, LogBytes, RuntimeVersion,
+            StorageKeyHex, StorageValueBytes, TrieNodeBytes,
+        },
+    },
+};
+
+/// Well-known storage key of the runtime code, `:code`
+const CODE_STORAGE_KEY: &str = "0x3a636f6465";
+
+/// Decode a hexadecimal string with an optional 0x prefix
+fn parse_hex<T: FromHex<Error = hex::FromHexError>>(value: &str) -> Result<T, Error> {
+    T::from_hex(value.trim_start_matches("0x"))
+        .map_err(|error| Error::ParsingHexFailed(value.to_string(), error))
+}
+
+/// Blake2-256 hash of the SCALE encoded header
+pub fn header_hash(header: &BlockHeader) -> Result<[u8; 32], Error> {
+    let number = u32::try_from(&header.number)
+        .map_err(|err| Error::ParsingBlockNumberFailed(header.number.clone(), err))?;
+
+    let mut encoded = parse_hex::<[u8; 32]>(&header.parent_hash.0)?.to_vec();
+    Compact(number).encode_to(&mut encoded);
+    encoded.extend(parse_hex::<[u8; 32]>(&header.state_root)?);
+    encoded.extend(parse_hex::<[u8; 32]>(&header.extrinsics_root)?);
+    // The digest logs are already SCALE encoded `DigestItem`s
+    Compact(header.digest.logs.len() as u32).encode_to(&mut encoded);
+    for LogBytes(log) in &header.digest.logs {
+        encoded.extend(log);
+    }
+
+    Ok(BlakeTwo256::hash(&encoded).0)
+}
+
+/// Ordered trie root of the encoded extrinsics
+///
+/// Runtimes with a system version above 1 build the extrinsics trie with state version 1.
+pub fn extrinsics_root(
+    extrinsics: &[ExtrinsicBytes],
+    runtime_version: &RuntimeVersion,
+) -> [u8; 32] {
+    let state_version = match runtime_version
+        .system_version
+        .or(runtime_version.state_version)
+    {
+        None | Some(0) | Some(1) => StateVersion::V0,
+        Some(_) => StateVersion::V1,
+    };
+    let extrinsics = extrinsics
+        .iter()
+        .map(|ExtrinsicBytes(extrinsic)| extrinsic.clone())
+        .collect();
+
+    BlakeTwo256::ordered_trie_root(extrinsics, state_version).0
+}
+
+/// Check that the header hashes to the block hash
+pub fn verify_header(header: &BlockHeader, block_hash: &BlockHashHex) -> Result<(), Error> {
+    let computed = BlockHashHex::from(header_hash(header)?);
+    if computed != *block_hash {
+        Err(Error::HeaderHashMismatch {
+            block_hash: block_hash.clone(),
+            computed,
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Check the header hash and the extrinsics root of a block returned by the node
+pub fn verify_block(
+    block: &Block,
+    block_hash: &BlockHashHex,
+    runtime_version: &RuntimeVersion,
+) -> Result<(), Error> {
+    verify_header(&block.header, block_hash)?;
+
+    let expected = parse_hex::<[u8; 32]>(&block.header.extrinsics_root)?;
+    let computed = extrinsics_root(&block.extrinsics, runtime_version);
+    if computed != expected {
+        Err(Error::ExtrinsicsRootMismatch {
+            block_hash: block_hash.clone(),
+            expected: block.header.extrinsics_root.clone(),
+            computed: format!("0x{}", hex::encode(computed)),
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Version and metadata of a runtime, read from its code instead of asked to the node
+#[derive(Debug)]
+pub struct VerifiedRuntime {
+    pub version: sc_executor::RuntimeVersion,
+    /// SCALE encoded `RuntimeMetadataPrefixed`, as returned by `state_getMetadata`
+    pub metadata_bytes: Vec<u8>,
+}
+
+/// Read the embedded version of the runtime code and execute its `Metadata_metadata` entry point
+fn execute_runtime(code: &[u8]) -> Result<VerifiedRuntime, Error> {
+    let blob = RuntimeBlob::uncompress_if_needed(code)
+        .map_err(|error| Error::ExecutingRuntimeFailed(error.to_string()))?;
+    let version = read_embedded_version(&blob)
+        .map_err(|error| Error::ExecutingRuntimeFailed(error.to_string()))?
+        .ok_or(Error::RuntimeVersionNotEmbedded)?;
+
+    let executor = WasmExecutor::<sp_io::SubstrateHostFunctions>::builder().build();
+    let output = executor
+        .uncached_call(
+            blob,
+            &mut BasicExternalities::default(),
+            true,
+            "Metadata_metadata",
+            &[],
+        )
+        .map_err(|error| Error::ExecutingRuntimeFailed(error.to_string()))?;
+    // `OpaqueMetadata` wraps the encoded metadata in a byte vector
+    let metadata_bytes =
+        Vec::<u8>::decode(&mut &output[..]).map_err(Error::ParsingRuntimeMetadataFailed)?;
+
+    Ok(VerifiedRuntime {
+        version,
+        metadata_bytes,
+    })
+}
+
+/// Reads storage values from `state_getReadProof` proofs instead of trusting `state_getStorage`
+///
+/// The state root comes from a header hashing to the requested block hash, so the node can't
+/// forge a value without breaking the proof. The runtime version and metadata are derived from the
+/// `:code` read that way.
+#[derive(Debug, Default)]
+pub struct StorageVerifier {
+    state_roots: Mutex<HashMap<BlockHashHex, [u8; 32]>>,
+    runtimes_by_code_hash: Mutex<HashMap<[u8; 32], Arc<VerifiedRuntime>>>,
+}
+
+impl StorageVerifier {
+    async fn state_root(
+        &self,
+        rpc: &NodeRPC,
+        block_hash: &BlockHashHex,
+    ) -> Result<[u8; 32], Error> {
+        if let Some(state_root) = self.state_roots.lock().get(block_hash) {
+            return Ok(*state_root);
+        }
+
+        let header = rpc.chain_get_header(block_hash.clone()).await?;
+        verify_header(&header, block_hash)?;
+        let state_root = parse_hex::<[u8; 32]>(&header.state_root)?;
+
+        self.state_roots
+            .lock()
+            .insert(block_hash.clone(), state_root);
+        Ok(state_root)
+    }
+
+    pub async fn read_storage(
+        &self,
+        rpc: &NodeRPC,
+        storage_key: &StorageKeyHex,
+        block_hash: &BlockHashHex,
+    ) -> Result<Option<StorageValueBytes>, Error> {
+        let state_root = self.state_root(rpc, block_hash).await?;
+        let read_proof = rpc
+            .state_get_read_proof(std::slice::from_ref(storage_key), block_hash)
+            .await?;
+        debug!(
+            %storage_key,
+            %block_hash,
+            proof_nodes = read_proof.proof.len(),
+            "verifying storage proof"
+        );
+
+        let key = parse_hex::<Vec<u8>>(&storage_key.0)?;
+        let db = StorageProof::new(read_proof.proof.into_iter().map(|TrieNodeBytes(node)| node))
+            .into_memory_db::<BlakeTwo256>();
+
+        let value =
+            read_trie_value::<LayoutV1<BlakeTwo256>, _>(&db, &state_root.into(), &key, None, None)
+                .map_err(|error| Error::InvalidStorageProof {
+                    storage_key: storage_key.clone(),
+                    block_hash: block_hash.clone(),
+                    reason: error.to_string(),
+                })?;
+
+        Ok(value.map(StorageValueBytes))
+    }
+
+    /// Version and metadata of the runtime code of a block, executed once per code
+    pub async fn runtime(
+        &self,
+        rpc: &NodeRPC,
+        block_hash: &BlockHashHex,
+    ) -> Result<Arc<VerifiedRuntime>, Error> {
+        let StorageValueBytes(code) = self
+            .read_storage(
+                rpc,
+                &StorageKeyHex(CODE_STORAGE_KEY.to_string()),
+                block_hash,
+            )
+            .await?
+            .ok_or_else(|| Error::RuntimeCodeNotFound(block_hash.clone()))?;
+
+        let code_hash = BlakeTwo256::hash(&code).0;
+        if let Some(runtime) = self.runtimes_by_code_hash.lock().get(&code_hash) {
+            return Ok(runtime.clone());
+        }
+
+        info!(%block_hash, "executing the runtime code to verify its metadata");
+        let runtime = Arc::new(tokio::task::block_in_place(|| execute_runtime(&code))?);
+        self.runtimes_by_code_hash
+            .lock()
+            .insert(code_hash, runtime.clone());
+        Ok(runtime)
+    }
+
+    /// Check the runtime version reported by the node against the version embedded in the code
+    pub async fn verify_runtime_version(
+        &self,
+        rpc: &NodeRPC,
+        block_hash: &BlockHashHex,
+        runtime_version: &RuntimeVersion,
+    ) -> Result<(), Error> {
+        let runtime = self.runtime(rpc, block_hash).await?;
+        let embedded = &runtime.version;
+
+        let reported_system_version = runtime_version
+            .system_version
+            .or(runtime_version.state_version)
+            .unwrap_or_default();
+        if runtime_version.spec_name != embedded.spec_name
+            || runtime_version.spec_version != u64::from(embedded.spec_version)
+            || reported_system_version != embedded.system_version
+        {
+            Err(Error::RuntimeVersionMismatch {
+                block_hash: block_hash.clone(),
+                reported: format!(
+                    "{} {} (system version {reported_system_version})",
+                    runtime_version.spec_name, runtime_version.spec_version
+                ),
+                embedded: format!(
+                    "{} {} (system version {})",
+                    embedded.spec_name, embedded.spec_version, embedded.system_version
+                ),
+            })?;
+        }
+
+        Ok(())
+    }
+}
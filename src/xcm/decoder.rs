@@ -0,0 +1,189 @@
+use parity_scale_codec::Decode;
+use scale_info::PortableRegistry;
+use scale_value::{Composite, Value, ValueDef, Variant};
+use sp_runtime::traits::{BlakeTwo256, Hash};
+use thiserror::Error;
+
+use crate::decoder::{
+    metadata::AnyRuntimeMetadata,
+    value_decoder::{ValueDecoderError, format_value},
+};
+
+#[derive(Debug, Error)]
+pub enum XcmError {
+    #[error("The runtime metadata has no `xcm::VersionedXcm` type")]
+    VersionedXcmTypeNotFound,
+
+    #[error("Couldn't decode XCM message: {0}")]
+    DecodingFailed(#[from] scale_value::scale::DecodeError),
+
+    #[error("Couldn't decode XCMP message format: {0}")]
+    ParsingMessageFormatFailed(#[from] parity_scale_codec::Error),
+
+    #[error("Unexpected XCM program {0}")]
+    UnexpectedProgram(String),
+
+    #[error("{0} trailing bytes after the XCM message")]
+    TrailingBytes(usize),
+
+    #[error(transparent)]
+    ValueDecoderError(#[from] ValueDecoderError),
+}
+
+/// An XCM program rendered as a list of instructions
+#[derive(Debug, Clone)]
+pub struct XcmProgram {
+    /// `V2` to `V5` for versioned messages, `None` for messages of a known version
+    pub version: Option<String>,
+    pub instructions: Vec<String>,
+    /// Topic of the trailing `SetTopic` instruction, used as the message ID since XCM V3
+    pub topic: Option<[u8; 32]>,
+}
+
+impl XcmProgram {
+    /// Render an `xcm::VersionedXcm` value
+    pub fn from_versioned<T>(value: &Value<T>) -> Result<Self, XcmError>
+    where
+        T: std::fmt::Debug,
+    {
+        match &value.value {
+            ValueDef::Variant(Variant { name, values }) if name.starts_with('V') => {
+                let mut program = Self::from_values(values)?;
+                program.version = Some(name.clone());
+                Ok(program)
+            }
+            _ => Err(XcmError::UnexpectedProgram(format_value(value))),
+        }
+    }
+
+    /// Render an `xcm::Xcm` value, the message of the `Sent` events
+    pub fn from_unversioned<T>(value: &Value<T>) -> Result<Self, XcmError>
+    where
+        T: std::fmt::Debug,
+    {
+        match &value.value {
+            ValueDef::Composite(values) => Self::from_values(values),
+            _ => Err(XcmError::UnexpectedProgram(format_value(value))),
+        }
+    }
+
+    /// `Xcm(Vec<Instruction>)` is a newtype around the instructions, every single element composite
+    /// is unwrapped until the instruction variants are reached
+    fn from_values<T>(mut values: &Composite<T>) -> Result<Self, XcmError>
+    where
+        T: std::fmt::Debug,
+    {
+        while let Composite::Unnamed(elements) = values {
+            match elements.as_slice() {
+                [
+                    Value {
+                        value: ValueDef::Composite(inner),
+                        ..
+                    },
+                ] => values = inner,
+                _ => break,
+            }
+        }
+
+        let Composite::Unnamed(instructions) = values else {
+            return Err(XcmError::UnexpectedProgram(format!("{values:?}")));
+        };
+
+        let topic = match instructions.last().map(|instruction| &instruction.value) {
+            Some(ValueDef::Variant(Variant { name, values })) if name == "SetTopic" => {
+                values.values().next().and_then(decode_topic)
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            version: None,
+            instructions: instructions.iter().map(format_value).collect(),
+            topic,
+        })
+    }
+
+    /// ID of the message as reported by the `Sent` and `MessageQueue.Processed` events
+    ///
+    /// Messages ending with `SetTopic` are identified by the topic, others by the hash of the
+    /// encoded message.
+    pub fn message_id(&self, encoded: &[u8]) -> [u8; 32] {
+        self.topic.unwrap_or_else(|| BlakeTwo256::hash(encoded).0)
+    }
+}
+
+fn decode_topic<T>(value: &Value<T>) -> Option<[u8; 32]> {
+    match &value.value {
+        ValueDef::Composite(Composite::Unnamed(bytes)) => bytes
+            .iter()
+            .map(|byte| byte.as_u128().and_then(|byte| u8::try_from(byte).ok()))
+            .collect::<Option<Vec<u8>>>()?
+            .try_into()
+            .ok(),
+        _ => None,
+    }
+}
+
+/// Type of the `xcm::VersionedXcm` messages, taken from the `XcmPallet` / `PolkadotXcm` calls
+fn versioned_xcm_type(types: &PortableRegistry) -> Option<u32> {
+    types
+        .types
+        .iter()
+        .find(|ty| ty.ty.path.segments == ["xcm", "VersionedXcm"])
+        .map(|ty| ty.id)
+}
+
+/// Decode a SCALE encoded `xcm::VersionedXcm`, advancing `bytes` past the message
+fn decode_next(bytes: &mut &[u8], types: &PortableRegistry) -> Result<XcmProgram, XcmError> {
+    let type_id = versioned_xcm_type(types).ok_or(XcmError::VersionedXcmTypeNotFound)?;
+    let value = scale_value::scale::decode_as_type(bytes, type_id, types)?;
+    XcmProgram::from_versioned(&value)
+}
+
+/// Decode a downward or upward message, a single SCALE encoded `xcm::VersionedXcm`
+pub fn decode_versioned_xcm(
+    mut bytes: &[u8],
+    metadata: AnyRuntimeMetadata<'_>,
+) -> Result<XcmProgram, XcmError> {
+    let types = metadata
+        .type_registry()
+        .ok_or(XcmError::VersionedXcmTypeNotFound)?;
+    let program = decode_next(&mut bytes, types)?;
+    if !bytes.is_empty() {
+        Err(XcmError::TrailingBytes(bytes.len()))?;
+    }
+    Ok(program)
+}
+
+/// `cumulus_primitives_core::XcmpMessageFormat`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Decode)]
+enum XcmpMessageFormat {
+    ConcatenatedVersionedXcm,
+    ConcatenatedEncodedBlob,
+    Signals,
+}
+
+/// Decode a horizontal message, an `XcmpMessageFormat` followed by concatenated messages
+///
+/// Every message is returned with its encoding, to compute its ID. Blobs and signals don't carry
+/// XCM programs and decode to no messages.
+pub fn decode_hrmp_message<'a>(
+    mut bytes: &'a [u8],
+    metadata: AnyRuntimeMetadata<'_>,
+) -> Result<Vec<(&'a [u8], XcmProgram)>, XcmError> {
+    let types = metadata
+        .type_registry()
+        .ok_or(XcmError::VersionedXcmTypeNotFound)?;
+
+    if XcmpMessageFormat::decode(&mut bytes)? != XcmpMessageFormat::ConcatenatedVersionedXcm {
+        return Ok(Vec::new());
+    }
+
+    let mut programs = Vec::new();
+    while !bytes.is_empty() {
+        let start = bytes;
+        let program = decode_next(&mut bytes, types)?;
+        programs.push((&start[..start.len() - bytes.len()], program));
+    }
+    Ok(programs)
+}
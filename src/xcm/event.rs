@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use scale_decode::ext::primitive_types::H256;
+use scale_value::Value;
+use serde::Deserialize;
+
+use crate::{
+    decoder::value_decoder::{composite_to_record, format_value, get_field},
+    pallets::system::decoder::Event,
+    xcm::decoder::{XcmError, XcmProgram},
+};
+
+/// Pallets sending XCM messages, `XcmPallet` on relay chains and `PolkadotXcm` on parachains
+pub const XCM_PALLETS: [&str; 2] = ["XcmPallet", "PolkadotXcm"];
+
+/// Fields of the `XcmpQueue.XcmpMessageSent` event
+#[derive(Debug, Deserialize)]
+struct XcmpMessageSent {
+    message_hash: [u8; 32],
+}
+
+#[derive(Debug, Clone)]
+pub enum XcmEvent {
+    /// A message was sent by `XcmPallet` / `PolkadotXcm`
+    Sent {
+        destination: String,
+        program: XcmProgram,
+        message_id: [u8; 32],
+    },
+    /// A local XCM program was executed by `XcmPallet` / `PolkadotXcm`
+    Attempted { outcome: String },
+    /// A message was queued for a sibling parachain by `XcmpQueue`
+    XcmpMessageSent { message_hash: [u8; 32] },
+    /// An inbound message was executed by `MessageQueue`
+    Processed {
+        id: [u8; 32],
+        origin: String,
+        success: bool,
+    },
+    /// An inbound message couldn't be executed by `MessageQueue`
+    ProcessingFailed {
+        id: [u8; 32],
+        origin: String,
+        error: String,
+    },
+}
+
+impl XcmEvent {
+    /// ID linking the `Sent` event of the sending chain to the `Processed` event of the receiver
+    pub fn message_id(&self) -> Option<[u8; 32]> {
+        match self {
+            XcmEvent::Sent { message_id, .. } => Some(*message_id),
+            XcmEvent::Attempted { .. } => None,
+            XcmEvent::XcmpMessageSent { message_hash } => Some(*message_hash),
+            XcmEvent::Processed { id, .. } | XcmEvent::ProcessingFailed { id, .. } => Some(*id),
+        }
+    }
+}
+
+/// Decode the XCM related events of `XcmPallet` / `PolkadotXcm`, `XcmpQueue` and `MessageQueue`
+/// Returns `None` for any other event
+pub fn decode_xcm_event(event: &Event) -> Option<Result<XcmEvent, XcmError>> {
+    let decode = |decode_fields: fn(HashMap<String, Value<()>>) -> Result<XcmEvent, XcmError>| {
+        composite_to_record(event.values.clone())
+            .map_err(XcmError::from)
+            .and_then(decode_fields)
+    };
+
+    match (event.name.as_str(), event.action.as_str()) {
+        (pallet, "Sent") if XCM_PALLETS.contains(&pallet) => Some(decode(|mut fields| {
+            let destination: Value<()> = get_field("destination", &mut fields)?;
+            let message: Value<()> = get_field("message", &mut fields)?;
+            Ok(XcmEvent::Sent {
+                destination: format_value(&destination),
+                program: XcmProgram::from_unversioned(&message)?,
+                message_id: get_field("message_id", &mut fields)?,
+            })
+        })),
+        (pallet, "Attempted") if XCM_PALLETS.contains(&pallet) => Some(decode(|mut fields| {
+            let outcome: Value<()> = get_field("outcome", &mut fields)?;
+            Ok(XcmEvent::Attempted {
+                outcome: format_value(&outcome),
+            })
+        })),
+        ("XcmpQueue", "XcmpMessageSent") => Some(
+            event
+                .fields_as::<XcmpMessageSent>()
+                .map(|XcmpMessageSent { message_hash }| XcmEvent::XcmpMessageSent { message_hash })
+                .map_err(XcmError::from),
+        ),
+        ("MessageQueue", "Processed") => Some(decode(|mut fields| {
+            let id: H256 = get_field("id", &mut fields)?;
+            let origin: Value<()> = get_field("origin", &mut fields)?;
+            Ok(XcmEvent::Processed {
+                id: id.0,
+                origin: format_value(&origin),
+                success: get_field("success", &mut fields)?,
+            })
+        })),
+        ("MessageQueue", "ProcessingFailed") => Some(decode(|mut fields| {
+            let id: H256 = get_field("id", &mut fields)?;
+            let origin: Value<()> = get_field("origin", &mut fields)?;
+            let error: Value<()> = get_field("error", &mut fields)?;
+            Ok(XcmEvent::ProcessingFailed {
+                id: id.0,
+                origin: format_value(&origin),
+                error: format_value(&error),
+            })
+        })),
+        _ => None,
+    }
+}
@@ -0,0 +1,5 @@
+pub mod decoder;
+pub mod event;
+
+pub use decoder::{XcmProgram, decode_hrmp_message, decode_versioned_xcm};
+pub use event::{XcmEvent, decode_xcm_event};
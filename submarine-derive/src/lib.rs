@@ -0,0 +1,381 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    Attribute, Data, DataEnum, DeriveInput, Fields, GenericParam, LitStr, Path, parse_macro_input,
+    parse_quote, spanned::Spanned,
+};
+
+/// Derive `ValueDecoder<T>` for structs and enums
+///
+/// - structs with named fields are decoded from a `Composite::Named` record
+/// - tuple structs are decoded from a `Composite::Unnamed` with the same number of elements
+/// - enums are decoded from a `ValueDef::Variant`, matched by variant name
+///
+/// Errors are spanned with the field name, element index or variant name.
+///
+/// Container attributes:
+/// - `#[value_decoder(transparent)]`: decode a single field struct directly from the value
+/// - `#[value_decoder(crate = "path")]`: path of the `value_decoder` module, defaults to
+///   `crate::decoder::value_decoder`
+///
+/// Variant attributes:
+/// - `#[value_decoder(rename = "Name")]`: match a different variant name
+///
+/// Field attributes:
+/// - `#[value_decoder(rename = "name")]`: read a different record field
+/// - `#[value_decoder(default)]`: use `Default::default()` if the record field is missing
+/// - `#[value_decoder(with = "path")]`: decode with a `fn(Value<T>) -> Result<R, ValueDecoderError>`
+#[proc_macro_derive(ValueDecoder, attributes(value_decoder))]
+pub fn derive_value_decoder(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct Attrs {
+    rename: Option<String>,
+    default: bool,
+    with: Option<Path>,
+    transparent: bool,
+    module: Option<Path>,
+    present: Vec<(&'static str, Span)>,
+}
+
+impl Attrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Attrs> {
+        let mut parsed = Attrs::default();
+
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("value_decoder"))
+        {
+            attr.parse_nested_meta(|meta| {
+                let name = if meta.path.is_ident("rename") {
+                    parsed.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                    "rename"
+                } else if meta.path.is_ident("default") {
+                    parsed.default = true;
+                    "default"
+                } else if meta.path.is_ident("with") {
+                    parsed.with = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    "with"
+                } else if meta.path.is_ident("transparent") {
+                    parsed.transparent = true;
+                    "transparent"
+                } else if meta.path.is_ident("crate") {
+                    parsed.module = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    "crate"
+                } else {
+                    return Err(meta.error("unsupported value_decoder attribute"));
+                };
+                parsed.present.push((name, meta.path.span()));
+                Ok(())
+            })?;
+        }
+
+        Ok(parsed)
+    }
+
+    fn allow_only(&self, allowed: &[&str]) -> syn::Result<()> {
+        match self
+            .present
+            .iter()
+            .find(|(name, _)| !allowed.contains(name))
+        {
+            Some((name, span)) => Err(syn::Error::new(
+                *span,
+                format!("value_decoder attribute `{name}` is not supported here"),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let container_attrs = Attrs::parse(&input.attrs)?;
+    container_attrs.allow_only(&["transparent", "crate"])?;
+    let vd = match &container_attrs.module {
+        Some(module) => quote!(#module),
+        None => quote!(crate::decoder::value_decoder),
+    };
+
+    let body = match &input.data {
+        Data::Struct(data) if container_attrs.transparent => decode_transparent(&vd, &data.fields)?,
+        Data::Struct(data) => {
+            let fields = decode_fields(&vd, quote!(Self), &data.fields)?;
+            quote! {
+                let composite = #vd::decode_composite(value)?;
+                #fields
+            }
+        }
+        Data::Enum(data) => decode_enum(&vd, data)?,
+        Data::Union(_) => Err(syn::Error::new(
+            input.span(),
+            "ValueDecoder can't be derived for unions",
+        ))?,
+    };
+
+    let mut generics = input.generics.clone();
+    let type_params = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<Vec<_>>();
+    let where_clause = generics.make_where_clause();
+    for ident in type_params {
+        where_clause
+            .predicates
+            .push(parse_quote!(#ident: #vd::ValueDecoder<__T>));
+    }
+    let (_, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut impl_generics = generics.clone();
+    let lifetimes_count = impl_generics.lifetimes().count();
+    impl_generics
+        .params
+        .insert(lifetimes_count, GenericParam::Type(parse_quote!(__T)));
+    let (impl_generics, _, _) = impl_generics.split_for_impl();
+
+    let name = &input.ident;
+
+    Ok(quote! {
+        impl #impl_generics #vd::ValueDecoder<__T> for #name #ty_generics #where_clause {
+            fn decode(
+                value: ::scale_value::Value<__T>,
+            ) -> ::std::result::Result<Self, #vd::ValueDecoderError>
+            where
+                __T: ::std::fmt::Debug,
+            {
+                #body
+            }
+        }
+    })
+}
+
+fn decode_transparent(vd: &TokenStream2, fields: &Fields) -> syn::Result<TokenStream2> {
+    let field = match fields.iter().collect::<Vec<_>>()[..] {
+        [field] => field,
+        _ => Err(syn::Error::new(
+            fields.span(),
+            "transparent ValueDecoder requires exactly one field",
+        ))?,
+    };
+
+    let attrs = Attrs::parse(&field.attrs)?;
+    attrs.allow_only(&["with"])?;
+    let decode = match &attrs.with {
+        Some(with) => quote!(#with),
+        None => quote!(#vd::ValueDecoder::decode),
+    };
+
+    Ok(match &field.ident {
+        Some(ident) => quote!(Ok(Self { #ident: #decode(value)? })),
+        None => quote!(Ok(Self(#decode(value)?))),
+    })
+}
+
+/// Build `constructor` from the fields of `composite`
+fn decode_fields(
+    vd: &TokenStream2,
+    constructor: TokenStream2,
+    fields: &Fields,
+) -> syn::Result<TokenStream2> {
+    match fields {
+        Fields::Named(named) => {
+            let fields = named
+                .named
+                .iter()
+                .map(|field| {
+                    let attrs = Attrs::parse(&field.attrs)?;
+                    attrs.allow_only(&["rename", "default", "with"])?;
+
+                    let ident = field.ident.as_ref().unwrap();
+                    let field_name = attrs.rename.clone().unwrap_or_else(|| ident.to_string());
+
+                    let decode = match &attrs.with {
+                        Some(with) => quote!(#vd::get_field_with(#field_name, &mut record, #with)?),
+                        None => quote!(#vd::get_field(#field_name, &mut record)?),
+                    };
+                    let decode = if attrs.default {
+                        quote! {
+                            if record.contains_key(#field_name) {
+                                #decode
+                            } else {
+                                ::std::default::Default::default()
+                            }
+                        }
+                    } else {
+                        decode
+                    };
+
+                    Ok(quote!(#ident: #decode))
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            Ok(quote! {
+                let mut record = #vd::composite_to_record(composite)?;
+                Ok(#constructor { #(#fields),* })
+            })
+        }
+        Fields::Unnamed(unnamed) => {
+            let len = unnamed.unnamed.len();
+            let fields = unnamed
+                .unnamed
+                .iter()
+                .enumerate()
+                .map(|(index, field)| {
+                    let attrs = Attrs::parse(&field.attrs)?;
+                    attrs.allow_only(&["with"])?;
+
+                    Ok(match &attrs.with {
+                        Some(with) => quote!(#vd::get_element_with(#index, &mut elements, #with)?),
+                        None => quote!(#vd::get_element(#index, &mut elements)?),
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            Ok(quote! {
+                let mut elements = #vd::composite_to_elements(composite, #len)?;
+                Ok(#constructor(#(#fields),*))
+            })
+        }
+        Fields::Unit => Ok(quote! {
+            #vd::composite_to_elements(composite, 0)?;
+            Ok(#constructor)
+        }),
+    }
+}
+
+fn decode_enum(vd: &TokenStream2, data: &DataEnum) -> syn::Result<TokenStream2> {
+    let arms = data
+        .variants
+        .iter()
+        .map(|variant| {
+            let attrs = Attrs::parse(&variant.attrs)?;
+            attrs.allow_only(&["rename"])?;
+
+            let ident = &variant.ident;
+            let variant_name = attrs.rename.unwrap_or_else(|| ident.to_string());
+            let fields = decode_fields(vd, quote!(Self::#ident), &variant.fields)?;
+
+            Ok(quote! {
+                #variant_name => #vd::WithErrorSpan::add_error_span(
+                    (|| -> ::std::result::Result<Self, #vd::ValueDecoderError> { #fields })(),
+                    #variant_name,
+                ),
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        let (variant_name, composite) = #vd::decode_variant(value)?;
+        match &variant_name[..] {
+            #(#arms)*
+            _ => Err(#vd::ValueDecoderError::UnexpectedVariantName {
+                variant_name,
+                span: ::std::string::String::new(),
+            }),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand_str(input: &str) -> syn::Result<String> {
+        expand(syn::parse_str(input)?).map(|tokens| tokens.to_string())
+    }
+
+    /// Message and `(line, column)` of the error of an expansion
+    fn expand_error(input: &str) -> (String, (usize, usize)) {
+        let error = expand(syn::parse_str(input).unwrap()).unwrap_err();
+        let start = error.span().start();
+        (error.to_string(), (start.line, start.column))
+    }
+
+    #[test]
+    fn named_struct_reads_renamed_and_default_fields() {
+        let expanded = expand_str(
+            r#"struct Account {
+                #[value_decoder(rename = "free_balance")]
+                free: u128,
+                #[value_decoder(default)]
+                frozen: u128,
+            }"#,
+        )
+        .unwrap();
+
+        assert!(expanded.contains(r#"get_field ("free_balance" , & mut record)"#));
+        assert!(expanded.contains(r#"if record . contains_key ("frozen")"#));
+        assert!(expanded.contains("crate :: decoder :: value_decoder :: ValueDecoder < __T >"));
+    }
+
+    #[test]
+    fn with_and_transparent_call_the_decoder_function() {
+        let expanded = expand_str(
+            r#"#[value_decoder(transparent)]
+            struct Hash(#[value_decoder(with = "decode_hash")] [u8; 32]);"#,
+        )
+        .unwrap();
+
+        assert!(expanded.contains("Ok (Self (decode_hash (value) ?))"));
+        assert!(!expanded.contains("decode_composite"));
+    }
+
+    #[test]
+    fn crate_attribute_overrides_the_module_path() {
+        let expanded = expand_str(
+            r#"#[value_decoder(crate = "::submarine::value_decoder")]
+            enum Phase { Initialization, #[value_decoder(rename = "ApplyExtrinsic")] Apply(u32) }"#,
+        )
+        .unwrap();
+
+        assert!(expanded.contains(":: submarine :: value_decoder :: decode_variant (value)"));
+        assert!(expanded.contains(r#""ApplyExtrinsic" =>"#));
+        assert!(!expanded.contains("crate :: decoder"));
+    }
+
+    #[test]
+    fn unsupported_attribute_is_spanned_at_its_name() {
+        assert_eq!(
+            expand_error("struct Account {\n    #[value_decoder(skip)]\n    free: u128,\n}"),
+            ("unsupported value_decoder attribute".to_string(), (2, 20)),
+        );
+    }
+
+    #[test]
+    fn misplaced_attribute_is_spanned_at_its_name() {
+        assert_eq!(
+            expand_error("enum Phase {\n    #[value_decoder(default)]\n    Finalization,\n}"),
+            (
+                "value_decoder attribute `default` is not supported here".to_string(),
+                (2, 20)
+            ),
+        );
+        assert_eq!(
+            expand_error("struct Pair(\n    #[value_decoder(rename = \"a\")] u32,\n    u32,\n);"),
+            (
+                "value_decoder attribute `rename` is not supported here".to_string(),
+                (2, 20)
+            ),
+        );
+    }
+
+    #[test]
+    fn transparent_requires_a_single_field() {
+        let (message, _) = expand_error("#[value_decoder(transparent)]\nstruct Pair(u32, u32);");
+        assert_eq!(
+            message,
+            "transparent ValueDecoder requires exactly one field"
+        );
+    }
+
+    #[test]
+    fn unions_are_rejected() {
+        let (message, _) = expand_error("union Bits { a: u32, b: f32 }");
+        assert_eq!(message, "ValueDecoder can't be derived for unions");
+    }
+}